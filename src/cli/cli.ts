@@ -7,6 +7,7 @@ import {
   cmdQuery,
   cmdQueryAnswer,
   cmdConsolidate,
+  cmdMaintain,
   cmdConcepts,
   cmdBenchmark,
   cmdSyncConcepts,
@@ -67,6 +68,7 @@ async function main() {
     query  <question>    Retrieve relevant knowledge
     query-answer <question> Retrieve + generate grounded answer
     consolidate          Type weak connections once
+    maintain [--dry-run] Full maintenance pass (sweeps, consolidation, sync)
     concepts             Build concept abstractions and print all concepts
     sync-concepts        Sync concept embeddings to Qdrant (run after concepts)
     benchmark            Run benchmark on fixed queries
@@ -112,6 +114,11 @@ async function main() {
       break;
     }
 
+    case 'maintain': {
+      await cmdMaintain(args.includes('--dry-run'));
+      break;
+    }
+
     case 'concepts': {
       await cmdConcepts();
       break;
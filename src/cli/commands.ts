@@ -5,7 +5,7 @@ import chokidar from 'chokidar';
 import { db } from '../db';
 import { ingest, ingestText } from '../ingest';
 import { retrieve } from '../retrieve';
-import { consolidateAll, abstractConcepts } from '../consolidate';
+import { consolidateAll, runMaintenance, previewMaintenance, abstractConcepts } from '../consolidate';
 import { syncConceptEmbeddings } from '../concepts/sync';
 import { queryAnswer } from '../answer/query';
 import { parseUrl } from '../ingest/parser';
@@ -251,6 +251,11 @@ export async function cmdConsolidate(): Promise<void> {
   await consolidateAll();
 }
 
+export async function cmdMaintain(dryRun: boolean = false): Promise<void> {
+  const stats = dryRun ? await previewMaintenance() : await runMaintenance();
+  console.log(JSON.stringify(stats, null, 2));
+}
+
 export async function cmdConcepts(): Promise<void> {
   await abstractConcepts();
 
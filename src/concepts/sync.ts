@@ -2,6 +2,7 @@
 // Only re-embeds concepts whose `version` has changed since last sync.
 
 import { db, qdrant, CONCEPT_COLLECTION } from '../db';
+import { withStoreRetry } from '../db/retry';
 import { embedBatch } from '../embed';
 import { EMBED_BATCH_SIZE, DEBUG_PERF } from '../config';
 
@@ -105,7 +106,7 @@ export async function syncConceptEmbeddings(limit?: number): Promise<{ synced: n
     });
 
     try {
-      await qdrant.upsert(CONCEPT_COLLECTION, { points });
+      await withStoreRetry(() => qdrant.upsert(CONCEPT_COLLECTION, { points }));
     } catch (error) {
       const msg = error instanceof Error ? error.message : String(error);
       console.warn(`⚠️  conceptSync Qdrant upsert failed: ${msg}`);
@@ -25,6 +25,7 @@ export const AUDIO_CHUNK_MINUTES = Number.parseInt(process.env.AUDIO_CHUNK_MINUT
 export const INCLUDE_CONCEPTS = process.env.INCLUDE_CONCEPTS === 'true';
 export const ENABLE_LEARNING_WEIGHTS = process.env.ENABLE_LEARNING_WEIGHTS !== 'false'; // on by default
 export const ENABLE_CONCEPT_VALIDATION = process.env.ENABLE_CONCEPT_VALIDATION !== 'false';
+export const ACCESS_REFRESHES_EDGES = process.env.ACCESS_REFRESHES_EDGES !== 'false'; // recall resets incident edges' decay clock
 export const DEBUG_PERF = process.env.DEBUG_PERF === 'true';
 export const DEBUG_CHUNKS = process.env.DEBUG_CHUNKS === 'true';
 
@@ -45,17 +46,105 @@ export const LLM_TIMEOUT_MS = Number(process.env.LLM_TIMEOUT_MS ?? '120000');
 // ── Consolidation tuning ───────────────────────────────────────────────────
 export const CONSOLIDATION_BATCH_SIZE = Number(process.env.CONSOLIDATION_BATCH_SIZE ?? '10');
 export const CONSOLIDATION_INTERVAL_MS = Number(process.env.CONSOLIDATION_INTERVAL_MS ?? '30000');
+export const CONSOLIDATION_DEBOUNCE_MS = Number(process.env.CONSOLIDATION_DEBOUNCE_MS ?? '2000');
+// Ordered, comma-separated phases of a one-shot consolidateAll run; empty = the default sequence
+export const CONSOLIDATION_PHASES = process.env.CONSOLIDATION_PHASES ?? '';
+// Edges whose endpoints were both accessed within this window are "in focus" and skip decay; 0 = off
+export const DECAY_FOCUS_WINDOW_MS = Number(process.env.DECAY_FOCUS_WINDOW_MS ?? '0');
+// Consolidation prunes edges not reinforced for this many days whose weight is
+// still below STALE_EDGE_PRUNE_WEIGHT; 0 = never prune.
+export const STALE_EDGE_PRUNE_DAYS = Number(process.env.STALE_EDGE_PRUNE_DAYS ?? '0');
+export const STALE_EDGE_PRUNE_WEIGHT = Number(process.env.STALE_EDGE_PRUNE_WEIGHT ?? '0.15');
+
+// ── SQLite durability ──────────────────────────────────────────────────────
+// PRAGMA synchronous under WAL: off, normal (default), full or extra. Unknown
+// values fall back to normal. A positive checkpoint interval also folds the
+// WAL back into the main file on a timer instead of only every 1000 pages.
+export const SQLITE_SYNCHRONOUS = (process.env.SQLITE_SYNCHRONOUS ?? 'normal').trim().toLowerCase();
+export const SQLITE_CHECKPOINT_INTERVAL_MS = Number(process.env.SQLITE_CHECKPOINT_INTERVAL_MS ?? '0');
+
+// ── Store retries ───────────────────────────────────────────────────────────
+// Qdrant writes (upserts and deletes) are retried this many times in total on transient errors,
+// waiting STORE_RETRY_BASE_MS before the second try and doubling after that.
+export const STORE_RETRY_ATTEMPTS = Number(process.env.STORE_RETRY_ATTEMPTS ?? '3');
+export const STORE_RETRY_BASE_MS = Number(process.env.STORE_RETRY_BASE_MS ?? '200');
+
+// ── gRPC transport ──────────────────────────────────────────────────────────
+// HTTP/2 PING interval and how long to wait for the ack before dropping the
+// connection. Idle connections (no open calls) are closed after
+// GRPC_MAX_CONNECTION_IDLE_MS; 0 keeps them. 0 concurrent streams = unlimited.
+export const GRPC_KEEPALIVE_TIME_MS = Number(process.env.GRPC_KEEPALIVE_TIME_MS ?? '7200000');
+export const GRPC_KEEPALIVE_TIMEOUT_MS = Number(process.env.GRPC_KEEPALIVE_TIMEOUT_MS ?? '20000');
+export const GRPC_MAX_CONNECTION_IDLE_MS = Number(process.env.GRPC_MAX_CONNECTION_IDLE_MS ?? '0');
+export const GRPC_MAX_CONCURRENT_STREAMS = Number(process.env.GRPC_MAX_CONCURRENT_STREAMS ?? '0');
+// Server-side limit for unary calls other than Ingest and DeleteChunks, answered with
+// DEADLINE_EXCEEDED when reached. Off (0) by default: a timed-out Query still runs
+// to the end, so its access counts and Hebbian updates land anyway.
+export const GRPC_UNARY_TIMEOUT_MS = Number(process.env.GRPC_UNARY_TIMEOUT_MS ?? '0');
+
+// ── Shutdown ────────────────────────────────────────────────────────────────
+// On SIGINT/SIGTERM, how long in-flight HTTP requests and gRPC calls may run
+// before their connections are dropped.
+export const SHUTDOWN_GRACE_MS = Number(process.env.SHUTDOWN_GRACE_MS ?? '5000');
+
+// ── Live stats ──────────────────────────────────────────────────────────────
+// Default push interval of GET /api/stats/stream (clients may pass intervalMs).
+export const STATS_STREAM_INTERVAL_MS = Number(process.env.STATS_STREAM_INTERVAL_MS ?? '5000');
+
+// ── Graph export ────────────────────────────────────────────────────────────
+// Weight encoding for ExportGraph streams: f64 (exact) or u16 fixed point
+// (`weight_q16`, error at most 1/131070, roughly 8e-6).
+export const EXPORT_WEIGHT_PRECISION: 'f64' | 'u16' = process.env.EXPORT_WEIGHT_PRECISION === 'u16' ? 'u16' : 'f64';
+
+// ── Capacity ────────────────────────────────────────────────────────────────
+// Maximum chunks per memory database (0 = unlimited). When full, ingest either
+// rejects new chunks or evicts the least-accessed, oldest chunks first.
+export const MAX_CHUNKS = Number(process.env.MAX_CHUNKS ?? '0');
+export const MAX_CHUNKS_POLICY: 'reject' | 'evict' = process.env.MAX_CHUNKS_POLICY === 'evict' ? 'evict' : 'reject';
+// Upper bound on chunks evicted to admit a single batch (0 = no bound).
+export const MAX_EVICTIONS_PER_BATCH = Number(process.env.MAX_EVICTIONS_PER_BATCH ?? '100');
+// Chunks with at least this many connections are never evicted (0 = no protection).
+export const EVICTION_PROTECT_DEGREE = Number(process.env.EVICTION_PROTECT_DEGREE ?? '0');
+// Maximum connections touching one chunk, in or out (0 = unlimited). A new edge
+// past the cap is either not created or replaces the weakest unfrozen edge.
+export const MAX_EDGES_PER_CHUNK = Number(process.env.MAX_EDGES_PER_CHUNK ?? '0');
+export const MAX_EDGES_POLICY: 'reject' | 'evict_weakest' = process.env.MAX_EDGES_POLICY === 'evict_weakest' ? 'evict_weakest' : 'reject';
 
 // ── Chunk sizing ────────────────────────────────────────────────────────────
 export const CHUNK_TARGET_MIN_TOKENS = Number(process.env.CHUNK_TARGET_MIN_TOKENS ?? '350');
 export const CHUNK_TARGET_MAX_TOKENS = Number(process.env.CHUNK_TARGET_MAX_TOKENS ?? '500');
 export const CHUNK_OVERLAP_TOKENS = Number(process.env.CHUNK_OVERLAP_TOKENS ?? '40');
 
+// Longest chunk text accepted, in characters (0 = unlimited). Over the limit a
+// chunk is either rejected (failing the ingest) or truncated to the limit.
+export const MAX_CHUNK_CHARS = Number(process.env.MAX_CHUNK_CHARS ?? '0');
+export const MAX_CHUNK_CHARS_POLICY: 'reject' | 'truncate' = process.env.MAX_CHUNK_CHARS_POLICY === 'truncate' ? 'truncate' : 'reject';
+
 // ── Retrieval tuning ────────────────────────────────────────────────────────
 // Minimum blended score for a result to be returned. Set to 0.35 as a middle
 // ground: 0.20 (previous hardcoded value) let in too much noise; 0.40 was the
 // documented target but too aggressive before re-ranking was operational.
 export const MIN_SCORE = Number(process.env.MIN_SCORE ?? '0.35');
+// Entries kept by the opt-in recall cache (RetrieveOptions.useCache). 0 disables it.
+export const RECALL_CACHE_SIZE = Number(process.env.RECALL_CACHE_SIZE ?? '100');
+// Let the recall cache resize itself between these bounds: it doubles while the
+// hit rate stays low and the cache is full, and halves when the heap runs short.
+export const RECALL_CACHE_AUTOTUNE = process.env.RECALL_CACHE_AUTOTUNE === 'true';
+export const RECALL_CACHE_MIN_SIZE = Number(process.env.RECALL_CACHE_MIN_SIZE ?? '50');
+export const RECALL_CACHE_MAX_SIZE = Number(process.env.RECALL_CACHE_MAX_SIZE ?? '1000');
+// Largest max_hops / exact_depth and top_k a client may request; larger values are rejected.
+export const MAX_QUERY_HOPS = Number(process.env.MAX_QUERY_HOPS ?? '5');
+export const MAX_QUERY_TOP_K = Number(process.env.MAX_QUERY_TOP_K ?? '100');
+// When recall writes chunk access counts: every | debounced | never.
+export const ACCESS_WRITE_POLICY: 'every' | 'debounced' | 'never' =
+  process.env.ACCESS_WRITE_POLICY === 'debounced' || process.env.ACCESS_WRITE_POLICY === 'never'
+    ? process.env.ACCESS_WRITE_POLICY
+    : 'every';
+export const ACCESS_FLUSH_INTERVAL_MS = Number(process.env.ACCESS_FLUSH_INTERVAL_MS ?? '5000');
+// Upper bound on queued nodes during graph expansion; the weakest are dropped past it. 0 = unbounded.
+export const MAX_RECALL_FRONTIER = Number(process.env.MAX_RECALL_FRONTIER ?? '5000');
+// A node already reached is expanded again only if its score improves by more than this.
+export const RECALL_IMPROVEMENT_EPSILON = Number(process.env.RECALL_IMPROVEMENT_EPSILON ?? '0');
 
 // ── Audio overviews ──────────────────────────────────────────────────────────
 export const OVERVIEWS_DIR = process.env.OVERVIEWS_DIR ??
@@ -184,13 +184,19 @@ function splitCluster(cluster: string[], minWeight: number): string[][] {
 
 // ── Concept abstraction ────────────────────────────────────────────────────
 
-export async function abstractConcepts(): Promise<void> {
+export interface AbstractionStats {
+  created: number;
+  refreshed: number;
+  skipped: number;
+}
+
+export async function abstractConcepts(): Promise<AbstractionStats> {
   const s = S();
   const clusters = buildClusters(CONCEPT_EDGE_MIN_WEIGHT);
 
   if (clusters.length === 0) {
     console.log('💡 No clusters found for abstraction');
-    return;
+    return { created: 0, refreshed: 0, skipped: 0 };
   }
 
   const conceptRows = s.selectConcepts.all() as ConceptRow[];
@@ -297,6 +303,7 @@ export async function abstractConcepts(): Promise<void> {
 
   const total = created + refreshed;
   console.log(`💡 Abstracted ${total} concepts (${created} new, ${refreshed} refreshed, ${skipped} skipped)`);
+  return { created, refreshed, skipped };
 }
 
 /**
@@ -0,0 +1,27 @@
+// src/consolidate/debounce.ts — Coalesce bursts of on-demand consolidation requests
+
+/**
+ * Wrap `run` so every call within `windowMs` of the first one shares a single
+ * execution and resolves with its result. A call that arrives while a run is
+ * already in progress opens the next window, which starts only after the
+ * current run settles — runs never overlap.
+ */
+export function createDebouncedRunner<T>(run: () => Promise<T>, windowMs: number): () => Promise<T> {
+  let pending: Promise<T> | null = null;
+  let previous: Promise<unknown> = Promise.resolve();
+
+  return () => {
+    if (pending) return pending;
+
+    const wait = new Promise<void>(resolve => setTimeout(resolve, Math.max(0, windowMs)));
+    const next = Promise.all([wait, previous.catch(() => undefined)]).then(() => {
+      // Requests from here on belong to the next batch
+      pending = null;
+      return run();
+    });
+
+    pending = next;
+    previous = next;
+    return next;
+  };
+}
@@ -81,13 +81,13 @@ export function S(): Stmts {
 
     // Cycle 2: batch of untyped edges
     selectUntypedEdges: db.prepare(
-      'SELECT edge_id, source_chunk, target_chunk, avg_sim, support_count, contradict_count, seen_count FROM connections WHERE relationship = \'related_to\' LIMIT ?'
+      'SELECT edge_id, source_chunk, target_chunk, avg_sim, support_count, contradict_count, seen_count FROM connections WHERE relationship = \'related_to\' AND COALESCE(frozen, 0) = 0 LIMIT ?'
     ),
     selectAllUntypedSources: db.prepare(
-      'SELECT DISTINCT source_chunk FROM connections WHERE relationship = \'related_to\''
+      'SELECT DISTINCT source_chunk FROM connections WHERE relationship = \'related_to\' AND COALESCE(frozen, 0) = 0'
     ),
     selectUntypedBySource: db.prepare(
-      'SELECT edge_id, source_chunk, target_chunk, avg_sim, support_count, contradict_count, seen_count FROM connections WHERE source_chunk = ? AND relationship = \'related_to\' ORDER BY weight DESC, confidence DESC, created_at DESC'
+      'SELECT edge_id, source_chunk, target_chunk, avg_sim, support_count, contradict_count, seen_count FROM connections WHERE source_chunk = ? AND relationship = \'related_to\' AND COALESCE(frozen, 0) = 0 ORDER BY weight DESC, confidence DESC, created_at DESC'
     ),
 
     // Update after classification
@@ -100,7 +100,7 @@ export function S(): Stmts {
     // Cycle 3: reinforce/decay
     selectHighlyAccessedChunks: db.prepare('SELECT chunk_id, access_count FROM chunks WHERE access_count > ?'),
     selectOutgoingEdges: db.prepare(
-      'SELECT edge_id, weight, confidence, avg_sim, source_chunk FROM connections WHERE source_chunk = ?'
+      'SELECT edge_id, weight, confidence, avg_sim, source_chunk FROM connections WHERE source_chunk = ? AND COALESCE(frozen, 0) = 0'
     ),
     updateEdgeReinforce: db.prepare('UPDATE connections SET weight = ?, last_reinforced = ? WHERE edge_id = ?'),
     selectEdgesToDecay: db.prepare(
@@ -1,13 +1,18 @@
 // src/consolidate/index.ts — Public API for consolidation
 import { cycle2ClassifyBatch, consolidateChunk } from './classify';
-import { reinforceConnections, decayConnections, hebbianStrengthen } from './weights';
+import { reinforceConnections, decayConnections, pruneStaleConnections, hebbianStrengthen } from './weights';
 import { abstractConcepts } from './concepts';
+import type { AbstractionStats } from './concepts';
 import { clusterIntoConcepts } from './cluster';
 import { syncConceptEmbeddings } from '../concepts/sync';
-import { CONSOLIDATION_BATCH_SIZE, CONSOLIDATION_INTERVAL_MS } from '../config';
+import { CONSOLIDATION_BATCH_SIZE, CONSOLIDATION_INTERVAL_MS, CONSOLIDATION_DEBOUNCE_MS, CONSOLIDATION_PHASES } from '../config';
 import { trainAssociativeMemory } from '../associative';
 import { db, DEFAULT_MEMORY_DB } from '../db';
+import { sweepExpiredChunks, listExpiredChunks, deleteChunkRows } from '../db/chunks';
+import { sweepDeadConnections } from '../db/connections';
+import { withMaintenanceLock, isMaintenanceRunning } from '../db/maintenance';
 import { S } from './helpers';
+import { createDebouncedRunner } from './debounce';
 
 const HEBBIAN_INTERVAL_MS = 5 * 60 * 1000;
 const ASSOCIATIVE_TRAIN_INTERVAL_MS = 10 * 60 * 1000;
@@ -15,8 +20,9 @@ const CONCEPT_CLUSTER_INTERVAL_MS = 24 * 60 * 60 * 1000;
 
 // Re-export for external consumers
 export { cycle2ClassifyBatch, consolidateChunk } from './classify';
-export { reinforceConnections, decayConnections, hebbianStrengthen } from './weights';
+export { reinforceConnections, decayConnections, pruneStaleConnections, hebbianStrengthen, hebbianWeight, buildSimilarityEdges } from './weights';
 export { abstractConcepts } from './concepts';
+export type { AbstractionStats } from './concepts';
 export { clusterIntoConcepts } from './cluster';
 
 function isConceptTableEmpty(database: string = DEFAULT_MEMORY_DB): boolean {
@@ -38,10 +44,19 @@ export function runConsolidationWorker(intervalMs: number = CONSOLIDATION_INTERV
   let lastConceptClusterRun = 0;
 
   const tick = async () => {
-    if (isRunning) return;
+    // Skip rather than queue behind a one-shot run or clear; the next tick retries
+    if (isRunning || isMaintenanceRunning()) return;
     isRunning = true;
+    await withMaintenanceLock(runTick);
+  };
 
+  const runTick = async () => {
     try {
+      const expired = await sweepExpiredChunks();
+      if (expired > 0) {
+        console.log(`🧹 Swept ${expired} expired chunks`);
+      }
+
       // Cycle 2: classify batch of untyped edges
       await cycle2ClassifyBatch(CONSOLIDATION_BATCH_SIZE);
 
@@ -79,34 +94,234 @@ export function runConsolidationWorker(intervalMs: number = CONSOLIDATION_INTERV
   setInterval(() => void tick(), intervalMs);
 }
 
+export const CONSOLIDATION_PHASE_NAMES = [
+  'classify', 'reinforce', 'decay', 'prune', 'hebbian', 'cluster', 'train', 'abstract', 'sync',
+] as const;
+export type ConsolidationPhase = typeof CONSOLIDATION_PHASE_NAMES[number];
+
+/**
+ * Parse a comma-separated phase list such as `decay,prune,hebbian`. Order is
+ * kept and repeats are allowed; an empty list means the default sequence.
+ */
+export function parseConsolidationPhases(raw: string): ConsolidationPhase[] {
+  const names = raw.split(',').map(name => name.trim()).filter(Boolean);
+  if (names.length === 0) return [...CONSOLIDATION_PHASE_NAMES];
+
+  return names.map(name => {
+    if (!(CONSOLIDATION_PHASE_NAMES as readonly string[]).includes(name)) {
+      throw new Error(`Unknown consolidation phase "${name}" (expected ${CONSOLIDATION_PHASE_NAMES.join(', ')})`);
+    }
+    return name as ConsolidationPhase;
+  });
+}
+
 /**
- * One-shot consolidation (CLI command).
+ * One-shot consolidation (CLI command). Runs under the maintenance lock, so it
+ * never interleaves with a worker tick or a database clear. `phases` picks and
+ * orders the steps (CONSOLIDATION_PHASES by default), e.g. a light
+ * `decay,prune` pass versus the full sequence.
  */
-export async function consolidateAll(): Promise<void> {
+export function consolidateAll(phases: ConsolidationPhase[] = parseConsolidationPhases(CONSOLIDATION_PHASES)): Promise<void> {
+  return withMaintenanceLock(() => runConsolidateAll(phases));
+}
+
+export interface MaintenanceOptions {
+  phases?: ConsolidationPhase[];   // consolidation steps; default CONSOLIDATION_PHASES
+  sweepExpired?: boolean;          // delete chunks past expires_at first; default true
+  sweepDeadEdges?: boolean;        // delete edges left at the weight floor; default true
+}
+
+export interface MaintenanceStats {
+  phases: ConsolidationPhase[];
+  expired_chunks: number;
+  reinforced: number;
+  decayed: number;
+  pruned: number;
+  hebbian_updates: number;
+  concepts_clustered: number;
+  sources_classified: number;      // chunks whose untyped edges were classified
+  associative_samples: number;     // co-access samples the associative model trained on
+  concepts_created: number;
+  concepts_refreshed: number;
+  dead_edges_swept: number;
+  concepts_synced: number;
+  duration_ms: number;
+  dry_run?: boolean;                       // set by previewMaintenance
+  skipped_phases?: ConsolidationPhase[];   // phases a dry run cannot simulate
+}
+
+/** Zeroed stats for a maintenance pass over `phases`. */
+function emptyMaintenanceStats(phases: ConsolidationPhase[]): MaintenanceStats {
+  return {
+    phases,
+    expired_chunks: 0,
+    reinforced: 0,
+    decayed: 0,
+    pruned: 0,
+    hebbian_updates: 0,
+    concepts_clustered: 0,
+    sources_classified: 0,
+    associative_samples: 0,
+    concepts_created: 0,
+    concepts_refreshed: 0,
+    dead_edges_swept: 0,
+    concepts_synced: 0,
+    duration_ms: 0,
+  };
+}
+
+/**
+ * Full maintenance pass in one call, e.g. from a nightly cron: expiry sweep,
+ * the consolidation phases, a dead-edge sweep, then concept sync last so the
+ * vector store sees the final graph. Runs under one hold of the maintenance
+ * lock and reports what every step did.
+ */
+export function runMaintenance(options: MaintenanceOptions = {}): Promise<MaintenanceStats> {
+  const phases = options.phases ?? parseConsolidationPhases(CONSOLIDATION_PHASES);
+
+  return withMaintenanceLock(async () => {
+    const startedAt = Date.now();
+    const stats = emptyMaintenanceStats(phases);
+
+    if (options.sweepExpired ?? true) {
+      stats.expired_chunks = await sweepExpiredChunks();
+    }
+
+    for (const phase of phases.filter(name => name !== 'sync')) {
+      const result = await PHASE_RUNNERS[phase]();
+      const count = typeof result === 'number' ? result : 0;
+      if (phase === 'reinforce') stats.reinforced += count;
+      else if (phase === 'decay') stats.decayed += count;
+      else if (phase === 'prune') stats.pruned += count;
+      else if (phase === 'hebbian') stats.hebbian_updates += count;
+      else if (phase === 'cluster') stats.concepts_clustered += count;
+      else if (phase === 'classify') stats.sources_classified += count;
+      else if (phase === 'train') {
+        const training = result as Awaited<ReturnType<typeof trainAssociativeMemory>>;
+        if (training.trained) stats.associative_samples += training.samples;
+      } else if (phase === 'abstract') {
+        const abstraction = result as AbstractionStats;
+        stats.concepts_created += abstraction.created;
+        stats.concepts_refreshed += abstraction.refreshed;
+      }
+    }
+
+    if (options.sweepDeadEdges ?? true) {
+      stats.dead_edges_swept = sweepDeadConnections();
+    }
+
+    if (phases.includes('sync')) {
+      stats.concepts_synced = (await syncConceptEmbeddings()).synced;
+    }
+
+    stats.duration_ms = Date.now() - startedAt;
+    console.log(`🧰 Maintenance done in ${stats.duration_ms}ms`);
+    return stats;
+  });
+}
+
+// Phases that only touch SQLite synchronously, so a rolled-back transaction
+// can run them for real and report what they would have done
+const PREVIEW_PHASES = new Set<ConsolidationPhase>(['reinforce', 'decay', 'prune']);
+
+class PreviewRollback extends Error {}
+
+/**
+ * Dry run of `runMaintenance`: the expiry sweep, the reinforce, decay and
+ * prune phases and the dead-edge sweep run through the very same code inside
+ * a SQLite transaction that is then rolled back, so the counts are exactly
+ * what a real pass would report and nothing changes. Phases that call out
+ * to the embedder, the LLM or Qdrant cannot be rolled back; they are left
+ * out and listed in `skipped_phases`, and when any are skipped the real
+ * pass may differ.
+ */
+export function previewMaintenance(options: MaintenanceOptions = {}): Promise<MaintenanceStats> {
+  const phases = options.phases ?? parseConsolidationPhases(CONSOLIDATION_PHASES);
+
+  return withMaintenanceLock(async () => {
+    const startedAt = Date.now();
+    const stats: MaintenanceStats = {
+      ...emptyMaintenanceStats(phases),
+      dry_run: true,
+      skipped_phases: [...new Set(phases.filter(phase => !PREVIEW_PHASES.has(phase)))],
+    };
+
+    try {
+      db.transaction(() => {
+        if (options.sweepExpired ?? true) {
+          for (const [databaseId, ids] of listExpiredChunks()) {
+            stats.expired_chunks += deleteChunkRows(ids, databaseId);
+          }
+        }
+
+        for (const phase of phases) {
+          if (phase === 'reinforce') stats.reinforced += reinforceConnections();
+          else if (phase === 'decay') stats.decayed += decayConnections();
+          else if (phase === 'prune') stats.pruned += pruneStaleConnections();
+        }
+
+        if (options.sweepDeadEdges ?? true) {
+          stats.dead_edges_swept = sweepDeadConnections();
+        }
+
+        throw new PreviewRollback();
+      })();
+    } catch (error) {
+      if (!(error instanceof PreviewRollback)) throw error;
+    }
+
+    stats.duration_ms = Date.now() - startedAt;
+    console.log(`🔍 Maintenance dry run done in ${stats.duration_ms}ms (nothing changed)`);
+    return stats;
+  });
+}
+
+// Returns how many source chunks had their untyped edges classified
+async function classifyAllUntyped(): Promise<number> {
   const s = S();
   const rows = s.selectAllUntypedSources.all() as Array<{ source_chunk: string }>;
 
   if (rows.length === 0) {
     console.log('ℹ️  No untyped connections to consolidate.');
-  } else {
-    for (let i = 0; i < rows.length; i++) {
-      console.log(`Consolidating chunk ${i + 1} of ${rows.length}`);
-      try {
-        await consolidateChunk(rows[i].source_chunk);
-      } catch (error) {
-        const msg = error instanceof Error ? error.message : 'Unknown';
-        console.warn(`⚠️  Failed consolidating chunk ${rows[i].source_chunk}: ${msg}`);
-      }
+    return 0;
+  }
+
+  let classified = 0;
+
+  for (let i = 0; i < rows.length; i++) {
+    console.log(`Consolidating chunk ${i + 1} of ${rows.length}`);
+    try {
+      await consolidateChunk(rows[i].source_chunk);
+      classified++;
+    } catch (error) {
+      const msg = error instanceof Error ? error.message : 'Unknown';
+      console.warn(`⚠️  Failed consolidating chunk ${rows[i].source_chunk}: ${msg}`);
     }
   }
+  return classified;
+}
+
+const PHASE_RUNNERS: Record<ConsolidationPhase, () => unknown> = {
+  classify: classifyAllUntyped,
+  reinforce: reinforceConnections,
+  decay: () => decayConnections(),
+  prune: () => pruneStaleConnections(),
+  hebbian: () => hebbianStrengthen(0),
+  cluster: async () => (isConceptTableEmpty() ? clusterIntoConcepts() : 0),
+  train: () => trainAssociativeMemory(0),
+  abstract: abstractConcepts,
+  sync: syncConceptEmbeddings,
+};
 
-  reinforceConnections();
-  decayConnections();
-  await hebbianStrengthen(0);
-  if (isConceptTableEmpty()) {
-    await clusterIntoConcepts();
+async function runConsolidateAll(phases: ConsolidationPhase[]): Promise<void> {
+  for (const phase of phases) {
+    await PHASE_RUNNERS[phase]();
   }
-  await trainAssociativeMemory(0);
-  await abstractConcepts();
-  await syncConceptEmbeddings();
 }
+
+/**
+ * On-demand consolidation for API callers. Requests arriving within
+ * CONSOLIDATION_DEBOUNCE_MS are coalesced into one `consolidateAll` run and
+ * every caller awaits that same run.
+ */
+export const requestConsolidation = createDebouncedRunner(consolidateAll, CONSOLIDATION_DEBOUNCE_MS);
@@ -1,7 +1,10 @@
 // src/consolidate/weights.ts — Cycle 3: reinforce & decay connections
 import { randomUUID } from 'crypto';
-import { db, DEFAULT_MEMORY_DB } from '../db';
+import { db, DEFAULT_MEMORY_DB, hasTextIndex } from '../db';
 import { embed } from '../embed';
+import { contentSimilarity, contentTokens } from '../retrieve/diversity';
+import { insertWithinEdgeLimit } from '../db/connections';
+import { DECAY_FOCUS_WINDOW_MS, STALE_EDGE_PRUNE_DAYS, STALE_EDGE_PRUNE_WEIGHT } from '../config';
 import {
   S,
   REINFORCE_ACCESS_THRESHOLD,
@@ -48,26 +51,26 @@ function parseChunkIds(raw: string): string[] {
   }
 }
 
-function getConnection(a: string, b: string, database: string): { edge_id: string; weight: number } | null {
+function getConnection(a: string, b: string, database: string): { edge_id: string; weight: number; frozen: number } | null {
   const row = db.prepare(`
-    SELECT edge_id, weight
+    SELECT edge_id, weight, COALESCE(frozen, 0) AS frozen
     FROM connections
     WHERE source_chunk = ?
       AND target_chunk = ?
       AND database_id = ?
     LIMIT 1
-  `).get(a, b, database) as { edge_id: string; weight: number } | undefined;
+  `).get(a, b, database) as { edge_id: string; weight: number; frozen: number } | undefined;
 
   if (row) return row;
 
   const reverse = db.prepare(`
-    SELECT edge_id, weight
+    SELECT edge_id, weight, COALESCE(frozen, 0) AS frozen
     FROM connections
     WHERE source_chunk = ?
       AND target_chunk = ?
       AND database_id = ?
     LIMIT 1
-  `).get(b, a, database) as { edge_id: string; weight: number } | undefined;
+  `).get(b, a, database) as { edge_id: string; weight: number; frozen: number } | undefined;
 
   return reverse ?? null;
 }
@@ -95,6 +98,16 @@ async function chunkSimilarity(a: string, b: string, database: string, cache: Ma
   return cosineSimilarity(vecA, vecB);
 }
 
+/**
+ * Weight after `count` Hebbian steps of w += rate * (1 - w), in closed form,
+ * so a pair co-accessed several times in one batch is updated in one write.
+ */
+export function hebbianWeight(currentWeight: number, count: number, rate: number = HEBBIAN_RATE): number {
+  return 1 - (1 - currentWeight) * Math.pow(1 - rate, Math.max(0, count));
+}
+
+type CoAccessPair = { a: string; b: string; database: string; count: number };
+
 export async function hebbianStrengthen(since: number): Promise<number> {
   const rows = db.prepare(`
     SELECT event_id, chunk_ids, query_hash, query_embedding, timestamp, database_id
@@ -105,60 +118,73 @@ export async function hebbianStrengthen(since: number): Promise<number> {
 
   if (rows.length === 0) return 0;
 
-  let updates = 0;
-  const embeddingCache = new Map<string, number[]>();
-  const now = new Date().toISOString();
-
+  // Collapse repeated co-access of the same pair so its edge moves by the
+  // full count (weight and access_count) in a single update.
+  const pairs = new Map<string, CoAccessPair>();
   for (const event of rows) {
     const database = event.database_id || DEFAULT_MEMORY_DB;
-    const ids = parseChunkIds(event.chunk_ids);
-
-    for (const [a, b] of chunkPairs(ids)) {
-      const existing = getConnection(a, b, database);
+    for (const [a, b] of chunkPairs(parseChunkIds(event.chunk_ids))) {
+      const key = `${database}|${[a, b].sort().join('|')}`;
+      const existing = pairs.get(key);
       if (existing) {
-        const currentWeight = Number.isFinite(existing.weight) ? existing.weight : MIN_CONNECTION_WEIGHT;
-        const nextWeight = clamp(
-          currentWeight + HEBBIAN_RATE * (1 - currentWeight),
-          MIN_CONNECTION_WEIGHT,
-          MAX_CONNECTION_WEIGHT,
-        );
-
-        db.prepare(`
-          UPDATE connections
-          SET weight = ?,
-              last_reinforced = ?,
-              access_count = COALESCE(access_count, 0) + 1
-          WHERE edge_id = ?
-        `).run(nextWeight, now, existing.edge_id);
-        updates++;
-        continue;
+        existing.count++;
+      } else {
+        pairs.set(key, { a, b, database, count: 1 });
       }
+    }
+  }
 
-      const sim = await chunkSimilarity(a, b, database, embeddingCache);
-      if (sim <= 0.4) continue;
+  let updates = 0;
+  const embeddingCache = new Map<string, number[]>();
+  const now = new Date().toISOString();
 
-      db.prepare(`
-        INSERT OR IGNORE INTO connections (
-          edge_id, source_chunk, target_chunk, relationship, weight, confidence,
-          created_at, last_reinforced, avg_sim, seen_count, last_seen, database_id, access_count
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-      `).run(
-        randomUUID(),
-        a,
-        b,
-        'co_accessed',
-        HEBBIAN_RATE,
-        0.5,
-        now,
-        now,
-        sim,
-        1,
-        now,
-        database,
-        1,
+  for (const { a, b, database, count } of pairs.values()) {
+    const existing = getConnection(a, b, database);
+    if (existing?.frozen) continue;
+    if (existing) {
+      const currentWeight = Number.isFinite(existing.weight) ? existing.weight : MIN_CONNECTION_WEIGHT;
+      const nextWeight = clamp(
+        hebbianWeight(currentWeight, count),
+        MIN_CONNECTION_WEIGHT,
+        MAX_CONNECTION_WEIGHT,
       );
+
+      db.prepare(`
+        UPDATE connections
+        SET weight = ?,
+            last_reinforced = ?,
+            access_count = COALESCE(access_count, 0) + ?
+        WHERE edge_id = ?
+      `).run(nextWeight, now, count, existing.edge_id);
       updates++;
+      continue;
     }
+
+    const sim = await chunkSimilarity(a, b, database, embeddingCache);
+    if (sim <= 0.4) continue;
+
+    const inserted = insertWithinEdgeLimit(a, b, () => db.prepare(`
+      INSERT OR IGNORE INTO connections (
+        edge_id, source_chunk, target_chunk, relationship, weight, confidence,
+        created_at, last_reinforced, avg_sim, seen_count, last_seen, database_id, access_count
+      ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+    `).run(
+      randomUUID(),
+      a,
+      b,
+      'co_accessed',
+      hebbianWeight(0, count),
+      0.5,
+      now,
+      now,
+      sim,
+      1,
+      now,
+      database,
+      count,
+    ).changes, database);
+    if (inserted === 0) continue;
+    updates++;
   }
 
   if (updates > 0) {
@@ -172,8 +198,9 @@ export async function hebbianStrengthen(since: number): Promise<number> {
  * Reinforce connections for highly-accessed chunks.
  * With learning weights: uses access_count, confidence, avg_sim.
  * Without: flat +0.05 increment.
+ * Returns the number of edges reinforced.
  */
-export function reinforceConnections(): void {
+export function reinforceConnections(): number {
   const s = S();
   const now = new Date().toISOString();
   const chunks = s.selectHighlyAccessedChunks.all(REINFORCE_ACCESS_THRESHOLD) as ChunkRow[];
@@ -208,22 +235,43 @@ export function reinforceConnections(): void {
   }
 
   console.log(`🔗 Reinforced ${count} connections across ${chunks.length} chunks`);
+  return count;
 }
 
 /**
  * Decay connections not seen/reinforced recently.
- * With learning weights: only decays when last_seen is old.
+ * With learning weights: only decays when last_seen is old. Frozen edges keep
+ * their weight.
+ * Returns the number of edges decayed.
  */
-export function decayConnections(daysOld: number = 7): void {
+export function decayConnections(daysOld: number = 7, focusWindowMs: number = DECAY_FOCUS_WINDOW_MS): number {
   const s = S();
   const nowMs = Date.now();
   const edges = db.prepare(`
-    SELECT edge_id, weight, access_count, last_reinforced, created_at
-    FROM connections
-  `).all() as Array<ConnectionRow & { access_count?: number | null }>;
+    SELECT e.edge_id, e.weight, e.access_count, e.last_reinforced, e.created_at,
+           src.last_accessed AS source_accessed, tgt.last_accessed AS target_accessed
+    FROM connections e
+    LEFT JOIN chunks src ON src.chunk_id = e.source_chunk
+    LEFT JOIN chunks tgt ON tgt.chunk_id = e.target_chunk
+    WHERE COALESCE(e.frozen, 0) = 0
+  `).all() as Array<ConnectionRow & {
+    access_count?: number | null;
+    source_accessed: string | null;
+    target_accessed: string | null;
+  }>;
   let decayed = 0;
+  let exempted = 0;
+
+  // Both endpoints recently recalled: the user is working with this edge
+  const focusCutoffMs = focusWindowMs > 0 ? nowMs - focusWindowMs : Number.POSITIVE_INFINITY;
+  const inFocus = (accessed: string | null): boolean => Boolean(accessed) && Date.parse(accessed as string) >= focusCutoffMs;
 
   for (const edge of edges) {
+    if (inFocus(edge.source_accessed) && inFocus(edge.target_accessed)) {
+      exempted++;
+      continue;
+    }
+
     const current = edge.weight ?? MIN_CONNECTION_WEIGHT;
     const accessCount = Math.max(1, Number(edge.access_count ?? 0));
     const decayRate = BASE_DECAY / Math.log(1 + accessCount);
@@ -239,5 +287,158 @@ export function decayConnections(daysOld: number = 7): void {
     decayed++;
   }
 
-  console.log(`📉 Decayed ${decayed} connections`);
+  console.log(`📉 Decayed ${decayed} connections${exempted > 0 ? ` (${exempted} in focus kept)` : ''}`);
+  return decayed;
+}
+
+/**
+ * Drop edges that went stale without ever earning their keep: not reinforced
+ * (or, never reinforced, not created) within `staleDays`, and still weighted
+ * below `maxWeight`. Stale but strong edges stay; decay keeps wearing them
+ * down until they fall under the threshold. Frozen edges are never pruned.
+ * Without a database every database is pruned. Returns the number removed.
+ */
+export function pruneStaleConnections(
+  staleDays: number = STALE_EDGE_PRUNE_DAYS,
+  maxWeight: number = STALE_EDGE_PRUNE_WEIGHT,
+  database?: string,
+): number {
+  if (staleDays <= 0) return 0;
+
+  const cutoff = new Date(Date.now() - staleDays * 24 * 60 * 60 * 1000).toISOString();
+  const filters = ['COALESCE(last_reinforced, created_at) < ?', 'COALESCE(weight, 0) < ?', 'COALESCE(frozen, 0) = 0'];
+  const args: Array<string | number> = [cutoff, maxWeight];
+  if (database) {
+    filters.push('database_id = ?');
+    args.push(database);
+  }
+
+  const removed = db.prepare(`
+    DELETE FROM connections
+    WHERE ${filters.join(' AND ')}
+  `).run(...args).changes;
+
+  if (removed > 0) console.log(`✂️  Pruned ${removed} stale connections`);
+  return removed;
+}
+
+const SIMILARITY_CANDIDATE_LIMIT = 200;
+const SIMILARITY_QUERY_TOKENS = 64;
+const SIMILARITY_EDGE_WEIGHT = 0.3;
+
+export interface SimilarityEdgeStats {
+  compared: number;  // chunk pairs whose text overlap was computed
+  created: number;   // new related_to edges
+}
+
+/**
+ * Connect chunks of a database whose texts overlap by at least `threshold`
+ * (word Jaccard, as in diversity re-ranking), adding at most `maxPerChunk`
+ * new edges touching any one chunk, strongest pairs first. With the text
+ * index only chunks sharing a word are compared; without it every pair is.
+ * Pairs already connected in either direction are left alone. New edges are
+ * `related_to` at the same seeding weight ingest uses.
+ */
+export function buildSimilarityEdges(
+  threshold: number,
+  maxPerChunk: number,
+  database: string = DEFAULT_MEMORY_DB,
+): SimilarityEdgeStats {
+  if (!Number.isFinite(threshold) || threshold <= 0 || threshold > 1) {
+    throw new Error('threshold must be in (0, 1]');
+  }
+  const cap = Math.floor(maxPerChunk);
+  if (!Number.isFinite(cap) || cap < 1) {
+    throw new Error('maxPerChunk must be at least 1');
+  }
+
+  const nowIso = new Date().toISOString();
+  const chunks = db.prepare(`
+    SELECT chunk_id, text
+    FROM chunks
+    WHERE database_id = ?
+      AND (expires_at IS NULL OR expires_at > ?)
+    ORDER BY chunk_id
+  `).all(database, nowIso) as Array<{ chunk_id: string; text: string }>;
+  const textById = new Map(chunks.map(chunk => [chunk.chunk_id, chunk.text]));
+
+  const candidateStmt = hasTextIndex()
+    ? db.prepare(`
+      SELECT f.chunk_id
+      FROM chunks_fts f
+      JOIN chunks c ON c.chunk_id = f.chunk_id AND c.database_id = f.database_id
+      WHERE chunks_fts MATCH ? AND f.database_id = ?
+        AND (c.expires_at IS NULL OR c.expires_at > ?)
+      ORDER BY rank
+      LIMIT ?
+    `)
+    : null;
+
+  const pairs: Array<{ source: string; target: string; sim: number }> = [];
+  const seen = new Set<string>();
+  let compared = 0;
+
+  chunks.forEach((chunk, index) => {
+    let candidates: string[];
+    if (candidateStmt) {
+      const tokens = [...contentTokens(chunk.text)].slice(0, SIMILARITY_QUERY_TOKENS);
+      if (tokens.length === 0) return;
+      const match = tokens.map(token => `"${token}"`).join(' OR ');
+      candidates = (candidateStmt.all(match, database, nowIso, SIMILARITY_CANDIDATE_LIMIT) as Array<{ chunk_id: string }>)
+        .map(row => row.chunk_id);
+    } else {
+      candidates = chunks.slice(index + 1).map(other => other.chunk_id);
+    }
+
+    for (const other of candidates) {
+      if (other === chunk.chunk_id) continue;
+      if (candidateStmt) {
+        const key = chunk.chunk_id < other ? `${chunk.chunk_id}|${other}` : `${other}|${chunk.chunk_id}`;
+        if (seen.has(key)) continue;
+        seen.add(key);
+      }
+
+      const otherText = textById.get(other);
+      if (otherText === undefined) continue;
+      compared++;
+      const sim = contentSimilarity(chunk.text, otherText);
+      if (sim >= threshold) pairs.push({ source: chunk.chunk_id, target: other, sim });
+    }
+  });
+
+  pairs.sort((left, right) => right.sim - left.sim);
+
+  const connected = db.prepare(`
+    SELECT 1
+    FROM connections
+    WHERE database_id = ?
+      AND ((source_chunk = ? AND target_chunk = ?) OR (source_chunk = ? AND target_chunk = ?))
+    LIMIT 1
+  `);
+  const insertStmt = db.prepare(`
+    INSERT OR IGNORE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, last_reinforced, avg_sim, seen_count, last_seen, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+
+  const now = new Date().toISOString();
+  const added = new Map<string, number>();
+  let created = 0;
+
+  db.transaction(() => {
+    for (const { source, target, sim } of pairs) {
+      if ((added.get(source) ?? 0) >= cap || (added.get(target) ?? 0) >= cap) continue;
+      if (connected.get(database, source, target, target, source)) continue;
+
+      const inserted = insertWithinEdgeLimit(source, target, () => insertStmt.run(
+        randomUUID(), source, target, 'related_to', SIMILARITY_EDGE_WEIGHT, 0.5, now, null, sim, 1, now, database,
+      ).changes, database);
+      if (inserted === 0) continue;
+      added.set(source, (added.get(source) ?? 0) + 1);
+      added.set(target, (added.get(target) ?? 0) + 1);
+      created++;
+    }
+  })();
+
+  if (created > 0) console.log(`🔗 Built ${created} similarity connections from ${compared} compared pairs`);
+  return { compared, created };
 }
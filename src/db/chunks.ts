@@ -0,0 +1,631 @@
+// src/db/chunks.ts — Chunk lookup helpers
+import { db, qdrant, COLLECTION, CONCEPT_COLLECTION, DEFAULT_MEMORY_DB, hasTextIndex, assertGraphVersion } from './index';
+import { withStoreRetry } from './retry';
+import { matchQuality, candidateTokenQuery } from '../retrieve/textSearch';
+import type { SearchMode } from '../retrieve/textSearch';
+import { MAX_CHUNKS, MAX_CHUNKS_POLICY, MAX_EVICTIONS_PER_BATCH, EVICTION_PROTECT_DEGREE } from '../config';
+
+const LOOKUP_BATCH_SIZE = 500;
+
+export interface ChunkRecord {
+  chunk_id: string;
+  text: string;
+  source: string;
+  page: number;
+  timestamp: string;
+  access_count: number;
+  last_accessed: string | null;
+  tags: string;
+  confidence: number;
+  novelty: number | null;   // 1 - best similarity to earlier chunks at ingest; null if not measured
+}
+
+/**
+ * Fetch many chunks in as few queries as possible.
+ * The result preserves the order of `chunkIds` and holds `null` wherever an id
+ * does not exist in the given database. Chunks past `expires_at` count as
+ * missing unless `includeExpired` is set.
+ */
+export function getChunksByIds(
+  chunkIds: string[],
+  database: string = DEFAULT_MEMORY_DB,
+  options: { includeExpired?: boolean } = {},
+): Array<ChunkRecord | null> {
+  if (chunkIds.length === 0) return [];
+
+  const nowIso = new Date().toISOString();
+  const expiryFilter = options.includeExpired ? '' : 'AND (expires_at IS NULL OR expires_at > ?)';
+
+  const unique = [...new Set(chunkIds)];
+  const byId = new Map<string, ChunkRecord>();
+
+  for (let i = 0; i < unique.length; i += LOOKUP_BATCH_SIZE) {
+    const batch = unique.slice(i, i + LOOKUP_BATCH_SIZE);
+    const placeholders = batch.map(() => '?').join(', ');
+    const rows = db.prepare(`
+      SELECT chunk_id, text, source, page, timestamp, access_count, last_accessed, tags, COALESCE(confidence, 1.0) AS confidence, novelty
+      FROM chunks
+      WHERE database_id = ?
+        AND chunk_id IN (${placeholders})
+        ${expiryFilter}
+    `).all(database, ...batch, ...(options.includeExpired ? [] : [nowIso])) as ChunkRecord[];
+
+    for (const row of rows) {
+      byId.set(row.chunk_id, row);
+    }
+  }
+
+  return chunkIds.map(id => byId.get(id) ?? null);
+}
+
+/**
+ * Remove chunks, every connection touching them and their concept
+ * memberships. Qdrant points are deleted best effort after the SQLite
+ * transaction commits. Returns the number of chunk rows removed.
+ */
+export async function deleteChunksByIds(chunkIds: string[], database: string = DEFAULT_MEMORY_DB): Promise<number> {
+  const unique = [...new Set(chunkIds)];
+  if (unique.length === 0) return 0;
+
+  const { deleted, emptiedConcepts } = removeChunkRows(unique, database);
+  await deleteChunkPoints(unique, emptiedConcepts);
+  return deleted;
+}
+
+// Best effort: a failed delete leaves orphan points, which recall already skips
+async function deleteChunkPoints(chunkIds: string[], emptiedConcepts: string[]): Promise<void> {
+  try {
+    await withStoreRetry(() => qdrant.delete(COLLECTION, { wait: true, points: chunkIds }));
+    if (emptiedConcepts.length > 0) {
+      await withStoreRetry(() => qdrant.delete(CONCEPT_COLLECTION, { wait: true, points: emptiedConcepts }));
+    }
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    console.warn(`⚠️  Qdrant delete failed for ${chunkIds.length} chunks: ${message}`);
+  }
+}
+
+/**
+ * The SQLite half of `deleteChunksByIds`: chunk rows, their connections and
+ * concept memberships, leaving Qdrant untouched. Returns the number of chunk
+ * rows removed.
+ */
+export function deleteChunkRows(chunkIds: string[], database: string = DEFAULT_MEMORY_DB): number {
+  return removeChunkRows(chunkIds, database).deleted;
+}
+
+// Concepts that lose members get a version bump so the next sync re-embeds
+// them with the new member list; concepts left with no members are dropped
+// and returned so their Qdrant points can go too.
+function removeChunkRows(chunkIds: string[], database: string): { deleted: number; emptiedConcepts: string[] } {
+  const unique = [...new Set(chunkIds)];
+  let deleted = 0;
+  const emptiedConcepts: string[] = [];
+  const selectConcepts = db.prepare(`SELECT concept_id, member_chunks FROM concepts WHERE database_id = ?`);
+  const updateMembers = db.prepare(`
+    UPDATE concepts
+    SET member_chunks = ?, last_updated = ?, version = COALESCE(version, 1) + 1
+    WHERE concept_id = ?
+  `);
+  const deleteConcept = db.prepare(`DELETE FROM concepts WHERE concept_id = ?`);
+
+  const tx = db.transaction((batch: string[]) => {
+    const placeholders = batch.map(() => '?').join(', ');
+    db.prepare(`
+      DELETE FROM connections
+      WHERE database_id = ?
+        AND (source_chunk IN (${placeholders}) OR target_chunk IN (${placeholders}))
+    `).run(database, ...batch, ...batch);
+    const result = db.prepare(`
+      DELETE FROM chunks
+      WHERE database_id = ?
+        AND chunk_id IN (${placeholders})
+    `).run(database, ...batch);
+    deleted += result.changes;
+
+    const removed = new Set(batch);
+    const now = new Date().toISOString();
+    for (const concept of selectConcepts.all(database) as Array<{ concept_id: string; member_chunks: string }>) {
+      let members: string[];
+      try {
+        members = JSON.parse(concept.member_chunks) as string[];
+      } catch {
+        continue;
+      }
+      if (!Array.isArray(members)) continue;
+      const remaining = members.filter(id => !removed.has(id));
+      if (remaining.length === members.length) continue;
+      if (remaining.length === 0) {
+        deleteConcept.run(concept.concept_id);
+        emptiedConcepts.push(concept.concept_id);
+      } else {
+        updateMembers.run(JSON.stringify(remaining), now, concept.concept_id);
+      }
+    }
+  });
+
+  for (let i = 0; i < unique.length; i += LOOKUP_BATCH_SIZE) {
+    tx(unique.slice(i, i + LOOKUP_BATCH_SIZE));
+  }
+
+  return { deleted, emptiedConcepts };
+}
+
+// ── Eviction hooks ─────────────────────────────────────────────────────────
+
+/** Why a chunk left the graph without being deleted by a caller. */
+export type EvictionReason = 'evicted' | 'expired';
+
+export type EvictionListener = (chunk: ChunkRecord, database: string, reason: EvictionReason) => void;
+
+const evictionListeners = new Set<EvictionListener>();
+
+/**
+ * Get told about every chunk dropped by capacity eviction or the expiry
+ * sweep, e.g. to archive it elsewhere. Listeners run after the rows are gone
+ * and outside any transaction, so they may call back into the store; a
+ * throwing listener is logged and skipped. Returns an unsubscribe function.
+ */
+export function onChunkEvicted(listener: EvictionListener): () => void {
+  evictionListeners.add(listener);
+  return () => {
+    evictionListeners.delete(listener);
+  };
+}
+
+async function evictChunks(chunkIds: string[], database: string, reason: EvictionReason): Promise<number> {
+  return finishEviction(evictChunkRows(chunkIds, database, reason));
+}
+
+/** Chunk rows evicted inside a transaction, still to be dropped from Qdrant and reported. */
+export interface PendingEviction {
+  database: string;
+  reason: EvictionReason;
+  chunkIds: string[];
+  removed: number;
+  records: ChunkRecord[];     // captured for listeners before the rows went
+  emptiedConcepts: string[];
+}
+
+/**
+ * The SQLite half of an eviction, safe to run inside a caller's transaction.
+ * Pass the result to `finishEviction` once that transaction has committed.
+ */
+export function evictChunkRows(chunkIds: string[], database: string, reason: EvictionReason): PendingEviction {
+  const unique = [...new Set(chunkIds)];
+  const records = evictionListeners.size > 0 && unique.length > 0
+    ? getChunksByIds(unique, database, { includeExpired: true }).filter((row): row is ChunkRecord => row !== null)
+    : [];
+  const { deleted, emptiedConcepts } = unique.length > 0 ? removeChunkRows(unique, database) : { deleted: 0, emptiedConcepts: [] };
+  return { database, reason, chunkIds: unique, removed: deleted, records, emptiedConcepts };
+}
+
+/** Drop the evicted points from Qdrant and tell the listeners. Returns the number of chunks evicted. */
+export async function finishEviction(eviction: PendingEviction): Promise<number> {
+  if (eviction.chunkIds.length === 0) return 0;
+  await deleteChunkPoints(eviction.chunkIds, eviction.emptiedConcepts);
+
+  for (const record of eviction.records) {
+    for (const listener of evictionListeners) {
+      try {
+        listener(record, eviction.database, eviction.reason);
+      } catch (error) {
+        const message = error instanceof Error ? error.message : String(error);
+        console.warn(`⚠️  Eviction listener failed for ${record.chunk_id}: ${message}`);
+      }
+    }
+  }
+
+  return eviction.removed;
+}
+
+export interface DeleteChunkResult {
+  chunk_id: string;
+  deleted: boolean;   // false when the id did not exist in the database
+}
+
+/**
+ * Batch delete with a per-id outcome, in request order. Duplicated ids report
+ * `deleted: true` on their first occurrence only.
+ */
+export async function deleteChunks(
+  chunkIds: string[],
+  database: string = DEFAULT_MEMORY_DB,
+): Promise<{ results: DeleteChunkResult[]; deleted_count: number }> {
+  const existing = new Set(
+    getChunksByIds(chunkIds, database, { includeExpired: true })
+      .filter((row): row is ChunkRecord => row !== null)
+      .map(row => row.chunk_id),
+  );
+  const deletedCount = await deleteChunksByIds([...existing], database);
+
+  const reported = new Set<string>();
+  const results = chunkIds.map(chunkId => {
+    const deleted = existing.has(chunkId) && !reported.has(chunkId);
+    reported.add(chunkId);
+    return { chunk_id: chunkId, deleted };
+  });
+
+  return { results, deleted_count: deletedCount };
+}
+
+// ── Capacity ───────────────────────────────────────────────────────────────
+
+export type CapacityPolicy = 'reject' | 'evict';
+
+export class CapacityExceededError extends Error {
+  constructor(database: string, limit: number) {
+    super(`Capacity exceeded: database "${database}" is limited to ${limit} chunks`);
+    this.name = 'CapacityExceededError';
+  }
+}
+
+/**
+ * Pick eviction victims in a stable order: least recently used first, then
+ * least accessed, then chunk id, so repeated runs choose the same chunks.
+ * Hubs with at least `protectDegree` incident connections are never picked
+ * (0 disables the protection).
+ */
+export function selectEvictionCandidates(
+  count: number,
+  database: string = DEFAULT_MEMORY_DB,
+  protectDegree: number = EVICTION_PROTECT_DEGREE,
+): string[] {
+  if (count <= 0) return [];
+
+  const rows = db.prepare(`
+    SELECT chunk_id
+    FROM chunks
+    WHERE database_id = ?
+      AND (
+        ? <= 0 OR (
+          SELECT COUNT(*) FROM connections e
+          WHERE e.database_id = chunks.database_id
+            AND (e.source_chunk = chunks.chunk_id OR e.target_chunk = chunks.chunk_id)
+        ) < ?
+      )
+    ORDER BY COALESCE(last_accessed, timestamp) ASC, COALESCE(access_count, 0) ASC, chunk_id ASC
+    LIMIT ?
+  `).all(database, protectDegree, protectDegree, count) as Array<{ chunk_id: string }>;
+
+  return rows.map(row => row.chunk_id);
+}
+
+/**
+ * Make room for `incoming` new chunks under the per-database cap.
+ * `reject` throws CapacityExceededError; `evict` drops candidates from
+ * `selectEvictionCandidates`, but never more than `maxEvictions` at once
+ * (0 = no bound) — a larger overflow, or one that protected hubs leave no
+ * room for, is rejected instead. Returns how many chunks were evicted.
+ */
+export async function ensureChunkCapacity(
+  incoming: number,
+  database: string = DEFAULT_MEMORY_DB,
+  limit: number = MAX_CHUNKS,
+  policy: CapacityPolicy = MAX_CHUNKS_POLICY,
+  maxEvictions: number = MAX_EVICTIONS_PER_BATCH,
+): Promise<number> {
+  const victims = planChunkCapacity(incoming, database, { limit, policy, maxEvictions });
+  if (victims.length === 0) return 0;
+  return evictChunks(victims, database, 'evicted');
+}
+
+export interface CapacityOptions {
+  limit?: number;           // default MAX_CHUNKS (0 = no cap)
+  policy?: CapacityPolicy;  // default MAX_CHUNKS_POLICY
+  maxEvictions?: number;    // default MAX_EVICTIONS_PER_BATCH
+}
+
+/**
+ * The synchronous decision behind `ensureChunkCapacity`: the chunks to evict
+ * so `incoming` more fit (none when they already do), or CapacityExceededError.
+ * Nothing is deleted.
+ */
+export function planChunkCapacity(incoming: number, database: string = DEFAULT_MEMORY_DB, options: CapacityOptions = {}): string[] {
+  const limit = options.limit ?? MAX_CHUNKS;
+  const policy = options.policy ?? MAX_CHUNKS_POLICY;
+  const maxEvictions = options.maxEvictions ?? MAX_EVICTIONS_PER_BATCH;
+  if (!limit || limit <= 0 || incoming <= 0) return [];
+
+  const row = db.prepare('SELECT COUNT(*) AS total FROM chunks WHERE database_id = ?').get(database) as { total: number };
+  const overflow = row.total + incoming - limit;
+  if (overflow <= 0) return [];
+
+  if (policy === 'reject' || incoming > limit || (maxEvictions > 0 && overflow > maxEvictions)) {
+    throw new CapacityExceededError(database, limit);
+  }
+
+  const victims = selectEvictionCandidates(overflow, database);
+  if (victims.length < overflow) {
+    throw new CapacityExceededError(database, limit);
+  }
+  return victims;
+}
+
+/**
+ * Store `incoming` new chunks under the cap in one SQLite transaction: the
+ * graph version check first, then the count and any eviction, then `write`,
+ * which gets the evicted ids so new edges can leave them out. Counting in the
+ * transaction that inserts keeps concurrent ingests from both passing the cap;
+ * checking the version before evicting keeps the eviction's own deletes from
+ * failing the check, and nothing is evicted when it fails. Qdrant cleanup
+ * and eviction listeners run after the commit. Returns what `write` returned
+ * and how many chunks were evicted.
+ */
+export async function writeWithinCapacity<T>(
+  incoming: number,
+  database: string,
+  write: (evicted: Set<string>) => T,
+  options: CapacityOptions & { expectedVersion?: number } = {},
+): Promise<{ result: T; evicted: number }> {
+  const { result, eviction } = db.transaction(() => {
+    assertGraphVersion(options.expectedVersion, database);
+    const pending = evictChunkRows(planChunkCapacity(incoming, database, options), database, 'evicted');
+    return { result: write(new Set(pending.chunkIds)), eviction: pending };
+  })();
+  return { result, evicted: await finishEviction(eviction) };
+}
+
+// ── Expiry ─────────────────────────────────────────────────────────────────
+
+/**
+ * Delete every chunk whose `expires_at` has passed, with its connections, in
+ * one database or across all of them. Retrieval already hides expired chunks,
+ * so this only reclaims space. Returns the number of chunks removed.
+ */
+export async function sweepExpiredChunks(database?: string, nowIso: string = new Date().toISOString()): Promise<number> {
+  let removed = 0;
+  for (const [databaseId, ids] of listExpiredChunks(database, nowIso)) {
+    removed += await evictChunks(ids, databaseId, 'expired');
+  }
+  return removed;
+}
+
+/** Ids of chunks past `expires_at`, grouped by database. */
+export function listExpiredChunks(database?: string, nowIso: string = new Date().toISOString()): Map<string, string[]> {
+  const rows = (database
+    ? db.prepare(`
+        SELECT chunk_id, database_id FROM chunks
+        WHERE database_id = ? AND expires_at IS NOT NULL AND expires_at <= ?
+      `).all(database, nowIso)
+    : db.prepare(`
+        SELECT chunk_id, database_id FROM chunks
+        WHERE expires_at IS NOT NULL AND expires_at <= ?
+      `).all(nowIso)) as Array<{ chunk_id: string; database_id: string }>;
+
+  const byDatabase = new Map<string, string[]>();
+  for (const row of rows) {
+    const ids = byDatabase.get(row.database_id) ?? [];
+    ids.push(row.chunk_id);
+    byDatabase.set(row.database_id, ids);
+  }
+  return byDatabase;
+}
+
+/** The subset of `chunkIds` already past `expires_at` in `database`. */
+export function expiredChunkIds(chunkIds: string[], database: string = DEFAULT_MEMORY_DB, nowIso: string = new Date().toISOString()): Set<string> {
+  const expired = new Set<string>();
+  const unique = [...new Set(chunkIds)];
+  for (let i = 0; i < unique.length; i += LOOKUP_BATCH_SIZE) {
+    const batch = unique.slice(i, i + LOOKUP_BATCH_SIZE);
+    const placeholders = batch.map(() => '?').join(', ');
+    const rows = db.prepare(`
+      SELECT chunk_id FROM chunks
+      WHERE database_id = ? AND chunk_id IN (${placeholders})
+        AND expires_at IS NOT NULL AND expires_at <= ?
+    `).all(database, ...batch, nowIso) as Array<{ chunk_id: string }>;
+    for (const row of rows) expired.add(row.chunk_id);
+  }
+  return expired;
+}
+
+/** Earliest `expires_at` among `chunkIds`, or null when none of them expires. */
+export function earliestExpiry(chunkIds: string[], database: string = DEFAULT_MEMORY_DB): string | null {
+  let earliest: string | null = null;
+  const unique = [...new Set(chunkIds)];
+  for (let i = 0; i < unique.length; i += LOOKUP_BATCH_SIZE) {
+    const batch = unique.slice(i, i + LOOKUP_BATCH_SIZE);
+    const placeholders = batch.map(() => '?').join(', ');
+    const row = db.prepare(`
+      SELECT MIN(expires_at) AS earliest FROM chunks
+      WHERE database_id = ? AND chunk_id IN (${placeholders}) AND expires_at IS NOT NULL
+    `).get(database, ...batch) as { earliest: string | null };
+    if (row.earliest && (earliest === null || row.earliest < earliest)) earliest = row.earliest;
+  }
+  return earliest;
+}
+
+// ── Strength report ────────────────────────────────────────────────────────
+
+export interface ChunkReport {
+  chunk_id: string;
+  access_count: number;
+  last_accessed: string | null;
+  in_degree: number;
+  out_degree: number;
+  avg_edge_weight: number;
+  strongest_neighbor: string | null;
+  strongest_weight: number;
+  consolidated: boolean;   // member of at least one concept
+}
+
+/**
+ * One-call summary of how well a chunk is anchored in memory. Connections are
+ * counted in both directions; the strongest neighbour is the far end of the
+ * heaviest incident edge. Returns null when the chunk does not exist or has
+ * expired.
+ */
+export function getChunkReport(chunkId: string, database: string = DEFAULT_MEMORY_DB): ChunkReport | null {
+  const chunk = db.prepare(`
+    SELECT chunk_id, access_count, last_accessed
+    FROM chunks
+    WHERE chunk_id = ? AND database_id = ?
+      AND (expires_at IS NULL OR expires_at > ?)
+  `).get(chunkId, database, new Date().toISOString()) as { chunk_id: string; access_count: number | null; last_accessed: string | null } | undefined;
+  if (!chunk) return null;
+
+  const degrees = db.prepare(`
+    SELECT
+      SUM(CASE WHEN target_chunk = ? THEN 1 ELSE 0 END) AS in_degree,
+      SUM(CASE WHEN source_chunk = ? THEN 1 ELSE 0 END) AS out_degree,
+      AVG(weight) AS avg_weight
+    FROM connections
+    WHERE database_id = ?
+      AND (source_chunk = ? OR target_chunk = ?)
+  `).get(chunkId, chunkId, database, chunkId, chunkId) as { in_degree: number | null; out_degree: number | null; avg_weight: number | null };
+
+  const strongest = db.prepare(`
+    SELECT CASE WHEN source_chunk = ? THEN target_chunk ELSE source_chunk END AS neighbor, weight
+    FROM connections
+    WHERE database_id = ?
+      AND (source_chunk = ? OR target_chunk = ?)
+    ORDER BY weight DESC
+    LIMIT 1
+  `).get(chunkId, database, chunkId, chunkId) as { neighbor: string; weight: number } | undefined;
+
+  const concept = db.prepare(`
+    SELECT 1
+    FROM concepts, json_each(concepts.member_chunks)
+    WHERE concepts.database_id = ?
+      AND json_each.value = ?
+    LIMIT 1
+  `).get(database, chunkId);
+
+  return {
+    chunk_id: chunk.chunk_id,
+    access_count: chunk.access_count ?? 0,
+    last_accessed: chunk.last_accessed,
+    in_degree: degrees.in_degree ?? 0,
+    out_degree: degrees.out_degree ?? 0,
+    avg_edge_weight: degrees.avg_weight ?? 0,
+    strongest_neighbor: strongest?.neighbor ?? null,
+    strongest_weight: strongest?.weight ?? 0,
+    consolidated: Boolean(concept),
+  };
+}
+
+/**
+ * Chunks that are wired into the graph (at least one connection) but have not
+ * been absorbed into any concept yet — knowledge that hasn't stabilised.
+ */
+export function listUnconsolidatedChunks(database: string = DEFAULT_MEMORY_DB, limit: number = 500): string[] {
+  const rows = db.prepare(`
+    SELECT c.chunk_id
+    FROM chunks c
+    WHERE c.database_id = ?
+      AND EXISTS (
+        SELECT 1 FROM connections e
+        WHERE e.database_id = c.database_id
+          AND (e.source_chunk = c.chunk_id OR e.target_chunk = c.chunk_id)
+      )
+      AND NOT EXISTS (
+        SELECT 1 FROM concepts k, json_each(k.member_chunks) m
+        WHERE k.database_id = c.database_id
+          AND m.value = c.chunk_id
+      )
+    ORDER BY c.timestamp DESC, c.chunk_id ASC
+    LIMIT ?
+  `).all(database, Math.max(1, Math.floor(limit))) as Array<{ chunk_id: string }>;
+
+  return rows.map(row => row.chunk_id);
+}
+
+/**
+ * Most recently ingested chunks, newest first, for reviewing what was just
+ * learned. `since` (ISO timestamp) keeps only chunks stored strictly after it.
+ * Import placeholders have no content yet and are left out.
+ */
+export function listRecentChunks(
+  limit: number = 50,
+  since?: string,
+  database: string = DEFAULT_MEMORY_DB,
+): ChunkRecord[] {
+  const filters = ['database_id = ?', "text != ''", '(expires_at IS NULL OR expires_at > ?)'];
+  const args: Array<string | number> = [database, new Date().toISOString()];
+  if (since) {
+    filters.push('timestamp > ?');
+    args.push(since);
+  }
+
+  return db.prepare(`
+    SELECT chunk_id, text, source, page, timestamp, access_count, last_accessed, tags, COALESCE(confidence, 1.0) AS confidence, novelty
+    FROM chunks
+    WHERE ${filters.join(' AND ')}
+    ORDER BY timestamp DESC, chunk_id ASC
+    LIMIT ?
+  `).all(...args, Math.max(1, Math.floor(limit))) as ChunkRecord[];
+}
+
+// ── Literal search ─────────────────────────────────────────────────────────
+
+export interface ChunkSearchHit {
+  chunk_id: string;
+  text: string;
+  source: string;
+  quality: number;
+}
+
+export interface ChunkSearchStats {
+  candidates: number;   // rows read back and checked with matchQuality
+  indexed: boolean;     // whether the token index narrowed the candidates
+}
+
+/**
+ * Text search over chunk content (no embeddings), best match first.
+ * Single-word substring/prefix queries are pre-filtered with LIKE, multi-word
+ * ones through the token index; fuzzy scans every chunk in the database, so
+ * keep it for interactive use on modest collections.
+ */
+export function searchChunks(
+  query: string,
+  mode: SearchMode,
+  database: string = DEFAULT_MEMORY_DB,
+  limit: number = 20,
+  stats?: ChunkSearchStats,
+): ChunkSearchHit[] {
+  const trimmed = query.trim();
+  if (!trimmed) return [];
+
+  const tokenQuery = hasTextIndex() ? candidateTokenQuery(trimmed, mode) : null;
+  const nowIso = new Date().toISOString();
+  let rows: Array<{ chunk_id: string; text: string; source: string }>;
+  if (tokenQuery) {
+    rows = db.prepare(`
+      SELECT c.chunk_id, c.text, c.source
+      FROM chunks_fts f
+      JOIN chunks c ON c.chunk_id = f.chunk_id
+      WHERE chunks_fts MATCH ?
+        AND f.database_id = ?
+        AND c.database_id = ?
+        AND (c.expires_at IS NULL OR c.expires_at > ?)
+    `).all(tokenQuery, database, database, nowIso) as typeof rows;
+  } else {
+    // Matching collapses whitespace, so only single-token queries can be pushed
+    // down to LIKE without missing rows.
+    const escaped = trimmed.replace(/[\\%_]/g, character => `\\${character}`);
+    const pushDown = mode.kind !== 'fuzzy' && !/\s/.test(trimmed);
+    const pattern = !pushDown ? '%' : mode.kind === 'substring' ? `%${escaped}%` : `${escaped}%`;
+    rows = db.prepare(`
+      SELECT chunk_id, text, source
+      FROM chunks
+      WHERE database_id = ?
+        AND LTRIM(text) LIKE ? ESCAPE '\\'
+        AND (expires_at IS NULL OR expires_at > ?)
+    `).all(database, pattern, nowIso) as typeof rows;
+  }
+
+  if (stats) {
+    stats.candidates = rows.length;
+    stats.indexed = tokenQuery !== null;
+  }
+
+  const hits: ChunkSearchHit[] = [];
+  for (const row of rows) {
+    const quality = matchQuality(row.text, trimmed, mode);
+    if (quality !== null) hits.push({ ...row, quality });
+  }
+
+  return hits
+    .sort((a, b) => b.quality - a.quality || a.chunk_id.localeCompare(b.chunk_id))
+    .slice(0, Math.max(1, Math.floor(limit)));
+}
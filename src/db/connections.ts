@@ -0,0 +1,432 @@
+// src/db/connections.ts — Direct connection (edge) access helpers
+import { db, DEFAULT_MEMORY_DB } from './index';
+import { MIN_CONNECTION_WEIGHT, MAX_CONNECTION_WEIGHT, clamp } from '../consolidate/helpers';
+import { MAX_EDGES_PER_CHUNK, MAX_EDGES_POLICY } from '../config';
+
+export interface ConnectionRow {
+  edge_id: string;
+  source_chunk: string;
+  target_chunk: string;
+  relationship: string;
+  weight: number;
+  confidence: number;
+  created_at: string;
+}
+
+export interface EdgeListFilter {
+  database?: string;
+  minWeight?: number;
+  relationships?: string[];
+  pageSize?: number;
+}
+
+export interface EdgeSelector {
+  relationship?: string;
+  database?: string;
+}
+
+function edgeFilter(sourceChunk: string, targetChunk: string, selector: EdgeSelector): { where: string; args: string[] } {
+  const filters = ['source_chunk = ?', 'target_chunk = ?', 'database_id = ?'];
+  const args = [sourceChunk, targetChunk, selector.database || DEFAULT_MEMORY_DB];
+
+  if (selector.relationship) {
+    filters.push('relationship = ?');
+    args.push(selector.relationship);
+  }
+
+  return { where: filters.join(' AND '), args };
+}
+
+/**
+ * Read the weight of the edge source → target.
+ * Without a relationship the strongest matching edge wins. Returns null when
+ * no such edge exists.
+ */
+export function getEdgeWeight(sourceChunk: string, targetChunk: string, selector: EdgeSelector = {}): number | null {
+  const { where, args } = edgeFilter(sourceChunk, targetChunk, selector);
+  const row = db.prepare(`
+    SELECT weight
+    FROM connections
+    WHERE ${where}
+    ORDER BY weight DESC
+    LIMIT 1
+  `).get(...args) as { weight: number | null } | undefined;
+
+  if (!row) return null;
+  return row.weight ?? MIN_CONNECTION_WEIGHT;
+}
+
+/**
+ * Overwrite the weight of the edge source → target (every matching edge when
+ * no relationship is given). The weight is clamped to the connection range,
+ * `weight_version` is bumped and `last_reinforced` refreshed so decay does not
+ * immediately treat a hand-tuned edge as stale.
+ * Returns the stored weight, or null when no edge matched.
+ */
+export function setEdgeWeight(sourceChunk: string, targetChunk: string, weight: number, selector: EdgeSelector = {}): number | null {
+  if (!Number.isFinite(weight)) {
+    throw new Error('weight must be a finite number');
+  }
+
+  const next = clamp(weight, MIN_CONNECTION_WEIGHT, MAX_CONNECTION_WEIGHT);
+  const { where, args } = edgeFilter(sourceChunk, targetChunk, selector);
+  const result = db.prepare(`
+    UPDATE connections
+    SET weight = ?,
+        weight_version = COALESCE(weight_version, 1) + 1,
+        last_reinforced = ?
+    WHERE ${where}
+  `).run(next, new Date().toISOString(), ...args);
+
+  return result.changes > 0 ? next : null;
+}
+
+/**
+ * Freeze the edge source → target (every matching edge when no relationship
+ * is given) so Hebbian strengthening, reinforcement, classification, decay and
+ * pruning all leave it as it is. Hand-set weights (`setEdgeWeight`,
+ * `adjustEdgeWeights`) still apply. Returns the number of edges matched.
+ */
+export function freezeEdge(sourceChunk: string, targetChunk: string, selector: EdgeSelector = {}): number {
+  return setEdgeFrozen(sourceChunk, targetChunk, true, selector);
+}
+
+/** Undo `freezeEdge`; the edge takes part in plasticity again. */
+export function unfreezeEdge(sourceChunk: string, targetChunk: string, selector: EdgeSelector = {}): number {
+  return setEdgeFrozen(sourceChunk, targetChunk, false, selector);
+}
+
+function setEdgeFrozen(sourceChunk: string, targetChunk: string, frozen: boolean, selector: EdgeSelector): number {
+  const { where, args } = edgeFilter(sourceChunk, targetChunk, selector);
+  return db.prepare(`
+    UPDATE connections
+    SET frozen = ?
+    WHERE ${where}
+  `).run(frozen ? 1 : 0, ...args).changes;
+}
+
+// ── Edge limit ─────────────────────────────────────────────────────────────
+
+export type EdgeLimitPolicy = 'reject' | 'evict_weakest';
+
+/** Connections touching `chunkId` in either direction. */
+export function countIncidentEdges(chunkId: string, database: string = DEFAULT_MEMORY_DB): number {
+  const row = db.prepare(`
+    SELECT
+      (SELECT COUNT(*) FROM connections WHERE source_chunk = ? AND database_id = ?) +
+      (SELECT COUNT(*) FROM connections WHERE target_chunk = ? AND database_id = ? AND source_chunk != target_chunk) AS total
+  `).get(chunkId, database, chunkId, database) as { total: number };
+  return row.total;
+}
+
+type EdgeEndpoints = { edge_id: string; source_chunk: string; target_chunk: string };
+
+/**
+ * Decide whether a new edge source → target fits under the per-chunk edge cap
+ * without changing anything. Returns the edges to evict to make room (empty
+ * when both endpoints have space), or null when the edge must be refused:
+ * under `reject` whenever an endpoint is full, under `evict_weakest` when a
+ * full endpoint has too few unfrozen edges to give up. Both endpoints are
+ * checked before any victim is chosen. A limit of 0 admits everything.
+ */
+export function planEdgeAdmission(
+  sourceChunk: string,
+  targetChunk: string,
+  database: string = DEFAULT_MEMORY_DB,
+  limit: number = MAX_EDGES_PER_CHUNK,
+  policy: EdgeLimitPolicy = MAX_EDGES_POLICY,
+): string[] | null {
+  if (!limit || limit <= 0) return [];
+
+  const weakest = db.prepare(`
+    SELECT edge_id, source_chunk, target_chunk
+    FROM connections
+    WHERE database_id = ?
+      AND (source_chunk = ? OR target_chunk = ?)
+      AND COALESCE(frozen, 0) = 0
+    ORDER BY COALESCE(weight, 0) ASC, created_at ASC, edge_id ASC
+  `);
+  const victims: EdgeEndpoints[] = [];
+
+  for (const endpoint of new Set([sourceChunk, targetChunk])) {
+    const alreadyFreed = victims.filter(edge => edge.source_chunk === endpoint || edge.target_chunk === endpoint).length;
+    const excess = countIncidentEdges(endpoint, database) - alreadyFreed - limit + 1;
+    if (excess <= 0) continue;
+    if (policy === 'reject') return null;
+
+    const chosen = new Set(victims.map(edge => edge.edge_id));
+    const candidates = (weakest.all(database, endpoint, endpoint) as EdgeEndpoints[])
+      .filter(edge => !chosen.has(edge.edge_id))
+      .slice(0, excess);
+    if (candidates.length < excess) return null;
+    victims.push(...candidates);
+  }
+
+  return victims.map(edge => edge.edge_id);
+}
+
+/**
+ * Insert an edge under the per-chunk edge cap. `insert` runs the caller's
+ * INSERT and returns its change count; room is made by evicting the planned
+ * victims only when that insert actually wrote a row, so an ignored insert
+ * (triplet or edge_id already stored) never costs an edge. Plan, insert and
+ * eviction share one transaction. Returns the change count (0 when refused).
+ */
+export function insertWithinEdgeLimit(
+  sourceChunk: string,
+  targetChunk: string,
+  insert: () => number,
+  database: string = DEFAULT_MEMORY_DB,
+  limit: number = MAX_EDGES_PER_CHUNK,
+  policy: EdgeLimitPolicy = MAX_EDGES_POLICY,
+): number {
+  return db.transaction(() => {
+    const victims = planEdgeAdmission(sourceChunk, targetChunk, database, limit, policy);
+    if (victims === null) return 0;
+
+    const changes = insert();
+    if (changes > 0 && victims.length > 0) {
+      const deleteEdge = db.prepare('DELETE FROM connections WHERE edge_id = ?');
+      for (const edgeId of victims) deleteEdge.run(edgeId);
+    }
+    return changes;
+  })();
+}
+
+export function countFrozenEdges(database: string = DEFAULT_MEMORY_DB): number {
+  const row = db.prepare(`
+    SELECT COUNT(*) AS total
+    FROM connections
+    WHERE database_id = ?
+      AND COALESCE(frozen, 0) = 1
+  `).get(database) as { total: number };
+  return row.total;
+}
+
+export interface AdjustWeightsResult {
+  adjusted: number;   // edges whose weight was changed
+  missing: Array<{ source: string; target: string }>;
+}
+
+/**
+ * Curation helper: add `delta` to the weight of every listed source → target
+ * edge (all relationships unless the selector names one), clamped to the
+ * connection range, in one transaction. Bookkeeping matches `setEdgeWeight`.
+ * Pairs with no matching edge are reported back instead of failing the batch.
+ */
+export function adjustEdgeWeights(
+  pairs: Array<{ source: string; target: string }>,
+  delta: number,
+  selector: EdgeSelector = {},
+): AdjustWeightsResult {
+  if (!Number.isFinite(delta)) {
+    throw new Error('delta must be a finite number');
+  }
+
+  const result: AdjustWeightsResult = { adjusted: 0, missing: [] };
+  const now = new Date().toISOString();
+
+  db.transaction(() => {
+    for (const pair of pairs) {
+      const { where, args } = edgeFilter(pair.source, pair.target, selector);
+      const changed = db.prepare(`
+        UPDATE connections
+        SET weight = MIN(?, MAX(?, COALESCE(weight, ?) + ?)),
+            weight_version = COALESCE(weight_version, 1) + 1,
+            last_reinforced = ?
+        WHERE ${where}
+      `).run(MAX_CONNECTION_WEIGHT, MIN_CONNECTION_WEIGHT, MIN_CONNECTION_WEIGHT, delta, now, ...args);
+
+      if (changed.changes > 0) {
+        result.adjusted += changed.changes;
+      } else {
+        result.missing.push({ source: pair.source, target: pair.target });
+      }
+    }
+  })();
+
+  return result;
+}
+
+/**
+ * Delete connections that decay has driven to the weight floor (or that were
+ * imported at zero). Retrieval never follows them, so they only cost scan
+ * time; this is far cheaper than a consolidation pass. Frozen edges are kept.
+ * Without a database every database is swept. Returns the number of edges
+ * removed.
+ */
+export function sweepDeadConnections(database?: string, threshold: number = MIN_CONNECTION_WEIGHT): number {
+  const filters = ['weight IS NOT NULL', 'weight <= ?', 'COALESCE(frozen, 0) = 0'];
+  const args: Array<string | number> = [threshold];
+  if (database) {
+    filters.push('database_id = ?');
+    args.push(database);
+  }
+
+  return db.prepare(`
+    DELETE FROM connections
+    WHERE ${filters.join(' AND ')}
+  `).run(...args).changes;
+}
+
+const HOUR_MS = 60 * 60 * 1000;
+const DAY_MS = 24 * HOUR_MS;
+
+export const DEFAULT_EDGE_AGE_BUCKETS_MS = [HOUR_MS, DAY_MS, 7 * DAY_MS, 30 * DAY_MS];
+
+/**
+ * Count edges by age since `created_at`. `bucketsMs` are ascending upper
+ * bounds; the result has one extra trailing slot for edges older than the last
+ * bound. Edges with a missing or unparseable `created_at` land in that slot too.
+ */
+export function getEdgeAgeDistribution(
+  bucketsMs: number[] = DEFAULT_EDGE_AGE_BUCKETS_MS,
+  database: string = DEFAULT_MEMORY_DB,
+  nowMs: number = Date.now(),
+): number[] {
+  const bounds = [...bucketsMs].sort((a, b) => a - b);
+  const counts = new Array<number>(bounds.length + 1).fill(0);
+
+  const rows = db.prepare(`
+    SELECT created_at
+    FROM connections
+    WHERE database_id = ?
+  `).all(database) as Array<{ created_at: string | null }>;
+
+  for (const row of rows) {
+    const createdMs = row.created_at ? Date.parse(row.created_at) : Number.NaN;
+    if (!Number.isFinite(createdMs)) {
+      counts[bounds.length]++;
+      continue;
+    }
+
+    const age = Math.max(0, nowMs - createdMs);
+    const index = bounds.findIndex(bound => age <= bound);
+    counts[index === -1 ? bounds.length : index]++;
+  }
+
+  return counts;
+}
+
+/**
+ * Iterate every connection in a database in edge_id order, optionally keeping
+ * only edges at or above `minWeight` and of the given relationship types.
+ * Rows are fetched a page at a time so huge graphs stream in flat memory.
+ */
+export function* iterateConnections(filter: EdgeListFilter = {}): Generator<ConnectionRow> {
+  const database = filter.database || DEFAULT_MEMORY_DB;
+  const pageSize = Math.max(1, Math.floor(filter.pageSize ?? 500));
+  const relationships = (filter.relationships ?? []).map(value => value.trim()).filter(Boolean);
+
+  const clauses = ['database_id = ?', 'edge_id > ?'];
+  const args: Array<string | number> = [database];
+  if (typeof filter.minWeight === 'number' && Number.isFinite(filter.minWeight)) {
+    clauses.push('COALESCE(weight, 0) >= ?');
+  }
+  if (relationships.length > 0) {
+    clauses.push(`relationship IN (${relationships.map(() => '?').join(', ')})`);
+  }
+
+  const stmt = db.prepare(`
+    SELECT edge_id, source_chunk, target_chunk, relationship,
+           COALESCE(weight, 0) AS weight, COALESCE(confidence, 0.5) AS confidence, created_at
+    FROM connections
+    WHERE ${clauses.join(' AND ')}
+    ORDER BY edge_id
+    LIMIT ?
+  `);
+
+  let lastEdgeId = '';
+  while (true) {
+    const params: Array<string | number> = [...args, lastEdgeId];
+    if (typeof filter.minWeight === 'number' && Number.isFinite(filter.minWeight)) params.push(filter.minWeight);
+    params.push(...relationships, pageSize);
+
+    const rows = stmt.all(...params) as ConnectionRow[];
+    for (const row of rows) {
+      yield row;
+    }
+    if (rows.length < pageSize) break;
+    lastEdgeId = rows[rows.length - 1].edge_id;
+  }
+}
+
+export type ValidationIssueKind = 'dangling_edge' | 'self_loop' | 'duplicate_reciprocal';
+
+export interface ValidationIssue {
+  kind: ValidationIssueKind;
+  edge_id: string;
+  source_chunk: string;
+  target_chunk: string;
+  detail: string;
+}
+
+/**
+ * Consistency check over a database's connections, handy after bulk imports:
+ * edges whose endpoint chunk is missing, self-loops, and reciprocal pairs
+ * (A → B and B → A with the same relationship). Reciprocal pairs are reported
+ * once, on the edge with the larger edge_id.
+ */
+export function validateGraph(database: string = DEFAULT_MEMORY_DB): ValidationIssue[] {
+  const issues: ValidationIssue[] = [];
+
+  const dangling = db.prepare(`
+    SELECT edge_id, source_chunk, target_chunk, has_source, has_target
+    FROM (
+      SELECT e.edge_id, e.source_chunk, e.target_chunk,
+             (SELECT 1 FROM chunks c WHERE c.chunk_id = e.source_chunk AND c.database_id = e.database_id) AS has_source,
+             (SELECT 1 FROM chunks c WHERE c.chunk_id = e.target_chunk AND c.database_id = e.database_id) AS has_target
+      FROM connections e
+      WHERE e.database_id = ?
+    )
+    WHERE has_source IS NULL OR has_target IS NULL
+    ORDER BY edge_id
+  `).all(database) as Array<{ edge_id: string; source_chunk: string; target_chunk: string; has_source: number | null; has_target: number | null }>;
+
+  for (const row of dangling) {
+    const missing = [row.has_source ? null : row.source_chunk, row.has_target ? null : row.target_chunk].filter(Boolean);
+    issues.push({
+      kind: 'dangling_edge',
+      edge_id: row.edge_id,
+      source_chunk: row.source_chunk,
+      target_chunk: row.target_chunk,
+      detail: `missing chunk ${missing.join(', ')}`,
+    });
+  }
+
+  const loops = db.prepare(`
+    SELECT edge_id, source_chunk, target_chunk, relationship
+    FROM connections
+    WHERE database_id = ?
+      AND source_chunk = target_chunk
+    ORDER BY edge_id
+  `).all(database) as Array<{ edge_id: string; source_chunk: string; target_chunk: string; relationship: string }>;
+
+  for (const row of loops) {
+    issues.push({ ...pickEdge(row), kind: 'self_loop', detail: `${row.relationship} edge points at itself` });
+  }
+
+  const reciprocal = db.prepare(`
+    SELECT a.edge_id, a.source_chunk, a.target_chunk, a.relationship, b.edge_id AS twin_id
+    FROM connections a
+    JOIN connections b
+      ON b.database_id = a.database_id
+     AND b.source_chunk = a.target_chunk
+     AND b.target_chunk = a.source_chunk
+     AND b.relationship = a.relationship
+     AND b.edge_id < a.edge_id
+    WHERE a.database_id = ?
+      AND a.source_chunk != a.target_chunk
+    ORDER BY a.edge_id
+  `).all(database) as Array<{ edge_id: string; source_chunk: string; target_chunk: string; relationship: string; twin_id: string }>;
+
+  for (const row of reciprocal) {
+    issues.push({ ...pickEdge(row), kind: 'duplicate_reciprocal', detail: `${row.relationship} also stored as ${row.twin_id}` });
+  }
+
+  return issues;
+}
+
+function pickEdge(row: { edge_id: string; source_chunk: string; target_chunk: string }): Pick<ValidationIssue, 'edge_id' | 'source_chunk' | 'target_chunk'> {
+  return { edge_id: row.edge_id, source_chunk: row.source_chunk, target_chunk: row.target_chunk };
+}
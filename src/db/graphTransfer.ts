@@ -0,0 +1,551 @@
+// src/db/graphTransfer.ts — Paged graph export / streaming import
+import { v4 as uuidv4 } from 'uuid';
+import { db, qdrant, COLLECTION, DEFAULT_MEMORY_DB } from './index';
+import { embedBatch } from '../embed';
+import { limitChunkContent, isConfidence } from '../ingest/filters';
+import { withStoreRetry } from './retry';
+import { contentKey } from '../ingest/inflight';
+import { insertWithinEdgeLimit } from './connections';
+import { EXPORT_WEIGHT_PRECISION } from '../config';
+
+const DEFAULT_PAGE_SIZE = 500;
+const IMPORT_EMBED_BATCH = 32;
+const DEFAULT_PROGRESS_EVERY = 1000;
+
+export type GraphChunk = {
+  chunk_id: string;
+  text: string;
+  source: string;
+  page: number;
+  timestamp: string;
+  access_count: number;
+  tags: string;
+  metadata: string;
+  confidence?: number;   // absent from older exports; imported as 1
+};
+
+export type GraphConnection = {
+  edge_id: string;
+  source_chunk: string;
+  target_chunk: string;
+  relationship: string;
+  weight: number;
+  weight_q16?: number;   // set instead of `weight` by u16 exports
+  confidence: number;
+  created_at: string;
+};
+
+/**
+ * How connection weights travel in an export. `f64` sends the stored double;
+ * `u16` sends `weight_q16 = round(weight * 65535)` and leaves `weight` unset,
+ * a varint of at most 3 bytes instead of 8. Weights live in [0, 1], so the
+ * fixed-point grid is uniform and the round-trip error is at most 1/131070.
+ */
+export type WeightPrecision = 'f64' | 'u16';
+
+const WEIGHT_Q16_SCALE = 65535;
+
+export function quantizeWeight(weight: number): number {
+  return Math.round(Math.min(1, Math.max(0, weight)) * WEIGHT_Q16_SCALE);
+}
+
+export function dequantizeWeight(quantized: number): number {
+  return quantized / WEIGHT_Q16_SCALE;
+}
+
+/** The weight an imported edge should store, whichever encoding it arrived in. */
+function decodeWeight(edge: GraphConnection): number {
+  return edge.weight_q16 ? dequantizeWeight(edge.weight_q16) : edge.weight;
+}
+
+/** One streamed record — exactly one of `chunk` / `connection` is set. */
+export type GraphRecord = {
+  database?: string;
+  chunk?: GraphChunk | null;
+  connection?: GraphConnection | null;
+};
+
+export type ImportGraphResult = {
+  chunks_imported: number;
+  chunks_skipped: number;
+  connections_imported: number;
+  connections_skipped: number;
+  placeholders_created?: number;
+  chunks_merged?: number;         // duplicate chunks folded into an existing one
+  connections_merged?: number;    // rewired edges folded into an existing edge
+};
+
+/**
+ * What `importGraph` does with a connection endpoint that is neither imported
+ * nor already stored: reject the whole import, or create an empty placeholder
+ * chunk (metadata `{"placeholder":true}`) that a later import fills in.
+ */
+export type UnknownEndpointPolicy = 'reject' | 'placeholder';
+
+/**
+ * Progress reporting for long imports: `onProgress` receives the number of
+ * records consumed so far every `progressEvery` records, and once more at the
+ * end if the total is not a multiple.
+ */
+export type ImportProgressOptions = {
+  onProgress?: (processed: number) => void;
+  progressEvery?: number;
+};
+
+/**
+ * How `importGraph` treats imported chunks whose text (whitespace-normalized)
+ * matches a chunk already in the database or earlier in the import: keep them
+ * all, drop the duplicates along with their edges, or fold the duplicates into
+ * the surviving chunk, rewiring their edges onto it and adding their access
+ * counts.
+ */
+export type ImportDedupPolicy = 'none' | 'content' | 'content_merge';
+
+export function isImportDedupPolicy(value: unknown): value is ImportDedupPolicy {
+  return value === 'none' || value === 'content' || value === 'content_merge';
+}
+
+const PLACEHOLDER_METADATA = '{"placeholder":true}';
+
+/**
+ * Walk a database page by page: every chunk first, then every connection, so
+ * an importer has seen both endpoints before any edge arrives. Keyset
+ * pagination keeps memory flat regardless of graph size.
+ */
+export function* iterateGraphExport(
+  database: string = DEFAULT_MEMORY_DB,
+  pageSize: number = DEFAULT_PAGE_SIZE,
+  weightPrecision: WeightPrecision = EXPORT_WEIGHT_PRECISION,
+): Generator<GraphRecord> {
+  const limit = Math.max(1, Math.floor(pageSize) || DEFAULT_PAGE_SIZE);
+
+  const chunkPage = db.prepare(`
+    SELECT chunk_id, text, source, COALESCE(page, 0) AS page, timestamp,
+           COALESCE(access_count, 0) AS access_count,
+           COALESCE(tags, '[]') AS tags, COALESCE(metadata, '{}') AS metadata,
+           COALESCE(confidence, 1.0) AS confidence
+    FROM chunks
+    WHERE database_id = ? AND chunk_id > ?
+    ORDER BY chunk_id
+    LIMIT ?
+  `);
+  let lastChunkId = '';
+  while (true) {
+    const rows = chunkPage.all(database, lastChunkId, limit) as GraphChunk[];
+    for (const chunk of rows) {
+      yield { database, chunk };
+    }
+    if (rows.length < limit) break;
+    lastChunkId = rows[rows.length - 1].chunk_id;
+  }
+
+  const connectionPage = db.prepare(`
+    SELECT edge_id, source_chunk, target_chunk, relationship,
+           COALESCE(weight, 0.3) AS weight, COALESCE(confidence, 0.5) AS confidence, created_at
+    FROM connections
+    WHERE database_id = ? AND edge_id > ?
+    ORDER BY edge_id
+    LIMIT ?
+  `);
+  let lastEdgeId = '';
+  while (true) {
+    const rows = connectionPage.all(database, lastEdgeId, limit) as GraphConnection[];
+    for (const connection of rows) {
+      yield weightPrecision === 'u16'
+        ? { database, connection: { ...connection, weight: 0, weight_q16: quantizeWeight(connection.weight) } }
+        : { database, connection };
+    }
+    if (rows.length < limit) break;
+    lastEdgeId = rows[rows.length - 1].edge_id;
+  }
+}
+
+/** A record the import cannot store as given; nothing after it is loaded. */
+export class InvalidGraphRecordError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = 'InvalidGraphRecordError';
+  }
+}
+
+/**
+ * Load a record stream produced by `iterateGraphExport` (possibly on another
+ * server). Chunks are re-embedded in batches and keep their ids; connections
+ * keep their stored weights (dequantized when sent as `weight_q16`) and are
+ * skipped when either endpoint is missing.
+ * Existing chunk/edge ids are left untouched. Records are consumed one at a
+ * time and chunks flushed every embed batch, so memory stays bounded however
+ * long the stream is. `finalize` runs in a transaction after the last record
+ * (the same one as the final chunk batch when one is pending), so its writes
+ * never land for an import that fails part way.
+ */
+export async function importGraphRecords(
+  records: AsyncIterable<GraphRecord> | Iterable<GraphRecord>,
+  fallbackDatabase: string = DEFAULT_MEMORY_DB,
+  progress: ImportProgressOptions = {},
+  finalize?: () => void,
+): Promise<ImportGraphResult> {
+  const progressEvery = Math.max(1, Math.floor(progress.progressEvery ?? DEFAULT_PROGRESS_EVERY) || DEFAULT_PROGRESS_EVERY);
+  let processed = 0;
+
+  const result: ImportGraphResult = {
+    chunks_imported: 0,
+    chunks_skipped: 0,
+    connections_imported: 0,
+    connections_skipped: 0,
+  };
+
+  // Placeholders count as absent so their real content can land on them
+  const chunkExists = db.prepare(`
+    SELECT 1 FROM chunks
+    WHERE chunk_id = ?
+      AND COALESCE(json_extract(metadata, '$.placeholder'), 0) = 0
+  `);
+  const endpointExists = db.prepare('SELECT 1 FROM chunks WHERE chunk_id = ? AND database_id = ?');
+  const insertChunk = db.prepare(`
+    INSERT INTO chunks (chunk_id, text, source, page, timestamp, access_count, tags, metadata, database_id, confidence)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+    ON CONFLICT(chunk_id) DO UPDATE SET
+      text = excluded.text,
+      source = excluded.source,
+      page = excluded.page,
+      timestamp = excluded.timestamp,
+      access_count = excluded.access_count,
+      tags = excluded.tags,
+      metadata = excluded.metadata,
+      confidence = excluded.confidence
+    WHERE json_extract(chunks.metadata, '$.placeholder') = 1
+      AND chunks.database_id = excluded.database_id
+  `);
+  const insertConnection = db.prepare(`
+    INSERT OR IGNORE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+
+  let pending: Array<{ chunk: GraphChunk; database: string }> = [];
+
+  const flushChunks = async (final: boolean = false): Promise<void> => {
+    if (pending.length === 0) {
+      if (final && finalize) db.transaction(finalize)();
+      return;
+    }
+    const batch = pending;
+    pending = [];
+
+    const vectors = await embedBatch(batch.map(item => item.chunk.text));
+    await withStoreRetry(() => qdrant.upsert(COLLECTION, {
+      wait: true,
+      points: batch.map((item, index) => ({
+        id: item.chunk.chunk_id,
+        vector: vectors[index],
+        payload: {
+          text: item.chunk.text,
+          source: item.chunk.source,
+          chunk_id: item.chunk.chunk_id,
+          database_id: item.database,
+        },
+      })),
+    }));
+
+    db.transaction(() => {
+      for (const { chunk, database } of batch) {
+        insertChunk.run(
+          chunk.chunk_id,
+          chunk.text,
+          chunk.source,
+          chunk.page ?? 0,
+          chunk.timestamp || new Date().toISOString(),
+          chunk.access_count ?? 0,
+          chunk.tags || '[]',
+          chunk.metadata || '{}',
+          database,
+          chunk.confidence ?? 1,
+        );
+      }
+      if (final && finalize) finalize();
+    })();
+    result.chunks_imported += batch.length;
+  };
+
+  for await (const record of records) {
+    processed++;
+    if (progress.onProgress && processed % progressEvery === 0) progress.onProgress(processed);
+    const database = record.database?.trim() || fallbackDatabase;
+
+    if (record.chunk) {
+      const chunk = record.chunk;
+      if (!chunk.chunk_id || !chunk.text?.trim() || chunkExists.get(chunk.chunk_id)) {
+        result.chunks_skipped++;
+        continue;
+      }
+      if (chunk.confidence !== undefined && !isConfidence(chunk.confidence)) {
+        throw new InvalidGraphRecordError(`Chunk ${chunk.chunk_id}: confidence must be between 0 and 1`);
+      }
+      pending.push({ chunk, database });
+      if (pending.length >= IMPORT_EMBED_BATCH) await flushChunks();
+      continue;
+    }
+
+    if (record.connection) {
+      await flushChunks();
+      const edge = record.connection;
+      const endpointsPresent = Boolean(endpointExists.get(edge.source_chunk, database))
+        && Boolean(endpointExists.get(edge.target_chunk, database));
+      if (!edge.edge_id || !endpointsPresent) {
+        result.connections_skipped++;
+        continue;
+      }
+      // An edge already stored makes the insert a no-op, so it never evicts
+      const inserted = insertWithinEdgeLimit(edge.source_chunk, edge.target_chunk, () => insertConnection.run(
+        edge.edge_id,
+        edge.source_chunk,
+        edge.target_chunk,
+        edge.relationship || 'related_to',
+        decodeWeight(edge),
+        edge.confidence ?? 0.5,
+        edge.created_at || new Date().toISOString(),
+        database,
+      ).changes, database);
+      if (inserted > 0) {
+        result.connections_imported++;
+      } else {
+        result.connections_skipped++;
+      }
+    }
+  }
+
+  await flushChunks(true);
+  if (progress.onProgress && processed % progressEvery !== 0) progress.onProgress(processed);
+  return result;
+}
+
+// ── Adjacency export ───────────────────────────────────────────────────────
+
+const MAX_DENSE_ADJACENCY_NODES = 2000;
+
+/** Non-zero entries as [row, column, weight] triples into `chunk_ids`. */
+export type AdjacencyCoo = { chunk_ids: string[]; entries: Array<[number, number, number]> };
+export type AdjacencyDense = { chunk_ids: string[]; matrix: number[][] };
+
+/**
+ * The weighted graph as a matrix for offline analysis. Rows and columns follow
+ * `chunk_ids` (every chunk in the database, sorted); entry [i][j] is the weight
+ * of the edge i → j, the strongest one when several relationships link the
+ * pair. Edges to missing chunks are left out. The sparse COO form scales to any
+ * graph; the dense form is refused above MAX_DENSE_ADJACENCY_NODES chunks.
+ */
+export function exportAdjacency(database?: string, format?: 'coo'): AdjacencyCoo;
+export function exportAdjacency(database: string, format: 'dense'): AdjacencyDense;
+export function exportAdjacency(
+  database: string = DEFAULT_MEMORY_DB,
+  format: 'coo' | 'dense' = 'coo',
+): AdjacencyCoo | AdjacencyDense {
+  const chunkIds = (db.prepare(`
+    SELECT chunk_id FROM chunks WHERE database_id = ? ORDER BY chunk_id
+  `).all(database) as Array<{ chunk_id: string }>).map(row => row.chunk_id);
+  const index = new Map(chunkIds.map((chunkId, position) => [chunkId, position]));
+
+  if (format === 'dense' && chunkIds.length > MAX_DENSE_ADJACENCY_NODES) {
+    throw new Error(`Dense adjacency is limited to ${MAX_DENSE_ADJACENCY_NODES} chunks (database has ${chunkIds.length}); use the coo format`);
+  }
+
+  const edges = db.prepare(`
+    SELECT source_chunk, target_chunk, MAX(COALESCE(weight, 0)) AS weight
+    FROM connections
+    WHERE database_id = ?
+    GROUP BY source_chunk, target_chunk
+    ORDER BY source_chunk, target_chunk
+  `).all(database) as Array<{ source_chunk: string; target_chunk: string; weight: number }>;
+
+  const entries: Array<[number, number, number]> = [];
+  for (const edge of edges) {
+    const row = index.get(edge.source_chunk);
+    const column = index.get(edge.target_chunk);
+    if (row === undefined || column === undefined || edge.weight === 0) continue;
+    entries.push([row, column, edge.weight]);
+  }
+
+  if (format === 'coo') return { chunk_ids: chunkIds, entries };
+
+  const matrix = chunkIds.map(() => new Array<number>(chunkIds.length).fill(0));
+  for (const [row, column, weight] of entries) {
+    matrix[row][column] = weight;
+  }
+  return { chunk_ids: chunkIds, matrix };
+}
+
+// ── Direct import ──────────────────────────────────────────────────────────
+
+export type ImportChunkInput = {
+  chunk_id?: string;
+  text: string;
+  source?: string;
+  access_count?: number;
+  tags?: string[];
+};
+
+export type ImportConnectionInput = {
+  edge_id?: string;
+  source_chunk: string;
+  target_chunk: string;
+  relationship?: string;
+  weight: number;
+  confidence?: number;
+};
+
+/**
+ * Load an external graph with its own weights, instead of letting ingest seed
+ * connections and consolidation learn them. Every connection endpoint must be
+ * one of the imported chunks or already exist in the database, weights must
+ * lie in [0, 1] and chunk text must fit `MAX_CHUNK_CHARS`; the whole import is
+ * rejected up front otherwise. With the `placeholder` policy unknown endpoints
+ * become placeholder chunks instead, so edges may arrive before their nodes.
+ * `dedup` folds or drops chunks that duplicate existing content.
+ */
+export async function importGraph(
+  input: { chunks?: ImportChunkInput[]; connections?: ImportConnectionInput[] },
+  database: string = DEFAULT_MEMORY_DB,
+  unknownEndpoints: UnknownEndpointPolicy = 'reject',
+  dedup: ImportDedupPolicy = 'none',
+): Promise<ImportGraphResult> {
+  const now = new Date().toISOString();
+  let chunks: GraphChunk[] = (input.chunks ?? []).map(chunk => ({
+    chunk_id: chunk.chunk_id?.trim() || uuidv4(),
+    text: limitChunkContent(chunk.text ?? ''),
+    source: chunk.source?.trim() || 'import',
+    page: 0,
+    timestamp: now,
+    access_count: chunk.access_count ?? 0,
+    tags: JSON.stringify(chunk.tags ?? []),
+    metadata: '{}',
+  }));
+
+  let connections = input.connections ?? [];
+  const duplicates = new Map<string, string>();   // duplicate chunk id -> surviving chunk id
+  const carriedAccess: Array<{ survivor: string; accessCount: number }> = [];
+  if (dedup !== 'none') {
+    const survivors = new Map<string, string>();
+    const stored = db.prepare(`SELECT chunk_id, text FROM chunks WHERE database_id = ? AND text != ''`).all(database) as Array<{ chunk_id: string; text: string }>;
+    for (const row of stored) {
+      survivors.set(contentKey(row.text, database), row.chunk_id);
+    }
+
+    chunks = chunks.filter(chunk => {
+      const key = contentKey(chunk.text, database);
+      const survivor = survivors.get(key);
+      if (survivor === undefined) {
+        survivors.set(key, chunk.chunk_id);
+        return true;
+      }
+      if (survivor === chunk.chunk_id) return true;
+      duplicates.set(chunk.chunk_id, survivor);
+      if (chunk.access_count > 0) carriedAccess.push({ survivor, accessCount: chunk.access_count });
+      return false;
+    });
+
+    connections = connections
+      .filter(edge => dedup === 'content_merge' || (!duplicates.has(edge.source_chunk) && !duplicates.has(edge.target_chunk)))
+      .map(edge => ({
+        ...edge,
+        source_chunk: duplicates.get(edge.source_chunk) ?? edge.source_chunk,
+        target_chunk: duplicates.get(edge.target_chunk) ?? edge.target_chunk,
+      }))
+      .filter(edge => edge.source_chunk !== edge.target_chunk);
+  }
+  const droppedConnections = (input.connections ?? []).length - connections.length;
+
+  const knownIds = new Set(chunks.map(chunk => chunk.chunk_id));
+  const existsStmt = db.prepare('SELECT 1 FROM chunks WHERE chunk_id = ? AND database_id = ?');
+  const placeholders = new Set<string>();
+
+  for (const edge of connections) {
+    for (const endpoint of [edge.source_chunk, edge.target_chunk]) {
+      if (!knownIds.has(endpoint) && !existsStmt.get(endpoint, database)) {
+        if (unknownEndpoints !== 'placeholder' || !endpoint?.trim()) {
+          throw new Error(`Unknown connection endpoint: ${endpoint}`);
+        }
+        placeholders.add(endpoint);
+      }
+    }
+    if (!Number.isFinite(edge.weight) || edge.weight < 0 || edge.weight > 1) {
+      throw new Error(`Connection weight must be within [0, 1]: ${edge.source_chunk} -> ${edge.target_chunk}`);
+    }
+  }
+
+  // Placeholders have no text and no vector; retrieval skips them until filled
+  if (placeholders.size > 0) {
+    const insertPlaceholder = db.prepare(`
+      INSERT OR IGNORE INTO chunks (chunk_id, text, source, timestamp, metadata, database_id)
+      VALUES (?, '', 'placeholder', ?, ?, ?)
+    `);
+    db.transaction(() => {
+      for (const chunkId of placeholders) {
+        insertPlaceholder.run(chunkId, now, PLACEHOLDER_METADATA, database);
+      }
+    })();
+  }
+
+  let connectionsMerged = 0;
+  // Merges into stored rows are written with the last import batch, so a
+  // failed import leaves survivors' access counts and weights untouched
+  const storedAccess: Array<{ survivor: string; accessCount: number }> = [];
+  const strengthened: Array<{ edgeId: string; weight: number }> = [];
+  if (dedup === 'content_merge' && duplicates.size > 0) {
+    // Access history of a folded duplicate carries over to its survivor
+    const importedById = new Map(chunks.map(chunk => [chunk.chunk_id, chunk]));
+    for (const carried of carriedAccess) {
+      const imported = importedById.get(carried.survivor);
+      if (imported) imported.access_count += carried.accessCount;
+      else storedAccess.push(carried);
+    }
+
+    // A rewired edge that already exists keeps the stronger weight
+    const existingEdge = db.prepare(`
+      SELECT edge_id FROM connections
+      WHERE source_chunk = ? AND target_chunk = ? AND relationship = ? AND database_id = ?
+      LIMIT 1
+    `);
+    connections = connections.filter(edge => {
+      const row = existingEdge.get(edge.source_chunk, edge.target_chunk, edge.relationship?.trim() || 'related_to', database) as { edge_id: string } | undefined;
+      if (!row) return true;
+      strengthened.push({ edgeId: row.edge_id, weight: edge.weight });
+      connectionsMerged++;
+      return false;
+    });
+  }
+  const addAccess = db.prepare(`UPDATE chunks SET access_count = COALESCE(access_count, 0) + ? WHERE chunk_id = ? AND database_id = ?`);
+  const strengthen = db.prepare(`
+    UPDATE connections SET weight = MAX(COALESCE(weight, 0), ?)
+    WHERE edge_id = ? AND COALESCE(frozen, 0) = 0
+  `);
+  const applyMerges = (): void => {
+    for (const { survivor, accessCount } of storedAccess) addAccess.run(accessCount, survivor, database);
+    for (const { edgeId, weight } of strengthened) strengthen.run(weight, edgeId);
+  };
+
+  const records: GraphRecord[] = [
+    ...chunks.map(chunk => ({ database, chunk })),
+    ...connections.map(edge => ({
+      database,
+      connection: {
+        edge_id: edge.edge_id?.trim() || uuidv4(),
+        source_chunk: edge.source_chunk,
+        target_chunk: edge.target_chunk,
+        relationship: edge.relationship?.trim() || 'related_to',
+        weight: edge.weight,
+        confidence: edge.confidence ?? 0.5,
+        created_at: now,
+      },
+    })),
+  ];
+
+  const result: ImportGraphResult = await importGraphRecords(records, database, {}, applyMerges);
+  if (placeholders.size > 0) result.placeholders_created = placeholders.size;
+  if (dedup !== 'none') {
+    result.chunks_skipped += duplicates.size;
+    result.connections_skipped += droppedConnections;
+    result.chunks_merged = dedup === 'content_merge' ? duplicates.size : 0;
+    result.connections_merged = connectionsMerged;
+  }
+  return result;
+}
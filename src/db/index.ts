@@ -3,7 +3,7 @@ import { QdrantClient } from '@qdrant/js-client-rest';
 import Database from 'better-sqlite3';
 import path from 'path';
 import { v4 as uuidv4 } from 'uuid';
-import { EMBED_DIMS, QDRANT_URL, QDRANT_COLLECTION } from '../config';
+import { EMBED_DIMS, QDRANT_URL, QDRANT_COLLECTION, SQLITE_SYNCHRONOUS, SQLITE_CHECKPOINT_INTERVAL_MS } from '../config';
 
 const COLLECTION = QDRANT_COLLECTION;
 const CONCEPT_COLLECTION = `${QDRANT_COLLECTION}_concepts`;
@@ -56,7 +56,56 @@ const _betterSqliteAddon: string | undefined = (process as any).pkg
 
 export const db = new Database(DB_PATH, _betterSqliteAddon ? { nativeBinding: _betterSqliteAddon } : undefined);
 db.pragma('journal_mode = WAL');
-db.pragma('synchronous = NORMAL');
+
+export const SYNCHRONOUS_MODES = ['off', 'normal', 'full', 'extra'] as const;
+export type SynchronousMode = typeof SYNCHRONOUS_MODES[number];
+
+export function isSynchronousMode(value: unknown): value is SynchronousMode {
+  return typeof value === 'string' && (SYNCHRONOUS_MODES as readonly string[]).includes(value);
+}
+
+/**
+ * How often SQLite fsyncs. Under WAL, `normal` syncs only at checkpoints: a
+ * crash of the process loses nothing, a power cut can lose the last commits.
+ * `full` syncs the WAL on every commit; `off` never syncs and leaves
+ * durability to the OS.
+ */
+export function setSynchronousMode(mode: SynchronousMode): void {
+  if (!isSynchronousMode(mode)) {
+    throw new Error(`synchronous must be one of ${SYNCHRONOUS_MODES.join(', ')}`);
+  }
+  db.pragma(`synchronous = ${mode.toUpperCase()}`);
+}
+
+export function getSynchronousMode(): SynchronousMode {
+  const level = db.pragma('synchronous', { simple: true }) as number;
+  return SYNCHRONOUS_MODES[level] ?? 'normal';
+}
+
+setSynchronousMode(isSynchronousMode(SQLITE_SYNCHRONOUS) ? SQLITE_SYNCHRONOUS : 'normal');
+
+export type CheckpointResult = { busy: boolean; log_frames: number; checkpointed_frames: number };
+
+/** Copy committed WAL frames into the main database file without blocking writers. */
+export function checkpointWal(): CheckpointResult {
+  const [row] = db.pragma('wal_checkpoint(PASSIVE)') as Array<{ busy: number; log: number; checkpointed: number }>;
+  return { busy: row?.busy === 1, log_frames: row?.log ?? 0, checkpointed_frames: row?.checkpointed ?? 0 };
+}
+
+let checkpointTimer: NodeJS.Timeout | null = null;
+
+function startWalCheckpoints(intervalMs: number): void {
+  if (checkpointTimer || !Number.isFinite(intervalMs) || intervalMs <= 0) return;
+  checkpointTimer = setInterval(() => {
+    try {
+      checkpointWal();
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.warn(`⚠️  WAL checkpoint failed: ${message}`);
+    }
+  }, intervalMs);
+  checkpointTimer.unref();
+}
 
 export function addColumnIfMissing(table: string, definition: string) {
   try {
@@ -147,11 +196,17 @@ export function initSQLite() {
 
     CREATE UNIQUE INDEX IF NOT EXISTS idx_connections_unique_triplet
     ON connections (source_chunk, target_chunk, relationship);
+
+    CREATE INDEX IF NOT EXISTS idx_connections_target
+    ON connections (target_chunk);
   `);
 
   addColumnIfMissing('chunks', 'is_duplicate INTEGER DEFAULT 0');
   addColumnIfMissing('chunks', 'contradiction_flag INTEGER DEFAULT 0');
   addColumnIfMissing('chunks', 'metadata TEXT DEFAULT "{}"');
+  addColumnIfMissing('chunks', 'expires_at TEXT');   // NULL = never expires
+  addColumnIfMissing('chunks', 'confidence REAL DEFAULT 1.0');   // how far the chunk's source is trusted, 0–1
+  addColumnIfMissing('chunks', 'novelty REAL');   // 1 - best similarity to earlier chunks at ingest; NULL if not measured
   // Multi-database support
   addColumnIfMissing('chunks', 'database_id TEXT DEFAULT "default"');
   addColumnIfMissing('connections', 'database_id TEXT DEFAULT "default"');
@@ -170,6 +225,7 @@ export function initSQLite() {
   addColumnIfMissing('connections', 'avg_sim REAL DEFAULT 0');
   addColumnIfMissing('connections', 'evidence_score REAL DEFAULT 0');
   addColumnIfMissing('connections', 'weight_version INTEGER DEFAULT 1');
+  addColumnIfMissing('connections', 'frozen INTEGER DEFAULT 0');   // 1 = curated, plasticity leaves it alone
 
   // PHASE 5 — concept confidence/version
   addColumnIfMissing('concepts', 'confidence REAL DEFAULT 0.5');
@@ -179,9 +235,159 @@ export function initSQLite() {
   addColumnIfMissing('concepts', 'embedding_version INTEGER DEFAULT 0');
   addColumnIfMissing('concepts', 'embedding_updated_at TEXT');
 
+  initGraphVersionTracking();
+  initTextIndex();
+
   console.log('✅ SQLite schema ready');
 }
 
+// ── Text index ────────────────────────────────────
+// An FTS5 token index over chunk text, kept in step with `chunks` by triggers
+// so every write path (ingest, gRPC, import, edits) maintains it. Literal
+// search uses it to narrow multi-word queries to chunks sharing a token
+// instead of scanning the whole database.
+
+let textIndexReady = false;
+
+export function hasTextIndex(): boolean {
+  return textIndexReady;
+}
+
+function initTextIndex() {
+  // INSERT OR REPLACE only fires delete triggers for the row it removes when
+  // recursive triggers are on; without them the replaced row's index entry
+  // would be left behind. The pragma is connection-wide. That is safe here
+  // because no trigger writes to a table whose triggers lead back to it: the
+  // graph-version triggers only touch graph_versions, which has none. The one
+  // change elsewhere is that a REPLACE also bumps the version for the row it
+  // removes, which only moves the counter on.
+  db.pragma('recursive_triggers = ON');
+
+  try {
+    db.exec(`
+      CREATE VIRTUAL TABLE IF NOT EXISTS chunks_fts USING fts5(
+        text,
+        chunk_id UNINDEXED,
+        database_id UNINDEXED,
+        tokenize = 'unicode61'
+      );
+
+      CREATE TRIGGER IF NOT EXISTS trg_chunks_fts_insert AFTER INSERT ON chunks
+      BEGIN
+        INSERT INTO chunks_fts (rowid, text, chunk_id, database_id)
+        VALUES (NEW.rowid, NEW.text, NEW.chunk_id, COALESCE(NEW.database_id, '${DEFAULT_MEMORY_DB}'));
+      END;
+
+      CREATE TRIGGER IF NOT EXISTS trg_chunks_fts_update AFTER UPDATE OF text, chunk_id, database_id ON chunks
+      BEGIN
+        DELETE FROM chunks_fts WHERE rowid = OLD.rowid;
+        INSERT INTO chunks_fts (rowid, text, chunk_id, database_id)
+        VALUES (NEW.rowid, NEW.text, NEW.chunk_id, COALESCE(NEW.database_id, '${DEFAULT_MEMORY_DB}'));
+      END;
+
+      CREATE TRIGGER IF NOT EXISTS trg_chunks_fts_delete AFTER DELETE ON chunks
+      BEGIN
+        DELETE FROM chunks_fts WHERE rowid = OLD.rowid;
+      END;
+    `);
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    console.warn(`⚠️  Text index unavailable, literal search will scan: ${message}`);
+    return;
+  }
+
+  // Rows written before the index existed (or by a build without it) are
+  // picked up by a one-off rebuild on load.
+  const indexed = (db.prepare('SELECT COUNT(*) AS count FROM chunks_fts').get() as { count: number }).count;
+  const stored = (db.prepare('SELECT COUNT(*) AS count FROM chunks').get() as { count: number }).count;
+  if (indexed !== stored) {
+    db.transaction(() => {
+      db.exec('DELETE FROM chunks_fts');
+      db.exec(`
+        INSERT INTO chunks_fts (rowid, text, chunk_id, database_id)
+        SELECT rowid, text, chunk_id, COALESCE(database_id, '${DEFAULT_MEMORY_DB}') FROM chunks
+      `);
+    })();
+    console.log(`✅ Text index rebuilt (${stored} chunks)`);
+  }
+
+  textIndexReady = true;
+}
+
+// ── Graph version ─────────────────────────────────
+// A per-database counter bumped by triggers whenever chunk content, connection
+// structure/weights or concepts change. Access bookkeeping (access_count,
+// last_accessed, co-access events) deliberately does not bump it, so pure reads
+// never invalidate anything keyed on the version.
+
+const VERSIONED_TABLES: Array<{ table: string; columns: string }> = [
+  { table: 'chunks', columns: 'text, source, database_id' },
+  { table: 'connections', columns: 'source_chunk, target_chunk, relationship, weight, database_id' },
+  { table: 'concepts', columns: 'label, summary, member_chunks, confidence, database_id' },
+];
+
+function bumpVersionSql(row: 'NEW' | 'OLD'): string {
+  return `
+    INSERT INTO graph_versions (database_id, version)
+    VALUES (COALESCE(${row}.database_id, '${DEFAULT_MEMORY_DB}'), 1)
+    ON CONFLICT(database_id) DO UPDATE SET version = version + 1;
+  `;
+}
+
+function initGraphVersionTracking() {
+  db.exec(`
+    CREATE TABLE IF NOT EXISTS graph_versions (
+      database_id  TEXT PRIMARY KEY,
+      version      INTEGER NOT NULL DEFAULT 0
+    );
+  `);
+
+  for (const { table, columns } of VERSIONED_TABLES) {
+    db.exec(`
+      CREATE TRIGGER IF NOT EXISTS trg_${table}_version_insert AFTER INSERT ON ${table}
+      BEGIN ${bumpVersionSql('NEW')} END;
+
+      CREATE TRIGGER IF NOT EXISTS trg_${table}_version_update AFTER UPDATE OF ${columns} ON ${table}
+      BEGIN ${bumpVersionSql('NEW')} END;
+
+      CREATE TRIGGER IF NOT EXISTS trg_${table}_version_delete AFTER DELETE ON ${table}
+      BEGIN ${bumpVersionSql('OLD')} END;
+    `);
+  }
+}
+
+export function getGraphVersion(database: string = DEFAULT_MEMORY_DB): number {
+  const row = db.prepare('SELECT version FROM graph_versions WHERE database_id = ?').get(database) as { version: number } | undefined;
+  return row?.version ?? 0;
+}
+
+/**
+ * Compare-and-swap guard for mutating calls. An `expected` of 0/undefined means
+ * the caller did not ask for a check.
+ */
+export function graphVersionMatches(expected: number | undefined, database: string = DEFAULT_MEMORY_DB): boolean {
+  if (!expected) return true;
+  return getGraphVersion(database) === expected;
+}
+
+export class GraphVersionConflictError extends Error {
+  constructor(readonly expected: number, readonly current: number) {
+    super(`Graph version mismatch: expected ${expected}, current ${current}`);
+    this.name = 'GraphVersionConflictError';
+  }
+}
+
+/**
+ * Throw `GraphVersionConflictError` unless the version still matches. Call it
+ * inside the write transaction so the check and the write are atomic; a check
+ * made before awaited work only rejects early.
+ */
+export function assertGraphVersion(expected: number | undefined, database: string = DEFAULT_MEMORY_DB): void {
+  if (!graphVersionMatches(expected, database)) {
+    throw new GraphVersionConflictError(expected ?? 0, getGraphVersion(database));
+  }
+}
+
 async function initConceptQdrant() {
   await ensureCollection(CONCEPT_COLLECTION, VECTOR_SIZE, 'concept');
 }
@@ -195,6 +401,7 @@ export async function initDB() {
   await initConceptQdrant();
   await initImageQdrant();
   initSQLite();
+  startWalCheckpoints(SQLITE_CHECKPOINT_INTERVAL_MS);
 }
 
 export async function storeImageEmbedding(
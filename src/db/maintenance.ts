@@ -0,0 +1,32 @@
+// src/db/maintenance.ts — Mutual exclusion for whole-graph maintenance
+//
+// Locking discipline: consolidation (worker ticks and one-shot runs), expiry
+// sweeps and database clears rewrite many rows across several awaits, so they
+// run one at a time under this lock. Recall and ingest never take it — SQLite
+// statements are atomic on their own and those paths only ever see a graph
+// mid-maintenance in the same way they would between two worker ticks.
+
+let tail: Promise<void> = Promise.resolve();
+let holders = 0;
+
+/**
+ * Queue `task` behind every maintenance task already running or waiting and
+ * resolve with its result. A failing task releases the lock like any other.
+ */
+export function withMaintenanceLock<T>(task: () => Promise<T>): Promise<T> {
+  const run = tail.then(async () => {
+    holders++;
+    try {
+      return await task();
+    } finally {
+      holders--;
+    }
+  });
+  tail = run.then(() => undefined, () => undefined);
+  return run;
+}
+
+/** True while a maintenance task holds the lock. */
+export function isMaintenanceRunning(): boolean {
+  return holders > 0;
+}
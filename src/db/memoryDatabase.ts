@@ -1,6 +1,9 @@
 // src/db/memoryDatabase.ts — Memory database catalog helpers
 import { v4 as uuidv4 } from 'uuid';
-import { db, DEFAULT_MEMORY_DB } from './index';
+import { db, qdrant, COLLECTION, CONCEPT_COLLECTION, DEFAULT_MEMORY_DB } from './index';
+import { clearRecallCache } from '../retrieve/cache';
+import { withMaintenanceLock } from './maintenance';
+import { withStoreRetry } from './retry';
 
 export interface MemoryDatabase {
   id: string;
@@ -51,6 +54,78 @@ export function createDatabase(name: string, description?: string): MemoryDataba
   return { id, name: normalized, created_at: createdAt, description: desc, config_json: configJson };
 }
 
+/**
+ * Per-database recall defaults kept in `config_json`. They survive restarts
+ * and fill in any option a recall request leaves unset.
+ */
+export interface DatabaseConfig {
+  hopDecay?: number;           // in (0, 1)
+  degreeBoost?: number;        // >= 0
+  minContentLength?: number;   // integer >= 0
+}
+
+const DATABASE_CONFIG_KEYS = ['hopDecay', 'degreeBoost', 'minContentLength'] as const;
+
+function validateConfigValue(key: keyof DatabaseConfig, value: number): string | null {
+  if (typeof value !== 'number' || !Number.isFinite(value)) return `${key} must be a finite number`;
+  if (key === 'hopDecay' && !(value > 0 && value < 1)) return 'hopDecay must be between 0 and 1 (exclusive)';
+  if (key === 'degreeBoost' && value < 0) return 'degreeBoost must be >= 0';
+  if (key === 'minContentLength' && (value < 0 || !Number.isInteger(value))) return 'minContentLength must be a non-negative integer';
+  return null;
+}
+
+/** Stored config for `name`; unreadable or unknown entries are ignored. */
+export function getDatabaseConfig(name: string): DatabaseConfig {
+  const row = getDatabaseByName(normalizeDatabaseName(name));
+  if (!row?.config_json) return {};
+
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(row.config_json);
+  } catch {
+    return {};
+  }
+  if (!parsed || typeof parsed !== 'object') return {};
+
+  const config: DatabaseConfig = {};
+  for (const key of DATABASE_CONFIG_KEYS) {
+    const value = (parsed as Record<string, unknown>)[key];
+    if (typeof value === 'number' && validateConfigValue(key, value) === null) {
+      config[key] = value;
+    }
+  }
+  return config;
+}
+
+/**
+ * Merge `patch` into the stored config and persist it. A `null` value removes
+ * the key. Unknown keys and out-of-range values are rejected before anything
+ * is written. Returns the config now in effect.
+ */
+export function updateDatabaseConfig(name: string, patch: Record<string, unknown>): DatabaseConfig {
+  const normalized = normalizeDatabaseName(name);
+  if (!getDatabaseByName(normalized)) {
+    throw new Error(`Database not found: ${normalized}`);
+  }
+
+  const next: Record<string, number> = { ...getDatabaseConfig(normalized) };
+  for (const [key, value] of Object.entries(patch)) {
+    if (!(DATABASE_CONFIG_KEYS as readonly string[]).includes(key)) {
+      throw new Error(`Unknown config key: ${key} (expected ${DATABASE_CONFIG_KEYS.join(', ')})`);
+    }
+    if (value === null) {
+      delete next[key];
+      continue;
+    }
+    const error = validateConfigValue(key as keyof DatabaseConfig, value as number);
+    if (error) throw new Error(error);
+    next[key] = value as number;
+  }
+
+  db.prepare('UPDATE memory_databases SET config_json = ? WHERE name = ?').run(JSON.stringify(next), normalized);
+  return getDatabaseConfig(normalized);
+}
+
 export function deleteDatabase(name: string): void {
   const normalized = normalizeDatabaseName(name);
   if (!normalized) {
@@ -79,3 +154,62 @@ export function deleteDatabase(name: string): void {
 
   tx();
 }
+
+export type ClearDatabaseResult = {
+  chunks: number;
+  connections: number;
+  concepts: number;
+};
+
+/**
+ * Wipe every memory in a database but keep the database itself registered.
+ * SQLite rows go in one transaction (which also bumps the graph version);
+ * Qdrant points are removed afterwards by database_id / concept id.
+ */
+export function clearDatabase(name: string): Promise<ClearDatabaseResult> {
+  return withMaintenanceLock(() => clearDatabaseUnlocked(name));
+}
+
+async function clearDatabaseUnlocked(name: string): Promise<ClearDatabaseResult> {
+  const dbName = normalizeDatabaseName(name);
+  if (dbName !== DEFAULT_MEMORY_DB && !getDatabaseByName(dbName)) {
+    throw new Error(`Database not found: ${dbName}`);
+  }
+
+  const conceptIds = (db.prepare('SELECT concept_id FROM concepts WHERE database_id = ?').all(dbName) as Array<{ concept_id: string }>)
+    .map(row => row.concept_id);
+
+  const tx = db.transaction((): ClearDatabaseResult => {
+    const connections = db.prepare('DELETE FROM connections WHERE database_id = ?').run(dbName).changes;
+    const concepts = db.prepare('DELETE FROM concepts WHERE database_id = ?').run(dbName).changes;
+    const chunks = db.prepare('DELETE FROM chunks WHERE database_id = ?').run(dbName).changes;
+    db.prepare('DELETE FROM co_access_events WHERE database_id = ?').run(dbName);
+    db.prepare('DELETE FROM ingest_events WHERE database_id = ?').run(dbName);
+    // Move the version on rather than resetting it: a reset would let an
+    // expected_version from before the clear match the emptied graph.
+    db.prepare(`
+      INSERT INTO graph_versions (database_id, version) VALUES (?, 1)
+      ON CONFLICT(database_id) DO UPDATE SET version = version + 1
+    `).run(dbName);
+    return { chunks, connections, concepts };
+  });
+  const result = tx();
+
+  // The bumped version already misses every cached recall; drop them to free the memory.
+  clearRecallCache();
+
+  try {
+    await withStoreRetry(() => qdrant.delete(COLLECTION, {
+      wait: true,
+      filter: { must: [{ key: 'database_id', match: { value: dbName } }] },
+    }));
+    if (conceptIds.length > 0) {
+      await withStoreRetry(() => qdrant.delete(CONCEPT_COLLECTION, { wait: true, points: conceptIds }));
+    }
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    console.warn(`⚠️  Qdrant cleanup failed while clearing ${dbName}: ${message}`);
+  }
+
+  return result;
+}
@@ -0,0 +1,54 @@
+// src/db/retry.ts — Retry-with-backoff for transient Qdrant failures
+import { STORE_RETRY_ATTEMPTS, STORE_RETRY_BASE_MS } from '../config';
+
+export interface RetryOptions {
+  attempts?: number;      // total tries, including the first
+  baseDelayMs?: number;   // wait before the 2nd try; doubles each time after
+  isRetryable?: (error: unknown) => boolean;
+  sleep?: (ms: number) => Promise<void>;
+}
+
+const TRANSIENT_CODES = new Set(['ECONNRESET', 'ECONNREFUSED', 'ETIMEDOUT', 'EPIPE', 'EAI_AGAIN', 'UND_ERR_SOCKET']);
+
+/**
+ * Network drops, timeouts, 429 and 5xx responses are worth retrying; anything
+ * else (bad request, missing collection, wrong vector size) fails the same way
+ * every time and is surfaced at once.
+ */
+export function isTransientStoreError(error: unknown): boolean {
+  if (!error || typeof error !== 'object') return false;
+  const record = error as { status?: unknown; code?: unknown; cause?: unknown; message?: unknown };
+
+  if (typeof record.status === 'number') {
+    return record.status === 429 || record.status >= 500;
+  }
+  if (typeof record.code === 'string' && TRANSIENT_CODES.has(record.code)) return true;
+  if (typeof record.message === 'string' && /fetch failed|socket hang up|timed? ?out/i.test(record.message)) return true;
+  return record.cause !== undefined && record.cause !== error && isTransientStoreError(record.cause);
+}
+
+const defaultSleep = (ms: number) => new Promise<void>(resolve => setTimeout(resolve, ms));
+
+/**
+ * Run `task`, retrying transient failures with exponential backoff
+ * (base, 2×base, 4×base, ...). The last error is rethrown once the budget of
+ * STORE_RETRY_ATTEMPTS tries is spent or a non-retryable error occurs.
+ */
+export async function withStoreRetry<T>(task: () => Promise<T>, options: RetryOptions = {}): Promise<T> {
+  const attempts = Math.max(1, Math.floor(options.attempts ?? STORE_RETRY_ATTEMPTS));
+  const baseDelayMs = Math.max(0, options.baseDelayMs ?? STORE_RETRY_BASE_MS);
+  const isRetryable = options.isRetryable ?? isTransientStoreError;
+  const sleep = options.sleep ?? defaultSleep;
+
+  for (let attempt = 1; ; attempt++) {
+    try {
+      return await task();
+    } catch (error) {
+      if (attempt >= attempts || !isRetryable(error)) throw error;
+      const delayMs = baseDelayMs * 2 ** (attempt - 1);
+      const message = error instanceof Error ? error.message : String(error);
+      console.warn(`⚠️  Store write failed (attempt ${attempt}/${attempts}): ${message}; retrying in ${delayMs}ms`);
+      await sleep(delayMs);
+    }
+  }
+}
@@ -0,0 +1,106 @@
+// src/db/summary.ts — One-shot graph overview for dashboards
+import { db, DEFAULT_MEMORY_DB, getGraphVersion } from './index';
+import { getEdgeAgeDistribution, DEFAULT_EDGE_AGE_BUCKETS_MS, countFrozenEdges } from './connections';
+
+export const WEIGHT_HISTOGRAM_BOUNDS = [0.2, 0.4, 0.6, 0.8, 1.0];
+const TOP_CHUNK_COUNT = 5;
+
+export interface RelationshipSummary {
+  relationship: string;
+  count: number;
+  average_weight: number;
+}
+
+export interface GraphSummary {
+  database: string;
+  graph_version: number;
+  total_chunks: number;
+  total_connections: number;
+  total_concepts: number;
+  frozen_connections: number;      // edges plasticity leaves alone
+  average_weight: number;
+  consolidated_chunks: number;     // chunks that belong to at least one concept
+  consolidation_ratio: number;     // consolidated_chunks / total_chunks (0 when empty)
+  relationships: RelationshipSummary[];
+  top_chunks: Array<{ chunk_id: string; degree: number }>;   // highest in + out degree
+  weight_histogram: { bounds: number[]; counts: number[] };  // upper bounds; last slot catches anything above
+  edge_age_distribution: { buckets_ms: number[]; counts: number[] };
+  storage_bytes: number;           // size of the whole SQLite file, all databases
+}
+
+/**
+ * Everything a dashboard needs about one memory database in a single call,
+ * assembled from the same queries the individual stats endpoints use.
+ */
+export function getGraphSummary(database: string = DEFAULT_MEMORY_DB): GraphSummary {
+  const count = (sql: string): number => (db.prepare(sql).get(database) as { total: number }).total;
+
+  const totalChunks = count('SELECT COUNT(*) AS total FROM chunks WHERE database_id = ?');
+  const totalConcepts = count('SELECT COUNT(*) AS total FROM concepts WHERE database_id = ?');
+  const consolidatedChunks = count(`
+    SELECT COUNT(*) AS total
+    FROM chunks c
+    WHERE c.database_id = ?
+      AND EXISTS (
+        SELECT 1 FROM concepts k, json_each(k.member_chunks) m
+        WHERE k.database_id = c.database_id
+          AND m.value = c.chunk_id
+      )
+  `);
+
+  const relationships = db.prepare(`
+    SELECT relationship, COUNT(*) AS count, AVG(COALESCE(weight, 0)) AS average_weight
+    FROM connections
+    WHERE database_id = ?
+    GROUP BY relationship
+    ORDER BY count DESC, relationship ASC
+  `).all(database) as RelationshipSummary[];
+  const totalConnections = relationships.reduce((sum, row) => sum + row.count, 0);
+  const weightTotal = relationships.reduce((sum, row) => sum + row.average_weight * row.count, 0);
+
+  const topChunks = db.prepare(`
+    SELECT chunk_id, COUNT(*) AS degree
+    FROM (
+      SELECT source_chunk AS chunk_id FROM connections WHERE database_id = ?
+      UNION ALL
+      SELECT target_chunk AS chunk_id FROM connections WHERE database_id = ?
+    )
+    GROUP BY chunk_id
+    ORDER BY degree DESC, chunk_id ASC
+    LIMIT ?
+  `).all(database, database, TOP_CHUNK_COUNT) as Array<{ chunk_id: string; degree: number }>;
+
+  const histogram = new Array<number>(WEIGHT_HISTOGRAM_BOUNDS.length + 1).fill(0);
+  const weights = db.prepare(`
+    SELECT COALESCE(weight, 0) AS weight
+    FROM connections
+    WHERE database_id = ?
+  `).all(database) as Array<{ weight: number }>;
+  for (const { weight } of weights) {
+    const index = WEIGHT_HISTOGRAM_BOUNDS.findIndex(bound => weight <= bound);
+    histogram[index === -1 ? WEIGHT_HISTOGRAM_BOUNDS.length : index]++;
+  }
+
+  const pageCount = db.pragma('page_count', { simple: true }) as number;
+  const pageSize = db.pragma('page_size', { simple: true }) as number;
+
+  return {
+    database,
+    graph_version: getGraphVersion(database),
+    total_chunks: totalChunks,
+    total_connections: totalConnections,
+    total_concepts: totalConcepts,
+    frozen_connections: countFrozenEdges(database),
+    average_weight: totalConnections > 0 ? weightTotal / totalConnections : 0,
+    consolidated_chunks: consolidatedChunks,
+    consolidation_ratio: totalChunks > 0 ? consolidatedChunks / totalChunks : 0,
+    relationships,
+    top_chunks: topChunks,
+    weight_histogram: { bounds: WEIGHT_HISTOGRAM_BOUNDS, counts: histogram },
+    edge_age_distribution: {
+      buckets_ms: DEFAULT_EDGE_AGE_BUCKETS_MS,
+      counts: getEdgeAgeDistribution(DEFAULT_EDGE_AGE_BUCKETS_MS, database),
+    },
+    storage_bytes: pageCount * pageSize,
+  };
+}
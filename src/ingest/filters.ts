@@ -1,4 +1,5 @@
 // src/ingest/filters.ts — Chunk quality filters for the ingestion pipeline
+import { MAX_CHUNK_CHARS, MAX_CHUNK_CHARS_POLICY } from '../config';
 
 /**
  * Detect Wikipedia-style citation/reference blocks.
@@ -38,3 +39,45 @@ export function isGlossaryChunk(text: string): boolean {
 
   return defLines.length / lines.length > 0.45 && lines.length >= 4;
 }
+
+/**
+ * How new a chunk is relative to what was already stored: 1 minus its best
+ * cosine similarity to an existing chunk, clamped to [0, 1]. Near-duplicates
+ * score close to 0; an empty store (or no match at all) scores 1.
+ */
+export function noveltyScore(topSimilarity: number): number {
+  if (!Number.isFinite(topSimilarity)) return 1;
+  return 1 - Math.min(1, Math.max(0, topSimilarity));
+}
+
+/**
+ * A valid source confidence: a finite number in [0, 1]. Ingest, gRPC Ingest
+ * and graph import all accept exactly this range.
+ */
+export function isConfidence(value: unknown): value is number {
+  return typeof value === 'number' && Number.isFinite(value) && value >= 0 && value <= 1;
+}
+
+export class ContentTooLargeError extends Error {
+  constructor(length: number, limit: number) {
+    super(`Chunk content too large: ${length} characters exceeds the ${limit} character limit`);
+    this.name = 'ContentTooLargeError';
+  }
+}
+
+/**
+ * Enforce the chunk size limit. Text of exactly `maxChars` passes; longer text
+ * is truncated or rejected with `ContentTooLargeError` depending on `policy`.
+ * A limit of 0 disables the check. The chunkers only split on sentence
+ * boundaries, so this is what stops one giant unpunctuated run from becoming
+ * a single chunk.
+ */
+export function limitChunkContent(
+  text: string,
+  maxChars: number = MAX_CHUNK_CHARS,
+  policy: 'reject' | 'truncate' = MAX_CHUNK_CHARS_POLICY,
+): string {
+  if (maxChars <= 0 || text.length <= maxChars) return text;
+  if (policy === 'truncate') return text.slice(0, maxChars);
+  throw new ContentTooLargeError(text.length, maxChars);
+}
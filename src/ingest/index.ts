@@ -7,11 +7,16 @@ import { semanticChunkText, Chunk } from './chunking/semantic';
 import { tokenChunkText } from './chunking/token';
 import { llmChunkText } from './chunking/llm';
 import { db, qdrant, COLLECTION, DEFAULT_MEMORY_DB } from '../db';
+import { planChunkCapacity, writeWithinCapacity } from '../db/chunks';
+import { insertWithinEdgeLimit } from '../db/connections';
+import { withStoreRetry } from '../db/retry';
 import { ProgressBar } from '../progress';
-import { isCitationChunk, isGlossaryChunk } from './filters';
+import { isCitationChunk, isGlossaryChunk, limitChunkContent, noveltyScore, isConfidence } from './filters';
+import { claimContent, releaseContent } from './inflight';
 
 // Re-export for module boundary consumers
 export { semanticChunkText } from './chunking/semantic';
+export { limitChunkContent, noveltyScore, isConfidence, ContentTooLargeError } from './filters';
 export type { Chunk } from './chunking/semantic';
 
 /*
@@ -42,6 +47,7 @@ export type IngestResult = {
   chunks_skipped: number;
   connections_seeded: number;
   source: string;
+  novelty: number | null;   // mean novelty of the stored chunks; null when none was measured
 };
 
 export type ProgressEvent =
@@ -64,6 +70,9 @@ type IngestTextOptions = {
   concurrency?: number;
   onProgress?: (event: ProgressEvent) => void;
   metadata?: Record<string, unknown>;
+  ttlMs?: number;   // chunks expire this long after ingest; unset/0 = keep forever
+  confidence?: number;   // trust in the source, stored on every chunk; default 1
+  duplicateThreshold?: number;   // skip chunks at least this similar to a stored one; default 0.97
 };
 
 type PerfStageTotals = {
@@ -101,7 +110,8 @@ function rateForStage(chunks: number, milliseconds: number): string {
   return ((chunks * 1000) / milliseconds).toFixed(1);
 }
 
-async function searchSimilar(vector: number[], database: string): Promise<{ topScore: number; similarIds: string[]; scoreMap: Map<string, number> }> {
+// `topScore` is null when the search failed: nothing is known about similarity
+async function searchSimilar(vector: number[], database: string): Promise<{ topScore: number | null; similarIds: string[]; scoreMap: Map<string, number> }> {
   try {
     const results = await qdrant.search(COLLECTION, {
       vector,
@@ -127,7 +137,7 @@ async function searchSimilar(vector: number[], database: string): Promise<{ topS
       scoreMap,
     };
   } catch {
-    return { topScore: 0, similarIds: [], scoreMap: new Map() };
+    return { topScore: null, similarIds: [], scoreMap: new Map() };
   }
 }
 
@@ -147,8 +157,9 @@ function seedConnectionsBatch(
       for (const targetId of targetIds) {
         if (targetId === sourceId) continue;
         const sim = scoreMap?.get(targetId) ?? 0;
-        const result = insertStmt.run(uuidv4(), sourceId, targetId, 'related_to', 0.3, 0.5, timestamp, null, sim, 1, timestamp, database);
-        total += result.changes;
+        total += insertWithinEdgeLimit(sourceId, targetId, () => insertStmt.run(
+          uuidv4(), sourceId, targetId, 'related_to', 0.3, 0.5, timestamp, null, sim, 1, timestamp, database,
+        ).changes, database);
       }
     }
     return total;
@@ -219,6 +230,10 @@ export async function ingestText(
   options: IngestTextOptions = {},
   database: string = DEFAULT_MEMORY_DB,
 ): Promise<IngestResult> {
+  if (options.confidence !== undefined && !isConfidence(options.confidence)) {
+    throw new Error('confidence must be between 0 and 1');
+  }
+
   const wallStartedMs = Date.now();
   const debugPerf = process.env.DEBUG_PERF === 'true';
   const perfTotals: PerfStageTotals = {
@@ -322,7 +337,7 @@ export async function ingestText(
 
   const filtered = chunks.filter((chunk) => !isGlossaryChunk(chunk.text) && !isCitationChunk(chunk.text));
   const removedCount = chunks.length - filtered.length;
-  chunks = filtered;
+  chunks = filtered.map((chunk) => ({ ...chunk, text: limitChunkContent(chunk.text) }));
 
   process.stdout.write(` done. ${chunks.length} chunks found.\n`);
   if (removedCount > 0) {
@@ -334,7 +349,7 @@ export async function ingestText(
     console.log(`⚠️  Large document — estimated ~${formatDurationApprox(estimatedSeconds)} at concurrency ${concurrency}`);
   }
 
-  const duplicateThreshold = 0.97;
+  const duplicateThreshold = options.duplicateThreshold ?? 0.97;
   const skipDuplicateCheck = process.env.SKIP_DUPLICATE_CHECK === 'true';
   const deferGraphBuild = process.env.DEFER_GRAPH_BUILD === 'true';
   const embedModel = process.env.EMBED_MODEL ?? '';
@@ -344,6 +359,8 @@ export async function ingestText(
   let stored = 0;
   let skipped = 0;
   let seededConnections = 0;
+  let noveltySum = 0;
+  let noveltyMeasured = 0;
   const chunkCompletionTimesMs: number[] = [];
 
   onProgress?.({
@@ -429,17 +446,22 @@ export async function ingestText(
       chunks_skipped: 0,
       connections_seeded: 0,
       source,
+      novelty: null,
     };
   }
 
   const progress = new ProgressBar({ total: chunks.length, fallbackEvery: 50, minColumns: 60 });
   const insertChunkStmt = db.prepare(`
-    INSERT INTO chunks (chunk_id, text, source, page, timestamp, tags, metadata, database_id)
-    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+    INSERT INTO chunks (chunk_id, text, source, page, timestamp, tags, metadata, database_id, expires_at, confidence, novelty)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
   `);
-  const batchInsertChunks = db.transaction((items: { chunkId: string; text: string; source: string; page: number; timestamp: string; tagsJson: string; metadataJson: string }[]) => {
+  const expiresAt = typeof options.ttlMs === 'number' && options.ttlMs > 0
+    ? new Date(Date.now() + options.ttlMs).toISOString()
+    : null;
+  const confidence = options.confidence ?? 1;
+  const batchInsertChunks = db.transaction((items: { chunkId: string; text: string; source: string; page: number; timestamp: string; tagsJson: string; metadataJson: string; novelty: number | null }[]) => {
     for (const item of items) {
-      insertChunkStmt.run(item.chunkId, item.text, item.source, item.page, item.timestamp, item.tagsJson, item.metadataJson, databaseName);
+      insertChunkStmt.run(item.chunkId, item.text, item.source, item.page, item.timestamp, item.tagsJson, item.metadataJson, databaseName, expiresAt, confidence, item.novelty);
     }
   });
 
@@ -449,12 +471,27 @@ export async function ingestText(
     timestamp: string;
   };
 
+  const claimed: string[] = [];
+
   try {
     const deferredSeeds: DeferredGraphSeed[] = [];
     let completedBatchCount = 0;
 
     for (let i = 0; i < chunks.length; i += BATCH_SIZE) {
-      const batch = chunks.slice(i, i + BATCH_SIZE);
+      // Identical content already being stored (by a concurrent ingest or earlier in this one) is a duplicate
+      const batch = chunks.slice(i, i + BATCH_SIZE).filter((chunk) => {
+        if (skipDuplicateCheck) return true;
+        const key = claimContent(chunk.text, databaseName);
+        if (key) {
+          claimed.push(key);
+          return true;
+        }
+        skipped++;
+        progress.tick({ duplicates: 1 });
+        emitChunkProgress();
+        return false;
+      });
+      if (batch.length === 0) continue;
       completedBatchCount++;
       const batchIndex = completedBatchCount;
 
@@ -469,18 +506,18 @@ export async function ingestText(
       const skipSearch = skipDuplicateCheck && deferGraphBuild;
       const searchStart = Date.now();
       const searchResults = skipSearch
-        ? batch.map(() => ({ topScore: 0, similarIds: [] as string[], scoreMap: new Map<string, number>() }))
+        ? batch.map(() => ({ topScore: null as number | null, similarIds: [] as string[], scoreMap: new Map<string, number>() }))
         : await Promise.all(vectors.map(v => searchSimilar(v, databaseName)));
       const searchMs = skipSearch ? 0 : (Date.now() - searchStart);
       perfTotals.qdrantSearchMs += searchMs;
       perfCounts.qdrantSearchChunks += skipSearch ? 0 : batch.length;
 
       // Filter duplicates
-      type StoreItem = { chunk: Chunk; chunkId: string; vector: number[]; timestamp: string; similarIds: string[]; scoreMap: Map<string, number> };
+      type StoreItem = { chunk: Chunk; chunkId: string; vector: number[]; timestamp: string; similarIds: string[]; scoreMap: Map<string, number>; novelty: number | null };
       const toStore: StoreItem[] = [];
       for (let j = 0; j < batch.length; j++) {
         const { topScore, similarIds, scoreMap } = searchResults[j];
-        if (!skipDuplicateCheck && topScore >= duplicateThreshold) {
+        if (!skipDuplicateCheck && topScore !== null && topScore >= duplicateThreshold) {
           skipped++;
           progress.tick({ duplicates: 1 });
           emitChunkProgress();
@@ -493,6 +530,7 @@ export async function ingestText(
           timestamp: new Date().toISOString(),
           similarIds: deferGraphBuild ? [] : similarIds,
           scoreMap: deferGraphBuild ? new Map() : scoreMap,
+          novelty: topScore === null ? null : noveltyScore(topScore),
         });
       }
 
@@ -501,11 +539,14 @@ export async function ingestText(
         continue;
       }
 
+      // Rejects early; the count and any eviction commit with the inserts below
+      planChunkCapacity(toStore.length, databaseName);
+
       // Batch Qdrant upsert
       let upsertMs = 0;
       try {
         const upsertStart = Date.now();
-        await qdrant.upsert(COLLECTION, {
+        await withStoreRetry(() => qdrant.upsert(COLLECTION, {
           points: toStore.map(c => ({
             id: c.chunkId,
             vector: c.vector,
@@ -517,7 +558,7 @@ export async function ingestText(
               ...(c.chunk.metadata ?? {}),
             },
           })),
-        });
+        }));
         upsertMs = Date.now() - upsertStart;
       } catch (error: unknown) {
         const message = error instanceof Error ? error.message : String(error);
@@ -533,29 +574,51 @@ export async function ingestText(
       perfTotals.qdrantUpsertMs += upsertMs;
       perfCounts.qdrantUpsertChunks += toStore.length;
 
-      // Fix 3: Batch SQLite chunk inserts with transaction
-      const sqliteStart = Date.now();
-      batchInsertChunks(toStore.map(c => ({
-        chunkId: c.chunkId,
-        text: c.chunk.text,
-        source,
-        page: c.chunk.index,
-        timestamp: c.timestamp,
-        tagsJson: JSON.stringify(tags),
-          metadataJson: JSON.stringify(c.chunk.metadata ?? {}),
-      })));
-      const sqliteMs = Date.now() - sqliteStart;
+      // Fix 3 + 4: capacity, chunk inserts and seeded edges in one transaction
+      let sqliteMs = 0;
+      let seedingMs = 0;
+      let written: { result: number; evicted: number };
+      try {
+        written = await writeWithinCapacity(toStore.length, databaseName, (evicted) => {
+          const sqliteStart = Date.now();
+          batchInsertChunks(toStore.map(c => ({
+            chunkId: c.chunkId,
+            text: c.chunk.text,
+            source,
+            page: c.chunk.index,
+            timestamp: c.timestamp,
+            tagsJson: JSON.stringify(tags),
+            metadataJson: JSON.stringify(c.chunk.metadata ?? {}),
+            novelty: c.novelty,
+          })));
+          sqliteMs = Date.now() - sqliteStart;
+          if (deferGraphBuild) return 0;
+
+          // The similarity search ran before the eviction, so it may name chunks just evicted
+          const seedStart = Date.now();
+          const entries = toStore.map(c => ({ sourceId: c.chunkId, targetIds: c.similarIds.filter(id => !evicted.has(id)), scoreMap: c.scoreMap }));
+          const conns = seedConnectionsBatch(entries, ingestTimestamp, databaseName);
+          seedingMs = Date.now() - seedStart;
+          return conns;
+        });
+      } catch (error) {
+        // Nothing reached SQLite, so the points just upserted are orphans
+        try {
+          await withStoreRetry(() => qdrant.delete(COLLECTION, { wait: true, points: toStore.map(c => c.chunkId) }));
+        } catch (cleanupError) {
+          const message = cleanupError instanceof Error ? cleanupError.message : String(cleanupError);
+          console.warn(`\n⚠️  Qdrant cleanup failed for ${toStore.length} chunks: ${message}`);
+        }
+        throw error;
+      }
+      if (written.evicted > 0) {
+        console.log(`\n♻️  Evicted ${written.evicted} least-used chunks to stay under MAX_CHUNKS`);
+      }
       perfTotals.sqliteMs += sqliteMs;
       perfCounts.sqliteChunks += toStore.length;
 
-      // Fix 4: Batch seedConnections with INSERT OR IGNORE
-      let seedingMs = 0;
       if (!deferGraphBuild) {
-        const seedStart = Date.now();
-        const entries = toStore.map(c => ({ sourceId: c.chunkId, targetIds: c.similarIds, scoreMap: c.scoreMap }));
-        const conns = seedConnectionsBatch(entries, ingestTimestamp, databaseName);
-        seededConnections += conns;
-        seedingMs = Date.now() - seedStart;
+        seededConnections += written.result;
         perfTotals.connectionSeedingMs += seedingMs;
         perfCounts.connectionSeedingChunks += toStore.length;
       } else {
@@ -563,8 +626,12 @@ export async function ingestText(
       }
 
       // Update progress per stored chunk
-      for (const _item of toStore) {
+      for (const item of toStore) {
         stored++;
+        if (item.novelty !== null) {
+          noveltySum += item.novelty;
+          noveltyMeasured++;
+        }
         progress.tick({ stored: 1, connections: 0 });
         emitChunkProgress();
       }
@@ -619,6 +686,8 @@ export async function ingestText(
     }
 
     throw error;
+  } finally {
+    releaseContent(claimed);
   }
 
   const summary = progress.finish({
@@ -713,5 +782,6 @@ export async function ingestText(
     chunks_skipped: skipped,
     connections_seeded: seededConnections,
     source,
+    novelty: noveltyMeasured > 0 ? noveltySum / noveltyMeasured : null,
   };
 }
@@ -0,0 +1,34 @@
+// src/ingest/inflight.ts — Claims on chunk content while an ingest stores it
+//
+// Deduplication searches Qdrant for a near-identical vector, then stores the
+// chunk after several awaits. Two ingests of the same content can both search
+// before either has stored, and both miss. A claim closes that window: it is
+// taken synchronously before the search, so the second ingest sees the claim
+// (and counts the chunk as a duplicate) or searches only after the first one
+// released it, by which point the stored point is visible.
+import { createHash } from 'crypto';
+
+const claims = new Set<string>();
+
+export function contentKey(text: string, database: string): string {
+  const normalized = text.trim().replace(/\s+/g, ' ');
+  return `${database}|${createHash('sha256').update(normalized).digest('hex')}`;
+}
+
+/**
+ * Claim `text` for storage in `database`. Returns the claim key, or null when
+ * an in-flight ingest (possibly the caller itself) already holds identical
+ * content. Every key returned must be passed to `releaseContent`.
+ */
+export function claimContent(text: string, database: string): string | null {
+  const key = contentKey(text, database);
+  if (claims.has(key)) return null;
+  claims.add(key);
+  return key;
+}
+
+export function releaseContent(keys: Iterable<string>): void {
+  for (const key of keys) {
+    claims.delete(key);
+  }
+}
@@ -0,0 +1,79 @@
+// src/retrieve/accessLog.ts — Chunk access bookkeeping with a configurable write policy
+import { db } from '../db';
+import { ACCESS_WRITE_POLICY, ACCESS_FLUSH_INTERVAL_MS } from '../config';
+
+/**
+ * When recall writes chunk `access_count` / `last_accessed`:
+ * - `every`: on each recall (durable, one UPDATE per returned chunk)
+ * - `debounced`: counted in memory and flushed every ACCESS_FLUSH_INTERVAL_MS
+ *   (a crash loses at most one interval of counts)
+ * - `never`: not tracked at all, for read-dominated deployments
+ */
+export type AccessWritePolicy = 'every' | 'debounced' | 'never';
+
+type PendingAccess = { chunkId: string; database: string; count: number; lastAccessed: string };
+
+const pending = new Map<string, PendingAccess>();
+let flushTimer: NodeJS.Timeout | null = null;
+
+export function recordChunkAccess(
+  chunkIds: string[],
+  database: string,
+  policy: AccessWritePolicy = ACCESS_WRITE_POLICY,
+  now: string = new Date().toISOString(),
+): void {
+  if (policy === 'never' || chunkIds.length === 0) return;
+
+  if (policy === 'every') {
+    writeAccess(chunkIds.map(chunkId => ({ chunkId, database, count: 1, lastAccessed: now })));
+    return;
+  }
+
+  for (const chunkId of chunkIds) {
+    const key = `${database}|${chunkId}`;
+    const entry = pending.get(key);
+    if (entry) {
+      entry.count++;
+      entry.lastAccessed = now;
+    } else {
+      pending.set(key, { chunkId, database, count: 1, lastAccessed: now });
+    }
+  }
+
+  if (!flushTimer) {
+    flushTimer = setTimeout(() => {
+      flushTimer = null;
+      flushChunkAccess();
+    }, ACCESS_FLUSH_INTERVAL_MS);
+    flushTimer.unref();
+  }
+}
+
+/** Write out debounced access counts now. Returns the number of chunks touched. */
+export function flushChunkAccess(): number {
+  if (flushTimer) {
+    clearTimeout(flushTimer);
+    flushTimer = null;
+  }
+  if (pending.size === 0) return 0;
+
+  const entries = [...pending.values()];
+  pending.clear();
+  writeAccess(entries);
+  return entries.length;
+}
+
+function writeAccess(entries: PendingAccess[]): void {
+  const update = db.prepare(`
+    UPDATE chunks
+    SET access_count = COALESCE(access_count, 0) + ?,
+        last_accessed = ?
+    WHERE chunk_id = ?
+      AND database_id = ?
+  `);
+  db.transaction(() => {
+    for (const entry of entries) {
+      update.run(entry.count, entry.lastAccessed, entry.chunkId, entry.database);
+    }
+  })();
+}
@@ -0,0 +1,153 @@
+// src/retrieve/cache.ts — LRU cache for repeated identical retrievals
+import { createHash } from 'crypto';
+import { getHeapStatistics } from 'v8';
+import {
+  RECALL_CACHE_SIZE,
+  RECALL_CACHE_AUTOTUNE,
+  RECALL_CACHE_MIN_SIZE,
+  RECALL_CACHE_MAX_SIZE,
+} from '../config';
+import type { Result } from './index';
+
+type CacheStats = {
+  hits: number;
+  misses: number;
+  size: number;
+  capacity: number;
+};
+
+export interface CacheTuneSample {
+  hitRate: number;        // hits / lookups over the last window
+  full: boolean;          // the cache was at capacity when the window closed
+  heapUsedRatio: number;  // used heap / heap size limit
+}
+
+export interface CacheTuneBounds {
+  min: number;
+  max: number;
+}
+
+// Lookups between tuning decisions, and the thresholds that drive them.
+const TUNE_WINDOW = 200;
+const LOW_HIT_RATE = 0.2;
+const HEAP_PRESSURE_RATIO = 0.85;
+
+type CacheEntry = {
+  results: Result[];
+  expiresAt: number | null;   // ms epoch of the earliest expires_at among the results
+};
+
+const entries = new Map<string, CacheEntry>();
+let hits = 0;
+let misses = 0;
+let capacity = RECALL_CACHE_SIZE;
+let windowHits = 0;
+let windowLookups = 0;
+
+/**
+ * Pick the next cache capacity. Heap pressure wins: the cache halves (never
+ * below `min`). Otherwise a full cache that still misses most lookups is
+ * too small for the working set and doubles (never above `max`).
+ */
+export function nextCacheCapacity(current: number, sample: CacheTuneSample, bounds: CacheTuneBounds): number {
+  const min = Math.max(1, Math.min(bounds.min, bounds.max));
+  const max = Math.max(min, bounds.max);
+
+  if (sample.heapUsedRatio >= HEAP_PRESSURE_RATIO) {
+    return Math.max(min, Math.floor(current / 2));
+  }
+  if (sample.full && sample.hitRate < LOW_HIT_RATE) {
+    return Math.min(max, Math.max(min, current * 2));
+  }
+  return Math.min(max, Math.max(min, current));
+}
+
+function heapUsedRatio(): number {
+  const heap = getHeapStatistics();
+  return heap.heap_size_limit > 0 ? heap.used_heap_size / heap.heap_size_limit : 0;
+}
+
+function trimToCapacity(): void {
+  while (entries.size > capacity) {
+    const oldest = entries.keys().next().value as string | undefined;
+    if (!oldest) break;
+    entries.delete(oldest);
+  }
+}
+
+function recordLookup(hit: boolean): void {
+  if (!RECALL_CACHE_AUTOTUNE || RECALL_CACHE_SIZE <= 0) return;
+
+  windowLookups++;
+  if (hit) windowHits++;
+  if (windowLookups < TUNE_WINDOW) return;
+
+  capacity = nextCacheCapacity(capacity, {
+    hitRate: windowHits / windowLookups,
+    full: entries.size >= capacity,
+    heapUsedRatio: heapUsedRatio(),
+  }, { min: RECALL_CACHE_MIN_SIZE, max: RECALL_CACHE_MAX_SIZE });
+  windowHits = 0;
+  windowLookups = 0;
+  trimToCapacity();
+}
+
+/**
+ * Cache key = database + graph version + query hash + the options that shape
+ * the result set. Any content/graph mutation bumps the version, so stale
+ * entries are simply never looked up again and age out of the LRU. Expiry
+ * changes no rows, so entries also carry their own deadline (see setCachedRecall).
+ */
+export function recallCacheKey(database: string, graphVersion: number, query: string, options: unknown): string {
+  const queryHash = createHash('sha256').update(query).digest('hex');
+  return `${database}|${graphVersion}|${queryHash}|${JSON.stringify(options)}`;
+}
+
+export function getCachedRecall(key: string): Result[] | undefined {
+  let cached = entries.get(key);
+  if (cached && cached.expiresAt !== null && cached.expiresAt <= Date.now()) {
+    entries.delete(key);
+    cached = undefined;
+  }
+  if (!cached) {
+    misses++;
+    recordLookup(false);
+    return undefined;
+  }
+
+  // Re-insert to mark as most recently used
+  entries.delete(key);
+  entries.set(key, cached);
+  hits++;
+  recordLookup(true);
+  return cached.results.map(result => ({ ...result, path: [...result.path], conflicts: [...result.conflicts] }));
+}
+
+/**
+ * Store a recall result. `expiresAt` is the earliest `expires_at` among the
+ * returned chunks: past it the entry would still list a chunk that recall now
+ * hides, so it is dropped on the next lookup instead of being served.
+ */
+export function setCachedRecall(key: string, results: Result[], expiresAt: string | null = null): void {
+  if (RECALL_CACHE_SIZE <= 0) return;
+
+  entries.delete(key);
+  entries.set(key, {
+    results: results.map(result => ({ ...result, path: [...result.path], conflicts: [...result.conflicts] })),
+    expiresAt: expiresAt ? Date.parse(expiresAt) : null,
+  });
+  trimToCapacity();
+}
+
+export function getRecallCacheStats(): CacheStats {
+  return { hits, misses, size: entries.size, capacity: RECALL_CACHE_SIZE > 0 ? capacity : 0 };
+}
+
+export function clearRecallCache(): void {
+  entries.clear();
+  hits = 0;
+  misses = 0;
+  windowHits = 0;
+  windowLookups = 0;
+  capacity = RECALL_CACHE_SIZE;
+}
@@ -0,0 +1,121 @@
+// src/retrieve/combine.ts — How relevance signals merge: content vs association, and along graph paths
+
+export type ScoreCombine = 'weighted_sum' | 'max' | 'product' | 'harmonic_mean';
+
+export const SCORE_COMBINE_MODES: readonly ScoreCombine[] = ['weighted_sum', 'max', 'product', 'harmonic_mean'];
+
+export function isScoreCombine(value: unknown): value is ScoreCombine {
+  return typeof value === 'string' && (SCORE_COMBINE_MODES as readonly string[]).includes(value);
+}
+
+/**
+ * Merge the (already weighted) content and association components.
+ * `weighted_sum` is the historical additive blend; `max` lets either signal
+ * carry a chunk on its own; `product` and `harmonic_mean` demand both, the
+ * latter less harshly when one side is merely small.
+ */
+export function combineRelevance(content: number, association: number, mode: ScoreCombine = 'weighted_sum'): number {
+  switch (mode) {
+    case 'max':
+      return Math.max(content, association);
+    case 'product':
+      return content * association;
+    case 'harmonic_mean':
+      return content + association > 0 ? (2 * content * association) / (content + association) : 0;
+    case 'weighted_sum':
+    default:
+      return content + association;
+  }
+}
+
+// ── Path accumulation ──────────────────────────────────────────────────────
+
+export type PathAccumulation = 'multiplicative' | 'min_edge' | 'average';
+
+export const PATH_ACCUMULATION_MODES: readonly PathAccumulation[] = ['multiplicative', 'min_edge', 'average'];
+
+export function isPathAccumulation(value: unknown): value is PathAccumulation {
+  return typeof value === 'string' && (PATH_ACCUMULATION_MODES as readonly string[]).includes(value);
+}
+
+export type PathStep = {
+  previousScore: number;   // score of the node the edge leaves from
+  seedScore: number;       // vector score of the seed the path started at
+  edgeWeight: number;      // weight of the edge being taken
+  hopFactor: number;       // hop decay applied at this depth
+  decayProduct: number;    // product of every hop factor so far, this one included
+  minWeight: number;       // weakest edge on the path, this one included
+  weightSum: number;       // sum of edge weights on the path, this one included
+  hops: number;            // path length after this step
+};
+
+/**
+ * Score of a node reached by one more hop. `multiplicative` chains every edge
+ * weight (long paths fade fast); `min_edge` scores by the bottleneck link, so a
+ * path whose weakest edge is still strong survives; `average` uses the mean
+ * edge weight. Hop decay applies identically in all three.
+ */
+export function accumulatePathScore(step: PathStep, mode: PathAccumulation = 'multiplicative'): number {
+  switch (mode) {
+    case 'min_edge':
+      return step.seedScore * step.decayProduct * step.minWeight;
+    case 'average':
+      return step.seedScore * step.decayProduct * (step.weightSum / Math.max(1, step.hops));
+    case 'multiplicative':
+    default:
+      return step.previousScore * step.hopFactor * step.edgeWeight;
+  }
+}
+
+// ── Result count ───────────────────────────────────────────────────────────
+
+/**
+ * Smallest prefix of `results` (sorted best first) whose summed score reaches
+ * `mass` (a fraction in (0, 1]) of the total score. A peaked ranking yields a
+ * couple of results, a flat one many. Non-positive scores count as zero.
+ */
+export function cutByRelevanceMass<T extends { score: number }>(results: T[], mass: number): T[] {
+  const total = results.reduce((sum, result) => sum + Math.max(0, result.score), 0);
+  if (total <= 0 || mass >= 1) return results.slice();
+
+  const target = total * Math.max(0, mass);
+  let covered = 0;
+  for (let i = 0; i < results.length; i++) {
+    covered += Math.max(0, results[i].score);
+    if (covered >= target) return results.slice(0, i + 1);
+  }
+  return results.slice();
+}
+
+// ── Confidence mapping ─────────────────────────────────────────────────────
+
+export type ConfidenceMapping = 'sigmoid' | 'tanh' | 'linear_clamp';
+
+export const CONFIDENCE_MAPPINGS: readonly ConfidenceMapping[] = ['sigmoid', 'tanh', 'linear_clamp'];
+
+export function isConfidenceMapping(value: unknown): value is ConfidenceMapping {
+  return typeof value === 'string' && (CONFIDENCE_MAPPINGS as readonly string[]).includes(value);
+}
+
+const SIGMOID_MIDPOINT = 0.5;
+const SIGMOID_STEEPNESS = 8;
+
+/**
+ * Squash a raw relevance score (an unbounded product of similarities, edge
+ * weights and boosts) into a display confidence in [0, 1]. `sigmoid` is a
+ * logistic centred on 0.5; `tanh` keeps weak scores near-linear and saturates
+ * strong ones; `linear_clamp` passes [0, 1] through unchanged. All three are
+ * non-decreasing, and non-finite scores map to 0.
+ */
+export function mapConfidence(score: number, mapping: ConfidenceMapping): number {
+  if (!Number.isFinite(score)) return 0;
+  switch (mapping) {
+    case 'sigmoid':
+      return 1 / (1 + Math.exp(-SIGMOID_STEEPNESS * (score - SIGMOID_MIDPOINT)));
+    case 'tanh':
+      return Math.max(0, Math.tanh(score));
+    case 'linear_clamp':
+    default:
+      return Math.min(1, Math.max(0, score));
+  }
+}
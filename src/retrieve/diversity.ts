@@ -0,0 +1,69 @@
+// src/retrieve/diversity.ts — Maximal Marginal Relevance re-ranking
+import { cutByRelevanceMass } from './combine';
+
+export function contentTokens(text: string): Set<string> {
+  return new Set(text.toLowerCase().split(/[^\p{L}\p{N}]+/u).filter(token => token.length > 1));
+}
+
+/** Jaccard overlap of the word sets of two texts, in [0, 1]. */
+export function contentSimilarity(a: string, b: string): number {
+  const left = contentTokens(a);
+  const right = contentTokens(b);
+  if (left.size === 0 || right.size === 0) return 0;
+
+  let shared = 0;
+  for (const token of left) {
+    if (right.has(token)) shared++;
+  }
+  return shared / (left.size + right.size - shared);
+}
+
+/**
+ * Reorder `results` (best first) by Maximal Marginal Relevance: each pick
+ * maximises `(1 - diversity) * score - diversity * maxSimilarity`, where
+ * maxSimilarity is the content overlap with anything already picked. 0 keeps
+ * the input order; 1 ranks purely by novelty after the top result. Scores are
+ * left untouched, only the order changes.
+ */
+export function diversifyResults<T extends { text: string; score: number }>(results: T[], diversity: number, limit: number = results.length): T[] {
+  const weight = Math.min(1, Math.max(0, diversity));
+  const count = Math.min(results.length, Math.max(0, Math.floor(limit)));
+  if (weight === 0 || results.length <= 1) return results.slice(0, count);
+
+  const remaining = results.map((result, index) => ({ result, index, maxSimilarity: 0 }));
+  const picked: T[] = [];
+
+  while (picked.length < count && remaining.length > 0) {
+    let best = 0;
+    let bestValue = Number.NEGATIVE_INFINITY;
+    for (let i = 0; i < remaining.length; i++) {
+      const value = (1 - weight) * remaining[i].result.score - weight * remaining[i].maxSimilarity;
+      if (value > bestValue) {
+        best = i;
+        bestValue = value;
+      }
+    }
+
+    const [chosen] = remaining.splice(best, 1);
+    picked.push(chosen.result);
+    for (const candidate of remaining) {
+      candidate.maxSimilarity = Math.max(candidate.maxSimilarity, contentSimilarity(candidate.result.text, chosen.result.text));
+    }
+  }
+
+  return picked;
+}
+
+/**
+ * The final result list from a score-sorted pool. The relevance mass (when
+ * set) is measured over the whole pool first, so neither MMR reordering nor
+ * the `topK` cap changes which results count as relevant; MMR then orders the
+ * relevant ones and `topK` caps the list.
+ */
+export function selectResults<T extends { text: string; score: number }>(
+  pool: T[],
+  options: { topK: number; diversity: number; relevanceMass?: number },
+): T[] {
+  const relevant = options.relevanceMass === undefined ? pool : cutByRelevanceMass(pool, options.relevanceMass);
+  return diversifyResults(relevant, options.diversity, options.topK);
+}
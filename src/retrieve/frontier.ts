@@ -0,0 +1,107 @@
+// src/retrieve/frontier.ts — Bounded breadth-first queue for graph expansion
+
+type FrontierEntry<T> = { item: T; score: number; seq: number; live: boolean };
+
+/**
+ * FIFO queue that holds at most `capacity` items (0 = unbounded). Past the cap
+ * the lowest-scoring queued item is dropped, the oldest one on ties. A min-heap
+ * on (score, seq) finds it in O(log n); entries taken or dropped are only
+ * marked dead and skipped when they surface, so nothing is spliced out.
+ */
+export class Frontier<T> {
+  private readonly fifo: Array<FrontierEntry<T>> = [];
+  private readonly heap: Array<FrontierEntry<T>> = [];
+  private head = 0;
+  private nextSeq = 0;
+  private liveCount = 0;
+
+  constructor(private readonly capacity: number = 0) {}
+
+  get size(): number {
+    return this.liveCount;
+  }
+
+  /** Queue `item`; returns how many queued items were dropped to stay under the cap. */
+  push(item: T, score: number): number {
+    const entry: FrontierEntry<T> = { item, score, seq: this.nextSeq++, live: true };
+    this.fifo.push(entry);
+    this.liveCount++;
+    if (this.capacity <= 0) return 0;
+
+    this.heapPush(entry);
+    let dropped = 0;
+    while (this.liveCount > this.capacity) {
+      const weakest = this.heapPop();
+      if (!weakest) break;
+      if (!weakest.live) continue;
+      weakest.live = false;
+      this.liveCount--;
+      dropped++;
+    }
+    return dropped;
+  }
+
+  /** The oldest queued item, or undefined when the queue is empty. */
+  shift(): T | undefined {
+    while (this.head < this.fifo.length) {
+      const entry = this.fifo[this.head++];
+      if (!entry.live) continue;
+      entry.live = false;
+      this.liveCount--;
+      this.compact();
+      return entry.item;
+    }
+    return undefined;
+  }
+
+  private compact(): void {
+    // Drop the consumed prefix once it dominates, so long expansions stay O(n) in memory
+    if (this.head > 1024 && this.head * 2 > this.fifo.length) {
+      this.fifo.splice(0, this.head);
+      this.head = 0;
+    }
+    if (this.capacity > 0 && this.heap.length > 4 * Math.max(this.capacity, this.liveCount) + 1024) {
+      const live = this.heap.filter(entry => entry.live);
+      this.heap.length = 0;
+      for (const entry of live) this.heapPush(entry);
+    }
+  }
+
+  private static before<T>(a: FrontierEntry<T>, b: FrontierEntry<T>): boolean {
+    return a.score < b.score || (a.score === b.score && a.seq < b.seq);
+  }
+
+  private heapPush(entry: FrontierEntry<T>): void {
+    const heap = this.heap;
+    heap.push(entry);
+    let index = heap.length - 1;
+    while (index > 0) {
+      const parent = (index - 1) >> 1;
+      if (!Frontier.before(heap[index], heap[parent])) break;
+      [heap[index], heap[parent]] = [heap[parent], heap[index]];
+      index = parent;
+    }
+  }
+
+  private heapPop(): FrontierEntry<T> | undefined {
+    const heap = this.heap;
+    if (heap.length === 0) return undefined;
+    const top = heap[0];
+    const last = heap.pop()!;
+    if (heap.length > 0) {
+      heap[0] = last;
+      let index = 0;
+      while (true) {
+        const left = 2 * index + 1;
+        const right = left + 1;
+        let smallest = index;
+        if (left < heap.length && Frontier.before(heap[left], heap[smallest])) smallest = left;
+        if (right < heap.length && Frontier.before(heap[right], heap[smallest])) smallest = right;
+        if (smallest === index) break;
+        [heap[index], heap[smallest]] = [heap[smallest], heap[index]];
+        index = smallest;
+      }
+    }
+    return top;
+  }
+}
@@ -0,0 +1,103 @@
+// src/retrieve/grouped.ts — Recall grouped by concept, for theme-wise presentation
+import { db, DEFAULT_MEMORY_DB } from '../db';
+import { retrieve } from './index';
+import type { Result, RetrieveOptions } from './index';
+
+export type RecallGroup = {
+  concept_id: string | null;   // null collects results that belong to no concept
+  label: string;
+  results: Result[];
+};
+
+const LABEL_STOPWORDS = new Set([
+  'about', 'after', 'also', 'because', 'been', 'being', 'between', 'could', 'from', 'have',
+  'into', 'more', 'most', 'other', 'over', 'some', 'such', 'than', 'that', 'their', 'them',
+  'then', 'there', 'these', 'they', 'this', 'those', 'through', 'under', 'very', 'were',
+  'what', 'when', 'where', 'which', 'while', 'will', 'with', 'would', 'your',
+]);
+
+/**
+ * Label a group by the tokens shared by most of its texts (ties broken by
+ * total frequency, then alphabetically). Falls back to "misc" when nothing
+ * meaningful is shared.
+ */
+export function sharedTokenLabel(texts: string[], maxTokens: number = 3): string {
+  const documentFrequency = new Map<string, number>();
+  const totalFrequency = new Map<string, number>();
+
+  for (const text of texts) {
+    const tokens = text.toLowerCase().match(/[a-z0-9]{4,}/g) ?? [];
+    for (const token of tokens) {
+      if (LABEL_STOPWORDS.has(token)) continue;
+      totalFrequency.set(token, (totalFrequency.get(token) ?? 0) + 1);
+    }
+    for (const token of new Set(tokens)) {
+      if (LABEL_STOPWORDS.has(token)) continue;
+      documentFrequency.set(token, (documentFrequency.get(token) ?? 0) + 1);
+    }
+  }
+
+  const minShare = texts.length > 1 ? 2 : 1;
+  const ranked = [...documentFrequency.entries()]
+    .filter(([, count]) => count >= minShare)
+    .sort((a, b) => b[1] - a[1]
+      || (totalFrequency.get(b[0]) ?? 0) - (totalFrequency.get(a[0]) ?? 0)
+      || a[0].localeCompare(b[0]))
+    .slice(0, maxTokens)
+    .map(([token]) => token);
+
+  return ranked.length > 0 ? ranked.join(' ') : 'misc';
+}
+
+/**
+ * Run a normal recall, then bucket the results by concept. A chunk in several
+ * concepts goes to the most confident one. Groups keep the recall order of
+ * their results and are ordered by their best-scoring result; concepts
+ * without a stored label are named from their members' shared tokens.
+ */
+export async function retrieveGrouped(query: string, options: RetrieveOptions = {}): Promise<RecallGroup[]> {
+  const database = options.database?.trim() || DEFAULT_MEMORY_DB;
+  const results = await retrieve(query, { ...options, database });
+  if (results.length === 0) return [];
+
+  const concepts = db.prepare(`
+    SELECT concept_id, label, member_chunks, COALESCE(confidence, 0.5) AS confidence
+    FROM concepts
+    WHERE database_id = ?
+    ORDER BY confidence DESC, concept_id ASC
+  `).all(database) as Array<{ concept_id: string; label: string; member_chunks: string; confidence: number }>;
+
+  const wanted = new Set(results.map(result => result.chunk_id));
+  const conceptOf = new Map<string, { concept_id: string; label: string }>();
+  for (const concept of concepts) {
+    let members: string[] = [];
+    try {
+      members = JSON.parse(concept.member_chunks) as string[];
+    } catch {
+      continue;
+    }
+    for (const member of members) {
+      if (wanted.has(member) && !conceptOf.has(member)) {
+        conceptOf.set(member, { concept_id: concept.concept_id, label: concept.label });
+      }
+    }
+  }
+
+  const groups = new Map<string, RecallGroup>();
+  for (const result of results) {
+    const concept = conceptOf.get(result.chunk_id);
+    const key = concept?.concept_id ?? '';
+    let group = groups.get(key);
+    if (!group) {
+      group = { concept_id: concept?.concept_id ?? null, label: concept?.label?.trim() ?? '', results: [] };
+      groups.set(key, group);
+    }
+    group.results.push(result);
+  }
+
+  // Map iteration follows first insertion, i.e. each group's best result
+  return [...groups.values()].map(group => ({
+    ...group,
+    label: group.label || sharedTokenLabel(group.results.map(result => result.text)),
+  }));
+}
@@ -2,9 +2,18 @@
 import { createHash, randomUUID } from 'crypto';
 import { embed } from '../embed';
 import { loadXenova } from '../xenova';
-import { db, qdrant, COLLECTION, CONCEPT_COLLECTION, DEFAULT_MEMORY_DB } from '../db';
+import { db, qdrant, COLLECTION, CONCEPT_COLLECTION, DEFAULT_MEMORY_DB, getGraphVersion } from '../db';
+import { recallCacheKey, getCachedRecall, setCachedRecall } from './cache';
+import { recordChunkAccess } from './accessLog';
+import { expiredChunkIds, earliestExpiry } from '../db/chunks';
+import { getDatabaseConfig } from '../db/memoryDatabase';
+import type { DatabaseConfig } from '../db/memoryDatabase';
+import { selectResults } from './diversity';
+import { Frontier } from './frontier';
+import { combineRelevance, isScoreCombine, SCORE_COMBINE_MODES, accumulatePathScore, isPathAccumulation, PATH_ACCUMULATION_MODES, isConfidenceMapping, CONFIDENCE_MAPPINGS, mapConfidence } from './combine';
+import type { ScoreCombine, PathAccumulation, ConfidenceMapping } from './combine';
 import { buildChunkConceptMembership, conceptScoreForChunk, predictAssociativeScores } from '../associative';
-import { INCLUDE_CONCEPTS, DEBUG_PERF, CONCEPT_BOOST, CONCEPT_TOP_K, CONCEPT_MIN_SCORE, MIN_SCORE } from '../config';
+import { INCLUDE_CONCEPTS, ACCESS_REFRESHES_EDGES, MAX_RECALL_FRONTIER, RECALL_IMPROVEMENT_EPSILON, DEBUG_PERF, CONCEPT_BOOST, CONCEPT_TOP_K, CONCEPT_MIN_SCORE, MIN_SCORE } from '../config';
 import type { RetrievalLayer } from '../types/evidence';
 
 const MAX_HOPS = 2;
@@ -40,6 +49,7 @@ export interface Result {
   path: string[];
   conflicts: string[];
   rerankScore?: number;   // raw cross-encoder relevance (0–1); present only after re-ranking
+  confidence?: number;    // score squashed into [0, 1]; present only when a confidence mapping is requested
 }
 
 export interface CandidateChunk {
@@ -48,6 +58,11 @@ export interface CandidateChunk {
   hopDepth: number;
   path: string[];
   vectorScore: number;
+  shortestDepth?: number;   // fewest hops from any seed; set by multiHopExpand
+  pathDecay?: number;       // product of hop decays along the path (1 for seeds)
+  pathMinWeight?: number;   // weakest edge on the path
+  pathWeightSum?: number;   // sum of edge weights on the path
+  parentId?: string;        // node this one was reached from (unset for seeds)
 }
 
 export interface RetrieveOptions {
@@ -56,6 +71,22 @@ export interface RetrieveOptions {
   maxHops?: number;
   relationshipFilter?: string[];
   includeConflicts?: boolean;
+  minContentLength?: number;   // skip chunks whose trimmed text is shorter than this
+  useCache?: boolean;          // serve identical queries on an unchanged graph from the recall cache
+  hopDecay?: number;           // per-hop score multiplier for graph expansion, in (0, 1); default 0.9
+  degreeBoost?: number;        // scale scores by 1 + degreeBoost * ln(1 + in-degree); 0 disables
+  exactDepth?: number;         // only return chunks whose shortest graph distance from a seed is exactly this
+  recordAccess?: boolean;      // default true; false makes retrieval side-effect free (no access/co-access writes)
+  scoreCombine?: ScoreCombine; // how content and association scores merge; default weighted_sum
+  minEdgeAccessCount?: number; // only traverse edges co-activated at least this many times; 0 = any edge
+  refreshEdgeRecency?: boolean; // recorded access also resets incident edges' decay clock; default ACCESS_REFRESHES_EDGES
+  pathAccumulation?: PathAccumulation; // how edge weights along a path combine; default multiplicative
+  maxFrontier?: number;        // cap on queued nodes during expansion; default MAX_RECALL_FRONTIER, 0 = unbounded; ignored with exactDepth
+  improvementEpsilon?: number; // re-expand a reached node only if its score improves by more than this; default RECALL_IMPROVEMENT_EPSILON
+  diversity?: number;          // MMR trade-off in [0, 1] between relevance and novelty of content; 0 = off
+  relevanceMass?: number;      // return the fewest results covering this fraction (0, 1] of total score; topK stays the cap
+  useConfidence?: boolean;     // scale final scores by each chunk's stored source confidence
+  confidenceMapping?: ConfidenceMapping; // attach `confidence`, the final score squashed into [0, 1]
 }
 
 type EdgeRow = {
@@ -69,6 +100,10 @@ type ChunkRow = {
   source: string;
 };
 
+// Pool rows carry the trimmed length the content filters need, so id-only
+// recall can filter without reading the text itself
+type PoolRow = ChunkRow & { content_length: number };
+
 const VALID_RELATIONSHIPS = new Set<RelationshipType>([
   'supports',
   'contradicts',
@@ -87,17 +122,35 @@ function sanitizeRelationshipFilter(filter?: string[]): RelationshipType[] | und
   return normalized.length > 0 ? normalized : undefined;
 }
 
-function normalizeRetrieveArgs(
-  topKOrOptions: number | RetrieveOptions | undefined,
-  databaseOrOptions?: string | RetrieveOptions,
-  options?: RetrieveOptions,
-): {
+type NormalizedRetrieveOptions = {
   topK: number;
   database: string;
   maxHops: number;
   includeConflicts: boolean;
   relationshipFilter?: RelationshipType[];
-} {
+  minContentLength: number;
+  useCache: boolean;
+  hopDecay: number;
+  degreeBoost: number;
+  exactDepth?: number;
+  recordAccess: boolean;
+  scoreCombine: ScoreCombine;
+  minEdgeAccessCount: number;
+  refreshEdgeRecency: boolean;
+  pathAccumulation: PathAccumulation;
+  maxFrontier: number;
+  improvementEpsilon: number;
+  diversity: number;
+  relevanceMass?: number;
+  useConfidence: boolean;
+  confidenceMapping?: ConfidenceMapping;
+};
+
+function normalizeRetrieveArgs(
+  topKOrOptions: number | RetrieveOptions | undefined,
+  databaseOrOptions?: string | RetrieveOptions,
+  options?: RetrieveOptions,
+): NormalizedRetrieveOptions {
   let topK = 20;
   let database = DEFAULT_MEMORY_DB;
   let mergedOptions: RetrieveOptions = {};
@@ -126,13 +179,99 @@ function normalizeRetrieveArgs(
     database = mergedOptions.database.trim();
   }
 
-  const maxHops =
+  // Stored per-database defaults only fill options the caller left unset
+  const databaseConfig = getDatabaseConfig(database);
+  for (const key of Object.keys(databaseConfig) as Array<keyof DatabaseConfig>) {
+    if (mergedOptions[key] === undefined) {
+      mergedOptions = { ...mergedOptions, [key]: databaseConfig[key] };
+    }
+  }
+
+  const exactDepth =
+    typeof mergedOptions.exactDepth === 'number' && Number.isFinite(mergedOptions.exactDepth)
+      ? Math.max(0, Math.floor(mergedOptions.exactDepth))
+      : undefined;
+
+  const requestedHops =
     typeof mergedOptions.maxHops === 'number' && Number.isFinite(mergedOptions.maxHops)
       ? Math.max(0, Math.floor(mergedOptions.maxHops))
       : MAX_HOPS;
+  // Expansion has to reach the requested ring
+  const maxHops = exactDepth !== undefined ? Math.max(requestedHops, exactDepth) : requestedHops;
 
   const includeConflicts = mergedOptions.includeConflicts !== false;
   const relationshipFilter = sanitizeRelationshipFilter(mergedOptions.relationshipFilter);
+  const minContentLength =
+    typeof mergedOptions.minContentLength === 'number' && Number.isFinite(mergedOptions.minContentLength)
+      ? Math.max(0, Math.floor(mergedOptions.minContentLength))
+      : 0;
+  const useCache = mergedOptions.useCache === true;
+  const recordAccess = mergedOptions.recordAccess !== false;
+  const refreshEdgeRecency = typeof mergedOptions.refreshEdgeRecency === 'boolean'
+    ? mergedOptions.refreshEdgeRecency
+    : ACCESS_REFRESHES_EDGES;
+
+  let hopDecay = HOP_DECAY;
+  if (mergedOptions.hopDecay !== undefined) {
+    if (!Number.isFinite(mergedOptions.hopDecay) || mergedOptions.hopDecay <= 0 || mergedOptions.hopDecay >= 1) {
+      throw new Error('hopDecay must be between 0 and 1 (exclusive)');
+    }
+    hopDecay = mergedOptions.hopDecay;
+  }
+
+  const degreeBoost =
+    typeof mergedOptions.degreeBoost === 'number' && Number.isFinite(mergedOptions.degreeBoost)
+      ? Math.max(0, mergedOptions.degreeBoost)
+      : 0;
+
+  const minEdgeAccessCount =
+    typeof mergedOptions.minEdgeAccessCount === 'number' && Number.isFinite(mergedOptions.minEdgeAccessCount)
+      ? Math.max(0, Math.floor(mergedOptions.minEdgeAccessCount))
+      : 0;
+
+  const maxFrontier =
+    typeof mergedOptions.maxFrontier === 'number' && Number.isFinite(mergedOptions.maxFrontier)
+      ? Math.max(0, Math.floor(mergedOptions.maxFrontier))
+      : MAX_RECALL_FRONTIER;
+
+  const improvementEpsilon =
+    typeof mergedOptions.improvementEpsilon === 'number' && Number.isFinite(mergedOptions.improvementEpsilon)
+      ? Math.max(0, mergedOptions.improvementEpsilon)
+      : Math.max(0, RECALL_IMPROVEMENT_EPSILON);
+
+  let diversity = 0;
+  if (mergedOptions.diversity !== undefined) {
+    if (typeof mergedOptions.diversity !== 'number' || !(mergedOptions.diversity >= 0 && mergedOptions.diversity <= 1)) {
+      throw new Error('diversity must be between 0 and 1');
+    }
+    diversity = mergedOptions.diversity;
+  }
+
+  const relevanceMass = mergedOptions.relevanceMass;
+  if (relevanceMass !== undefined && !(typeof relevanceMass === 'number' && relevanceMass > 0 && relevanceMass <= 1)) {
+    throw new Error('relevanceMass must be in (0, 1]');
+  }
+
+  let pathAccumulation: PathAccumulation = 'multiplicative';
+  if (mergedOptions.pathAccumulation !== undefined) {
+    if (!isPathAccumulation(mergedOptions.pathAccumulation)) {
+      throw new Error(`pathAccumulation must be one of ${PATH_ACCUMULATION_MODES.join(', ')}`);
+    }
+    pathAccumulation = mergedOptions.pathAccumulation;
+  }
+
+  const confidenceMapping = mergedOptions.confidenceMapping;
+  if (confidenceMapping !== undefined && !isConfidenceMapping(confidenceMapping)) {
+    throw new Error(`confidenceMapping must be one of ${CONFIDENCE_MAPPINGS.join(', ')}`);
+  }
+
+  let scoreCombine: ScoreCombine = 'weighted_sum';
+  if (mergedOptions.scoreCombine !== undefined) {
+    if (!isScoreCombine(mergedOptions.scoreCombine)) {
+      throw new Error(`scoreCombine must be one of ${SCORE_COMBINE_MODES.join(', ')}`);
+    }
+    scoreCombine = mergedOptions.scoreCombine;
+  }
 
   return {
     topK,
@@ -140,10 +279,29 @@ function normalizeRetrieveArgs(
     maxHops,
     includeConflicts,
     relationshipFilter,
+    minContentLength,
+    useCache,
+    hopDecay,
+    degreeBoost,
+    exactDepth,
+    recordAccess,
+    scoreCombine,
+    minEdgeAccessCount,
+    refreshEdgeRecency,
+    pathAccumulation,
+    maxFrontier,
+    improvementEpsilon,
+    diversity,
+    relevanceMass,
+    useConfidence: mergedOptions.useConfidence === true,
+    confidenceMapping,
   };
 }
 
-function buildConnectionQuery(filter?: RelationshipType[]): { sql: string; paramsFactory: (chunkId: string, database: string) => unknown[] } {
+function buildConnectionQuery(
+  filter?: RelationshipType[],
+  minAccessCount: number = 0,
+): { sql: string; paramsFactory: (chunkId: string, database: string) => unknown[] } {
   if (!filter || filter.length === 0) {
     return {
       sql: `
@@ -152,8 +310,9 @@ function buildConnectionQuery(filter?: RelationshipType[]): { sql: string; param
         WHERE source_chunk = ?
           AND database_id = ?
           AND weight > ?
+          AND COALESCE(access_count, 0) >= ?
       `,
-      paramsFactory: (chunkId, database) => [chunkId, database, MIN_EDGE_WEIGHT],
+      paramsFactory: (chunkId, database) => [chunkId, database, MIN_EDGE_WEIGHT, minAccessCount],
     };
   }
 
@@ -165,35 +324,91 @@ function buildConnectionQuery(filter?: RelationshipType[]): { sql: string; param
       WHERE source_chunk = ?
         AND database_id = ?
         AND weight > ?
+        AND COALESCE(access_count, 0) >= ?
         AND relationship IN (${placeholders})
     `,
-    paramsFactory: (chunkId, database) => [chunkId, database, MIN_EDGE_WEIGHT, ...filter],
+    paramsFactory: (chunkId, database) => [chunkId, database, MIN_EDGE_WEIGHT, minAccessCount, ...filter],
   };
 }
 
-async function multiHopExpand(
+export type ExpansionStats = {
+  peakFrontier: number;   // largest queue length seen during expansion
+  dropped: number;        // queued nodes discarded by the frontier cap
+  enqueued: number;       // nodes pushed onto the queue, seeds included
+};
+
+export class UnknownSeedError extends Error {
+  constructor(public readonly seeds: string[], database: string) {
+    super(`Unknown seed chunks in ${database}: ${seeds.join(', ')}`);
+    this.name = 'UnknownSeedError';
+  }
+}
+
+/**
+ * Breadth-first expansion from the vector seeds along weighted connections.
+ * When `maxFrontier` is set, the queue never holds more than that many nodes:
+ * past the cap the lowest-scoring queued node is dropped (it stays a result
+ * candidate, it just isn't expanded further). Drops can hide a shorter route
+ * to a node, so `shortestDepth` is only exact with an unbounded frontier.
+ * A node reached again is re-queued only when its score beats the best so far
+ * by more than `improvementEpsilon`; smaller gains still update its score but
+ * are not worth another expansion, which cuts churn around cycles.
+ * Edges straight back to the node a path just came from are not followed:
+ * with reciprocal edges such a 2-cycle would otherwise re-credit the
+ * predecessor, and can lift it under average or min_edge scoring.
+ * With `validateSeeds`, for seeds supplied by a caller rather than found by
+ * search, every seed must be a stored chunk of `options.database`; otherwise
+ * an `UnknownSeedError` lists the ones that are not, instead of an empty
+ * result.
+ */
+export async function multiHopExpand(
   seeds: CandidateChunk[],
   visited: Set<string>,
   options: {
     database: string;
     maxHops: number;
     relationshipFilter?: RelationshipType[];
+    hopDecay?: number;
+    minEdgeAccessCount?: number;
+    pathAccumulation?: PathAccumulation;
+    maxFrontier?: number;
+    improvementEpsilon?: number;
+    stats?: ExpansionStats;
+    validateSeeds?: boolean;   // reject seeds that are not stored chunks; default false
   },
 ): Promise<CandidateChunk[]> {
-  if (seeds.length === 0 || options.maxHops <= 0) return [...seeds];
+  if (options.validateSeeds) {
+    const storedSeeds = storedChunkIds([...new Set(seeds.map(seed => seed.chunkId))], options.database);
+    const unknownSeeds = [...new Set(seeds.map(seed => seed.chunkId).filter(chunkId => !storedSeeds.has(chunkId)))];
+    if (unknownSeeds.length > 0) throw new UnknownSeedError(unknownSeeds, options.database);
+  }
+  if (seeds.length === 0 || options.maxHops <= 0) return seeds.map(seed => ({ ...seed, shortestDepth: 0 }));
 
   const allCandidates = new Map<string, CandidateChunk>();
-  const queue: CandidateChunk[] = [];
+  const shortestDepth = new Map<string, number>();
+  const queue = new Frontier<CandidateChunk>(options.maxFrontier ?? 0);
+  const enqueue = (candidate: CandidateChunk): void => {
+    const dropped = queue.push(candidate, candidate.score);
+    if (options.stats) {
+      options.stats.enqueued++;
+      options.stats.dropped += dropped;
+      options.stats.peakFrontier = Math.max(options.stats.peakFrontier, queue.size);
+    }
+  };
 
   for (const seed of seeds) {
-    queue.push(seed);
+    enqueue(seed);
     allCandidates.set(seed.chunkId, seed);
+    shortestDepth.set(seed.chunkId, 0);
   }
 
-  const connectionQuery = buildConnectionQuery(options.relationshipFilter);
+  const improvementEpsilon = Math.max(0, options.improvementEpsilon ?? 0);
+
+  const connectionQuery = buildConnectionQuery(options.relationshipFilter, options.minEdgeAccessCount ?? 0);
   const connectionStmt = db.prepare(connectionQuery.sql);
+  const hopDecay = options.hopDecay ?? HOP_DECAY;
 
-  while (queue.length > 0) {
+  while (queue.size > 0) {
     const current = queue.shift()!;
     if (current.hopDepth >= options.maxHops) continue;
 
@@ -204,28 +419,106 @@ async function multiHopExpand(
       const target = edge?.target_chunk;
       const edgeWeight = edge?.weight ?? 0;
       if (!target || edgeWeight <= 0) continue;
+      if (target === current.chunkId || target === current.parentId) continue;
 
       const nextDepth = current.hopDepth + 1;
-      const nextScore = current.score * Math.pow(HOP_DECAY, nextDepth) * edgeWeight;
+      // Breadth-first order means the first discovery is the shortest one
+      if (!shortestDepth.has(target)) shortestDepth.set(target, nextDepth);
+      const hopFactor = Math.pow(hopDecay, nextDepth);
+      const pathDecay = (current.pathDecay ?? 1) * hopFactor;
+      const pathMinWeight = Math.min(current.pathMinWeight ?? Number.POSITIVE_INFINITY, edgeWeight);
+      const pathWeightSum = (current.pathWeightSum ?? 0) + edgeWeight;
+      const nextScore = accumulatePathScore({
+        previousScore: current.score,
+        seedScore: current.vectorScore,
+        edgeWeight,
+        hopFactor,
+        decayProduct: pathDecay,
+        minWeight: pathMinWeight,
+        weightSum: pathWeightSum,
+        hops: nextDepth,
+      }, options.pathAccumulation);
       const nextCandidate: CandidateChunk = {
         chunkId: target,
         score: nextScore,
         hopDepth: nextDepth,
         path: [...current.path, `${target} (w:${edgeWeight.toFixed(2)})`],
         vectorScore: current.vectorScore,
+        pathDecay,
+        pathMinWeight,
+        pathWeightSum,
+        parentId: current.chunkId,
       };
 
       const existing = allCandidates.get(target);
       if (!existing || nextCandidate.score > existing.score) {
         allCandidates.set(target, nextCandidate);
-        if (!visited.has(target) && nextDepth < options.maxHops) {
-          queue.push(nextCandidate);
+        const worthExpanding = !existing || nextCandidate.score - existing.score > improvementEpsilon;
+        if (worthExpanding && !visited.has(target) && nextDepth < options.maxHops) {
+          enqueue(nextCandidate);
         }
       }
     }
   }
 
-  return Array.from(allCandidates.values());
+  return Array.from(allCandidates.values()).map(candidate => ({
+    ...candidate,
+    shortestDepth: shortestDepth.get(candidate.chunkId) ?? candidate.hopDepth,
+  }));
+}
+
+function buildInDegreeMap(chunkIds: string[], database: string): Map<string, number> {
+  const map = new Map<string, number>();
+  if (chunkIds.length === 0) return map;
+
+  const placeholders = chunkIds.map(() => '?').join(', ');
+  const rows = db.prepare(`
+    SELECT target_chunk, COUNT(*) AS in_degree
+    FROM connections
+    WHERE database_id = ?
+      AND target_chunk IN (${placeholders})
+    GROUP BY target_chunk
+  `).all(database, ...chunkIds) as Array<{ target_chunk: string; in_degree: number }>;
+
+  for (const row of rows) {
+    map.set(row.target_chunk, row.in_degree);
+  }
+
+  return map;
+}
+
+function storedChunkIds(chunkIds: string[], database: string): Set<string> {
+  if (chunkIds.length === 0) return new Set();
+
+  const placeholders = chunkIds.map(() => '?').join(', ');
+  const rows = db.prepare(`
+    SELECT chunk_id
+    FROM chunks
+    WHERE database_id = ?
+      AND chunk_id IN (${placeholders})
+  `).all(database, ...chunkIds) as Array<{ chunk_id: string }>;
+  return new Set(rows.map(row => row.chunk_id));
+}
+
+/**
+ * Scale each result by its chunk's stored confidence and re-sort, so
+ * low-trust sources sink below equally relevant trusted ones.
+ */
+function weightByConfidence(results: Result[], database: string): Result[] {
+  if (results.length === 0) return results;
+
+  const placeholders = results.map(() => '?').join(', ');
+  const rows = db.prepare(`
+    SELECT chunk_id, COALESCE(confidence, 1.0) AS confidence
+    FROM chunks
+    WHERE database_id = ?
+      AND chunk_id IN (${placeholders})
+  `).all(database, ...results.map(result => result.chunk_id)) as Array<{ chunk_id: string; confidence: number }>;
+  const confidence = new Map(rows.map(row => [row.chunk_id, row.confidence]));
+
+  return results
+    .map(result => ({ ...result, score: result.score * (confidence.get(result.chunk_id) ?? 1) }))
+    .sort((a, b) => b.score - a.score);
 }
 
 function buildConflictMap(chunkIds: string[], database: string): Map<string, Set<string>> {
@@ -252,6 +545,24 @@ function buildConflictMap(chunkIds: string[], database: string): Map<string, Set
   return map;
 }
 
+/**
+ * Reset the decay clock (`last_reinforced`) of every connection touching the
+ * recalled chunks. Weight is untouched, so this only keeps used associations
+ * out of the stale-decay penalty.
+ */
+function refreshIncidentEdges(chunkIds: string[], database: string): void {
+  const unique = [...new Set(chunkIds)];
+  if (unique.length === 0) return;
+
+  const placeholders = unique.map(() => '?').join(', ');
+  db.prepare(`
+    UPDATE connections
+    SET last_reinforced = ?
+    WHERE database_id = ?
+      AND (source_chunk IN (${placeholders}) OR target_chunk IN (${placeholders}))
+  `).run(new Date().toISOString(), database, ...unique, ...unique);
+}
+
 async function recordCoAccess(
   chunkIds: string[],
   queryHash: string,
@@ -361,6 +672,62 @@ export async function retrieve(
   options?: RetrieveOptions,
 ): Promise<Result[]> {
   const normalized = normalizeRetrieveArgs(topKOrOptions, databaseOrOptions, options);
+  return withConfidence(await cachedRecall(query, normalized, false), normalized.confidenceMapping);
+}
+
+async function cachedRecall(query: string, normalized: NormalizedRetrieveOptions, idsOnly: boolean): Promise<Result[]> {
+  if (!normalized.useCache) return runRetrieve(query, normalized, idsOnly);
+
+  // Cache hits skip access/co-access bookkeeping: an identical query on an
+  // unchanged graph would not teach the Hebbian layer anything new anyway.
+  // The confidence mapping is display-only, so it does not split cache entries;
+  // id-only results lack most text, so they do.
+  const shape = { ...normalized, useCache: undefined, confidenceMapping: undefined, idsOnly: idsOnly || undefined };
+  const cacheKey = recallCacheKey(normalized.database, getGraphVersion(normalized.database), query, shape);
+  const cached = getCachedRecall(cacheKey);
+  if (cached) return cached;
+
+  const results = await runRetrieve(query, normalized, idsOnly);
+  setCachedRecall(cacheKey, results, earliestExpiry(results.map(result => result.chunk_id), normalized.database));
+  return results;
+}
+
+function withConfidence(results: Result[], mapping?: ConfidenceMapping): Result[] {
+  if (!mapping) return results;
+  return results.map(result => ({ ...result, confidence: mapConfidence(result.score, mapping) }));
+}
+
+export type ScoredId = { chunk_id: string; score: number };
+
+/**
+ * `retrieve` reduced to ranked ids and scores, for callers that resolve
+ * content themselves (or not at all) and do not want text and paths on the
+ * wire. Same options, same order, same scores, but cheaper: chunk text is only
+ * read for the candidates the re-ranker scores, and conflicts are not looked up.
+ */
+export async function retrieveIds(query: string, options: RetrieveOptions = {}): Promise<ScoredId[]> {
+  const results = await cachedRecall(query, normalizeRetrieveArgs(options), true);
+  return results.map(result => ({ chunk_id: result.chunk_id, score: result.score }));
+}
+
+// Fill in the text id-only recall skipped, for the candidates about to be re-ranked
+function hydrateTexts(candidates: Result[], seedRowsById: Map<string, PoolRow>, database: string): void {
+  const missing = candidates.filter(candidate => !seedRowsById.has(candidate.chunk_id)).map(candidate => candidate.chunk_id);
+  const texts = new Map<string, string>();
+  if (missing.length > 0) {
+    const placeholders = missing.map(() => '?').join(', ');
+    const rows = db.prepare(`
+      SELECT chunk_id, text FROM chunks
+      WHERE database_id = ? AND chunk_id IN (${placeholders})
+    `).all(database, ...missing) as Array<{ chunk_id: string; text: string }>;
+    for (const row of rows) texts.set(row.chunk_id, row.text);
+  }
+  for (const candidate of candidates) {
+    candidate.text = seedRowsById.get(candidate.chunk_id)?.text ?? texts.get(candidate.chunk_id) ?? '';
+  }
+}
+
+async function runRetrieve(query: string, normalized: NormalizedRetrieveOptions, idsOnly: boolean = false): Promise<Result[]> {
   const dbName = normalized.database || DEFAULT_MEMORY_DB;
   const vector = await embed(query);
   const queryHash = createHash('sha256').update(query).digest('hex');
@@ -377,20 +744,37 @@ export async function retrieve(
     },
   });
 
+  // An empty database has nothing to recall; no access is recorded
   if (hits.length === 0) return [];
 
   const seeds: CandidateChunk[] = [];
-  const seedRowsById = new Map<string, ChunkRow>();
+  const seedRowsById = new Map<string, PoolRow>();
   const visited = new Set<string>();
+  const storedSeeds = storedChunkIds(hits.map(hit => (hit.payload as any)?.chunk_id).filter(Boolean), dbName);
+
+  // Expired chunks stay invisible even before the sweep deletes them
+  const nowIso = new Date().toISOString();
+  const expiredStmt = db.prepare(`
+    SELECT 1 FROM chunks
+    WHERE chunk_id = ? AND database_id = ?
+      AND expires_at IS NOT NULL AND expires_at <= ?
+  `);
+  const isExpired = (chunkId: string): boolean => Boolean(expiredStmt.get(chunkId, dbName, nowIso));
 
   for (const hit of hits) {
     const payload = hit.payload as any;
     const chunk_id = payload?.chunk_id;
     if (!chunk_id) continue;
+    // A vector whose chunk row is gone (e.g. a delete that failed half way)
+    // must not seed recall or have access recorded against it
+    if (!storedSeeds.has(chunk_id)) continue;
+    if (isExpired(chunk_id)) continue;
 
+    const text: string = payload.text ?? '';
     seedRowsById.set(chunk_id, {
-      text: payload.text ?? '',
+      text,
       source: payload.source ?? '',
+      content_length: text.trim().length,
     });
 
     seeds.push({
@@ -402,14 +786,24 @@ export async function retrieve(
     });
   }
 
+  if (seeds.length === 0) return [];
+
   const expandedCandidates = await multiHopExpand(seeds, visited, {
     database: dbName,
     maxHops: normalized.maxHops,
     relationshipFilter: normalized.relationshipFilter,
+    hopDecay: normalized.hopDecay,
+    minEdgeAccessCount: normalized.minEdgeAccessCount,
+    pathAccumulation: normalized.pathAccumulation,
+    // Exact-depth filtering needs true hop counts, which a trimmed frontier can miss
+    maxFrontier: normalized.exactDepth !== undefined ? 0 : normalized.maxFrontier,
+    improvementEpsilon: normalized.improvementEpsilon,
   });
 
+  // Id-only recall skips the text here; MAX_RERANK_CANDIDATES of them get it back before re-ranking
   const chunkStmt = db.prepare(`
-    SELECT text, source
+    SELECT ${idsOnly ? "'' AS text, '' AS source" : 'text, source'},
+           LENGTH(TRIM(text, ' ' || char(9, 10, 11, 12, 13))) AS content_length
     FROM chunks
     WHERE chunk_id = ?
       AND database_id = ?
@@ -420,10 +814,14 @@ export async function retrieve(
 
   for (const candidate of expandedCandidates) {
     if (seenIds.has(candidate.chunkId)) continue;
+    if (normalized.exactDepth !== undefined && candidate.shortestDepth !== normalized.exactDepth) continue;
 
     const seedRow = seedRowsById.get(candidate.chunkId);
-    const chunkRow = seedRow ?? (chunkStmt.get(candidate.chunkId, dbName) as ChunkRow | undefined);
+    const chunkRow = seedRow ?? (chunkStmt.get(candidate.chunkId, dbName) as PoolRow | undefined);
     if (!chunkRow) continue;
+    if (chunkRow.content_length === 0) continue;   // import placeholders carry no content yet
+    if (chunkRow.content_length < normalized.minContentLength) continue;
+    if (candidate.hopDepth > 0 && isExpired(candidate.chunkId)) continue;
 
     seenIds.add(candidate.chunkId);
     mergedPool.push({
@@ -442,7 +840,8 @@ export async function retrieve(
   }
 
   // PHASE 6: Concept-boosted retrieval via dedicated Qdrant collection
-  if (INCLUDE_CONCEPTS) {
+  // (concept members have no graph distance, so exact-depth queries skip them)
+  if (INCLUDE_CONCEPTS && normalized.exactDepth === undefined) {
     const t0 = DEBUG_PERF ? Date.now() : 0;
     try {
       // Search the concept vector collection — no in-process embedding needed
@@ -480,8 +879,10 @@ export async function retrieve(
         for (const memberId of memberChunks) {
           if (seenIds.has(memberId)) continue;
 
-          const chunkRow = chunkStmt.get(memberId, dbName) as { text: string; source: string } | undefined;
+          const chunkRow = chunkStmt.get(memberId, dbName) as PoolRow | undefined;
           if (!chunkRow) continue;
+          if (chunkRow.content_length < normalized.minContentLength) continue;
+          if (isExpired(memberId)) continue;
 
           // Base score = weakest vector hit score, boosted by concept fusion
           const baseScore = weakSeedScore;
@@ -525,10 +926,11 @@ export async function retrieve(
     for (const candidate of mergedPool) {
       const mlpConceptScore = conceptScoreForChunk(candidate.chunk_id, conceptScores, chunkConceptMap);
       candidate.mlpConceptScore = mlpConceptScore;
-      candidate.score =
-        (0.6 * candidate.vectorScore) +
-        (0.25 * candidate.graphScore) +
-        (mlpWeight * mlpConceptScore);
+      candidate.score = combineRelevance(
+        0.6 * candidate.vectorScore,
+        (0.25 * candidate.graphScore) + (mlpWeight * mlpConceptScore),
+        normalized.scoreCombine,
+      );
     }
   } catch (error) {
     if (DEBUG_PERF) {
@@ -537,6 +939,14 @@ export async function retrieve(
     }
   }
 
+  // Optional hub bias: well-connected chunks get a log-scaled lift.
+  if (normalized.degreeBoost > 0) {
+    const inDegrees = buildInDegreeMap(mergedPool.map(candidate => candidate.chunk_id), dbName);
+    for (const candidate of mergedPool) {
+      candidate.score *= 1 + normalized.degreeBoost * Math.log1p(inDegrees.get(candidate.chunk_id) ?? 0);
+    }
+  }
+
   mergedPool.sort((a, b) => b.score - a.score);
   const rescored = rescorePool(mergedPool).map(candidate => ({
     text: candidate.text,
@@ -550,7 +960,7 @@ export async function retrieve(
   }));
   rescored.sort((a, b) => b.score - a.score);
 
-  if (normalized.includeConflicts) {
+  if (normalized.includeConflicts && !idsOnly) {
     const conflictMap = buildConflictMap(rescored.map(r => r.chunk_id), dbName);
     for (const result of rescored) {
       result.conflicts = Array.from(conflictMap.get(result.chunk_id) ?? []);
@@ -558,41 +968,34 @@ export async function retrieve(
   }
 
   if (rescored.length <= 1) {
-    for (const result of rescored) {
-      db.prepare(`
-        UPDATE chunks
-        SET access_count = access_count + 1,
-            last_accessed = ?
-        WHERE chunk_id = ?
-          AND database_id = ?
-      `).run(new Date().toISOString(), result.chunk_id, dbName);
+    if (normalized.recordAccess) {
+      recordChunkAccess(rescored.map(r => r.chunk_id), dbName);
+      if (normalized.refreshEdgeRecency) refreshIncidentEdges(rescored.map(r => r.chunk_id), dbName);
+      await recordCoAccess(rescored.map(r => r.chunk_id), queryHash, vector, dbName);
     }
 
-    await recordCoAccess(rescored.map(r => r.chunk_id), queryHash, vector, dbName);
-
     return rescored;
   }
 
   const topCandidates = rescored.slice(0, MAX_RERANK_CANDIDATES);
-  const reranked = await rerankCandidates(query, topCandidates);
-  const filtered = reranked
-    .filter(c => c.score >= MIN_SCORE)
-    .slice(0, normalized.topK);
+  if (idsOnly) hydrateTexts(topCandidates, seedRowsById, dbName);
+  // Confidence scales the blended score, so the cross-encoder cannot undo it
+  const reranked = normalized.useConfidence
+    ? weightByConfidence(await rerankCandidates(query, topCandidates), dbName)
+    : await rerankCandidates(query, topCandidates);
+  // MMR picks from the whole relevant pool so near-duplicates can give way
+  // to lower-ranked but different passages.
+  const filtered = selectResults(reranked.filter(c => c.score >= MIN_SCORE), {
+    topK: normalized.topK,
+    diversity: normalized.diversity,
+    relevanceMass: normalized.relevanceMass,
+  });
 
   if (filtered.length === 0) return [];
+  if (!normalized.recordAccess) return filtered;
 
-  for (const result of filtered) {
-    const chunk_id = result.chunk_id;
-
-    db.prepare(`
-      UPDATE chunks
-      SET access_count = access_count + 1,
-          last_accessed = ?
-      WHERE chunk_id = ?
-        AND database_id = ?
-    `).run(new Date().toISOString(), chunk_id, dbName);
-  }
-
+  recordChunkAccess(filtered.map(r => r.chunk_id), dbName);
+  if (normalized.refreshEdgeRecency) refreshIncidentEdges(filtered.map(r => r.chunk_id), dbName);
   await recordCoAccess(filtered.map(r => r.chunk_id), queryHash, vector, dbName);
 
   return filtered;
@@ -628,11 +1031,13 @@ export async function retrieveByVector(embedding: number[], k: number = 10, data
 
   if (hits.length === 0) return [];
 
+  const expired = expiredChunkIds(hits.map(hit => (hit.payload as any)?.chunk_id).filter(Boolean), dbName);
   const results: Result[] = [];
   for (const hit of hits) {
     const payload = hit.payload as any;
     const chunk_id = payload?.chunk_id;
     if (!chunk_id) continue;
+    if (expired.has(chunk_id)) continue;
 
     results.push({
       text: payload.text ?? '',
@@ -675,7 +1080,12 @@ export async function expandWithConcepts(
 
     if (conceptHits.length === 0) return [];
 
-    const chunkStmt = db.prepare('SELECT text, source FROM chunks WHERE chunk_id = ? AND database_id = ?');
+    const chunkStmt = db.prepare(`
+      SELECT text, source FROM chunks
+      WHERE chunk_id = ? AND database_id = ?
+        AND (expires_at IS NULL OR expires_at > ?)
+    `);
+    const nowIso = new Date().toISOString();
     const results: Result[] = [];
 
     for (const hit of conceptHits) {
@@ -699,7 +1109,7 @@ export async function expandWithConcepts(
       for (const memberId of memberChunks) {
         if (results.length >= maxChunks) break;
 
-        const chunkRow = chunkStmt.get(memberId, dbName) as { text: string; source: string } | undefined;
+        const chunkRow = chunkStmt.get(memberId, dbName, nowIso) as { text: string; source: string } | undefined;
         if (!chunkRow) continue;
 
         const fusedScore = membershipFactor * CONCEPT_BOOST + conceptSimilarity;
@@ -0,0 +1,91 @@
+// src/retrieve/textSearch.ts — Literal (non-vector) text matching for search boxes
+
+export type SearchMode =
+  | { kind: 'substring' }
+  | { kind: 'prefix' }
+  | { kind: 'fuzzy'; maxDistance: number };
+
+/** Classic edit distance (insert / delete / substitute, all cost 1). */
+export function levenshtein(a: string, b: string): number {
+  if (a === b) return 0;
+  if (a.length === 0) return b.length;
+  if (b.length === 0) return a.length;
+
+  let previous = Array.from({ length: b.length + 1 }, (_, index) => index);
+  for (let i = 1; i <= a.length; i++) {
+    const current = [i];
+    for (let j = 1; j <= b.length; j++) {
+      const cost = a[i - 1] === b[j - 1] ? 0 : 1;
+      current[j] = Math.min(previous[j] + 1, current[j - 1] + 1, previous[j - 1] + cost);
+    }
+    previous = current;
+  }
+
+  return previous[b.length];
+}
+
+function normalize(text: string): string {
+  return text.toLowerCase().replace(/\s+/g, ' ').trim();
+}
+
+const TOKEN_CHAR = /[\p{L}\p{N}]/u;
+
+function ftsPhrase(word: string): string {
+  return `"${word.replace(/"/g, '""')}"`;
+}
+
+/**
+ * FTS5 MATCH expression that every chunk matched by a multi-word substring or
+ * prefix query must satisfy, or null when the query gives nothing to narrow
+ * on (fuzzy mode, or no whole words). Words strictly inside the query are
+ * whole words of the text; the last word is a word prefix; for prefix mode
+ * the first word is whole too. The expression is a superset filter — results
+ * are still confirmed with `matchQuality`.
+ */
+export function candidateTokenQuery(query: string, mode: SearchMode): string | null {
+  if (mode.kind === 'fuzzy') return null;
+
+  const words = normalize(query).split(' ').filter(Boolean);
+  if (words.length < 2) return null;
+
+  const whole = mode.kind === 'prefix' ? words.slice(0, -1) : words.slice(1, -1);
+  const terms = whole.filter(word => TOKEN_CHAR.test(word)).map(ftsPhrase);
+  const last = words[words.length - 1];
+  if (TOKEN_CHAR.test(last)) terms.push(`${ftsPhrase(last)}*`);
+
+  return terms.length > 0 ? terms.join(' AND ') : null;
+}
+
+/**
+ * Match quality in (0, 1], or null when `text` does not match.
+ * Substring/prefix favour texts where the query covers more of the content;
+ * fuzzy compares the query against every word window of the same length and
+ * scores by the best edit distance within `maxDistance`.
+ */
+export function matchQuality(text: string, query: string, mode: SearchMode): number | null {
+  const haystack = normalize(text);
+  const needle = normalize(query);
+  if (!needle || !haystack) return null;
+
+  if (mode.kind === 'substring') {
+    return haystack.includes(needle) ? needle.length / haystack.length : null;
+  }
+
+  if (mode.kind === 'prefix') {
+    return haystack.startsWith(needle) ? needle.length / haystack.length : null;
+  }
+
+  const maxDistance = Math.max(0, Math.floor(mode.maxDistance));
+  const words = haystack.split(' ');
+  const windowSize = needle.split(' ').length;
+  let best = Number.POSITIVE_INFINITY;
+
+  for (let i = 0; i + windowSize <= words.length; i++) {
+    const distance = levenshtein(words.slice(i, i + windowSize).join(' '), needle);
+    if (distance < best) best = distance;
+    if (best === 0) break;
+  }
+
+  if (best > maxDistance) return null;
+  return 1 - best / (needle.length + 1);
+}
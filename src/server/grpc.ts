@@ -1,15 +1,29 @@
 // src/server/grpc.ts — gRPC service handlers
 import { v4 as uuidv4 } from 'uuid';
 import * as grpc from '@grpc/grpc-js';
-import { db, qdrant, COLLECTION } from '../db';
+import { db, qdrant, COLLECTION, DEFAULT_MEMORY_DB, getGraphVersion, graphVersionMatches, GraphVersionConflictError } from '../db';
+import { iterateConnections, insertWithinEdgeLimit } from '../db/connections';
+import type { ConnectionRow } from '../db/connections';
+import { getChunksByIds, getChunkReport, listUnconsolidatedChunks, listRecentChunks, deleteChunks, planChunkCapacity, writeWithinCapacity, CapacityExceededError } from '../db/chunks';
+import { iterateGraphExport, importGraphRecords, InvalidGraphRecordError } from '../db/graphTransfer';
+import type { GraphRecord, ImportGraphResult, WeightPrecision } from '../db/graphTransfer';
+import type { ChunkReport } from '../db/chunks';
+import { getGraphSummary } from '../db/summary';
+import { withStoreRetry } from '../db/retry';
 import { embed } from '../embed';
 import { retrieve } from '../retrieve';
-import { semanticChunkText } from '../ingest';
+import { isScoreCombine, SCORE_COMBINE_MODES, isPathAccumulation, PATH_ACCUMULATION_MODES, isConfidenceMapping, CONFIDENCE_MAPPINGS } from '../retrieve/combine';
+import { semanticChunkText, limitChunkContent, noveltyScore, isConfidence, ContentTooLargeError } from '../ingest';
+import { claimContent, releaseContent } from '../ingest/inflight';
 import type {
   SimilarChunkHit, IngestRequest, IngestResponse,
   QueryRequest, QueryResponse, HealthResponse,
+  GetChunksRequest, GetChunksResponse, ExportGraphRequest, GetChunkReportRequest,
+  ListConnectionsRequest, ListUnconsolidatedRequest, ListUnconsolidatedResponse,
+  DeleteChunksRequest, DeleteChunksResponse, ListRecentRequest, ListRecentResponse,
+  GetGraphSummaryRequest, GraphSummaryResponse,
 } from './helpers';
-import { DUPLICATE_THRESHOLD } from './helpers';
+import { DUPLICATE_THRESHOLD, checkQueryLimits, toGrpcResult } from './helpers';
 
 // ── gRPC helpers ───────────────────────────────────────────────────────────
 
@@ -21,34 +35,32 @@ export function makeGrpcError(code: grpc.status, message: string): grpc.ServiceE
   } as grpc.ServiceError;
 }
 
-async function topSimilarityScore(vector: number[]): Promise<number> {
+// Null when the search failed, so novelty is left unmeasured rather than 1
+async function topSimilarityScore(vector: number[], database: string): Promise<number | null> {
   try {
     const results = await qdrant.search(COLLECTION, {
       vector,
       limit: 10,
       with_payload: false,
       with_vector: false,
+      filter: { must: [{ key: 'database_id', match: { value: database } }] },
     });
 
     if (!results || results.length === 0) return 0;
     return results[0].score ?? 0;
   } catch {
-    return 0;
+    return null;
   }
 }
 
-async function checkDuplicate(vector: number[], threshold: number): Promise<boolean> {
-  const score = await topSimilarityScore(vector);
-  return score >= threshold;
-}
-
-async function findSimilarExistingChunks(vector: number[], limit = 5): Promise<string[]> {
+async function findSimilarExistingChunks(vector: number[], database: string, limit = 5): Promise<string[]> {
   try {
     const results = await qdrant.search(COLLECTION, {
       vector,
       limit,
       with_payload: true,
       with_vector: false,
+      filter: { must: [{ key: 'database_id', match: { value: database } }] },
     }) as SimilarChunkHit[];
 
     return results
@@ -59,7 +71,7 @@ async function findSimilarExistingChunks(vector: number[], limit = 5): Promise<s
   }
 }
 
-function seedConnections(sourceChunkId: string, targetChunkIds: string[], timestamp: string): number {
+function seedConnections(sourceChunkId: string, targetChunkIds: string[], timestamp: string, database: string): number {
   if (targetChunkIds.length === 0) return 0;
 
   const existsStmt = db.prepare(`
@@ -72,8 +84,8 @@ function seedConnections(sourceChunkId: string, targetChunkIds: string[], timest
   `);
 
   const insertStmt = db.prepare(`
-    INSERT INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, last_reinforced)
-    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+    INSERT INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, last_reinforced, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
   `);
 
   let created = 0;
@@ -83,7 +95,7 @@ function seedConnections(sourceChunkId: string, targetChunkIds: string[], timest
     const existing = existsStmt.get(sourceChunkId, targetChunkId) as { edge_id: string } | undefined;
     if (existing) continue;
 
-    insertStmt.run(
+    created += insertWithinEdgeLimit(sourceChunkId, targetChunkId, () => insertStmt.run(
       uuidv4(),
       sourceChunkId,
       targetChunkId,
@@ -92,8 +104,8 @@ function seedConnections(sourceChunkId: string, targetChunkIds: string[], timest
       0.5,
       timestamp,
       null,
-    );
-    created++;
+      database,
+    ).changes, database);
   }
 
   return created;
@@ -122,42 +134,112 @@ export const ingestHandler: grpc.handleUnaryCall<IngestRequest, IngestResponse>
       return;
     }
 
+    const database = call.request.database?.trim() || DEFAULT_MEMORY_DB;
+    // Rejects early; the authoritative check runs inside the write transaction
+    const expectedVersion = Number(call.request.expected_version ?? 0);
+    if (!graphVersionMatches(expectedVersion, database)) {
+      const message = `Graph version mismatch: expected ${expectedVersion}, current ${getGraphVersion(database)}`;
+      console.error(`❌ Ingest failed: ${message}`);
+      callback(makeGrpcError(grpc.status.ABORTED, message), {
+        success: false,
+        chunks_stored: 0,
+        chunks_skipped: 0,
+        connections_seeded: 0,
+        error: message,
+      });
+      return;
+    }
+
+    const ttlMs = Number(call.request.ttl_ms ?? 0);
+    const expiresAt = Number.isFinite(ttlMs) && ttlMs > 0 ? new Date(Date.now() + ttlMs).toISOString() : null;
+
+    const confidence = call.request.confidence ?? 1;
+    if (!isConfidence(confidence)) {
+      const message = 'Invalid ingest request: confidence must be between 0 and 1.';
+      console.error(`❌ Ingest failed: ${message}`);
+      callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message), {
+        success: false,
+        chunks_stored: 0,
+        chunks_skipped: 0,
+        connections_seeded: 0,
+        error: message,
+      });
+      return;
+    }
+
+    const claimed: string[] = [];
+
     try {
-      const chunks = await semanticChunkText(text);
+      const chunks = (await semanticChunkText(text)).map(chunk => ({ ...chunk, text: limitChunkContent(chunk.text) }));
 
-      let stored = 0;
       let skipped = 0;
-      let seededConnections = 0;
+      type PendingChunk = { chunkId: string; text: string; page: number; vector: number[]; similarIds: string[]; novelty: number | null };
+      const pending: PendingChunk[] = [];
 
       for (const chunk of chunks) {
-        const chunk_id = uuidv4();
+        // Identical content already being stored by a concurrent ingest is a duplicate
+        const claim = claimContent(chunk.text, database);
+        if (!claim) {
+          skipped++;
+          continue;
+        }
+        claimed.push(claim);
+
         const vector = await embed(chunk.text);
-        const similarExistingChunkIds = await findSimilarExistingChunks(vector, 5);
+        const similarIds = await findSimilarExistingChunks(vector, database, 5);
 
-        const isDuplicate = await checkDuplicate(vector, DUPLICATE_THRESHOLD);
-        if (isDuplicate) {
+        const topScore = await topSimilarityScore(vector, database);
+        if (topScore !== null && topScore >= DUPLICATE_THRESHOLD) {
           skipped++;
           continue;
         }
+        const novelty = topScore === null ? null : noveltyScore(topScore);
+        pending.push({ chunkId: uuidv4(), text: chunk.text, page: chunk.index, vector, similarIds, novelty });
+      }
 
+      let seededConnections = 0;
+      if (pending.length > 0) {
+        // Rejects early; the count and any eviction run after the version check at commit
+        planChunkCapacity(pending.length, database);
         const timestamp = new Date().toISOString();
 
-        await qdrant.upsert(COLLECTION, {
-          points: [{
-            id: chunk_id,
-            vector,
-            payload: { text: chunk.text, source, chunk_id },
-          }],
-        });
-
-        db.prepare(`
-          INSERT INTO chunks (chunk_id, text, source, page, timestamp, tags)
-          VALUES (?, ?, ?, ?, ?, ?)
-        `).run(chunk_id, chunk.text, source, chunk.index, timestamp, JSON.stringify(tags));
-
-        seededConnections += seedConnections(chunk_id, similarExistingChunkIds, timestamp);
-        stored++;
+        await withStoreRetry(() => qdrant.upsert(COLLECTION, {
+          points: pending.map(item => ({
+            id: item.chunkId,
+            vector: item.vector,
+            payload: { text: item.text, source, chunk_id: item.chunkId, database_id: database },
+          })),
+        }));
+
+        const insertChunk = db.prepare(`
+          INSERT INTO chunks (chunk_id, text, source, page, timestamp, tags, expires_at, confidence, novelty, database_id)
+          VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        `);
+        try {
+          // The version check, any eviction and every row this ingest writes commit together
+          const written = await writeWithinCapacity(pending.length, database, (evicted) => {
+            let seeded = 0;
+            for (const item of pending) {
+              insertChunk.run(item.chunkId, item.text, source, item.page, timestamp, JSON.stringify(tags), expiresAt, confidence, item.novelty, database);
+              // The similarity search ran before the eviction, so it may name chunks just evicted
+              seeded += seedConnections(item.chunkId, item.similarIds.filter(id => !evicted.has(id)), timestamp, database);
+            }
+            return seeded;
+          }, { expectedVersion });
+          seededConnections = written.result;
+        } catch (error) {
+          // Nothing reached SQLite, so the points just upserted are orphans
+          try {
+            await withStoreRetry(() => qdrant.delete(COLLECTION, { wait: true, points: pending.map(item => item.chunkId) }));
+          } catch (cleanupError) {
+            const message = cleanupError instanceof Error ? cleanupError.message : String(cleanupError);
+            console.warn(`⚠️  Qdrant cleanup failed for ${pending.length} chunks: ${message}`);
+          }
+          throw error;
+        }
       }
+      const stored = pending.length;
+      const measured = pending.filter(item => item.novelty !== null).map(item => item.novelty as number);
 
       const response: IngestResponse = {
         success: true,
@@ -165,6 +247,7 @@ export const ingestHandler: grpc.handleUnaryCall<IngestRequest, IngestResponse>
         chunks_skipped: skipped,
         connections_seeded: seededConnections,
         error: '',
+        ...(measured.length > 0 ? { novelty: measured.reduce((sum, value) => sum + value, 0) / measured.length } : {}),
       };
 
       console.log(`✅ Ingest response stored=${stored} skipped=${skipped} seeded=${seededConnections}`);
@@ -172,13 +255,19 @@ export const ingestHandler: grpc.handleUnaryCall<IngestRequest, IngestResponse>
     } catch (error) {
       const message = error instanceof Error ? error.message : 'Unknown ingest error';
       console.error('❌ Ingest failed:', error);
-      callback(makeGrpcError(grpc.status.INTERNAL, message), {
+      const code = error instanceof CapacityExceededError
+        ? grpc.status.RESOURCE_EXHAUSTED
+        : error instanceof ContentTooLargeError ? grpc.status.INVALID_ARGUMENT
+          : error instanceof GraphVersionConflictError ? grpc.status.ABORTED : grpc.status.INTERNAL;
+      callback(makeGrpcError(code, message), {
         success: false,
         chunks_stored: 0,
         chunks_skipped: 0,
         connections_seeded: 0,
         error: message,
       });
+    } finally {
+      releaseContent(claimed);
     }
   })();
 };
@@ -196,6 +285,12 @@ export const queryHandler: grpc.handleUnaryCall<QueryRequest, QueryResponse> = (
     const includeConflicts = typeof call.request.include_conflicts === 'boolean'
       ? call.request.include_conflicts
       : true;
+    const minContentLength = typeof call.request.min_content_length === 'number' && call.request.min_content_length > 0
+      ? call.request.min_content_length
+      : undefined;
+    const hopDecay = typeof call.request.hop_decay === 'number' && call.request.hop_decay !== 0
+      ? call.request.hop_decay
+      : undefined;
 
     console.log(`➡️  Query request query=${query}`);
 
@@ -206,15 +301,90 @@ export const queryHandler: grpc.handleUnaryCall<QueryRequest, QueryResponse> = (
       return;
     }
 
+    if (hopDecay !== undefined && !(hopDecay > 0 && hopDecay < 1)) {
+      const message = 'Invalid query request: hop_decay must be between 0 and 1 (exclusive).';
+      console.error(`❌ Query failed: ${message}`);
+      callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+      return;
+    }
+
+    const exactDepth = typeof call.request.exact_depth === 'number' && call.request.exact_depth > 0
+      ? call.request.exact_depth
+      : undefined;
+    const limitError = checkQueryLimits({ topK, maxHops, exactDepth });
+    if (limitError) {
+      const message = `Invalid query request: ${limitError}.`;
+      console.error(`❌ Query failed: ${message}`);
+      callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+      return;
+    }
+
+    const pathAccumulation = call.request.path_accumulation?.trim() || undefined;
+    if (pathAccumulation !== undefined && !isPathAccumulation(pathAccumulation)) {
+      const message = `Invalid query request: path_accumulation must be one of ${PATH_ACCUMULATION_MODES.join(', ')}.`;
+      console.error(`❌ Query failed: ${message}`);
+      callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+      return;
+    }
+
+    const scoreCombine = call.request.score_combine?.trim() || undefined;
+    if (scoreCombine !== undefined && !isScoreCombine(scoreCombine)) {
+      const message = `Invalid query request: score_combine must be one of ${SCORE_COMBINE_MODES.join(', ')}.`;
+      console.error(`❌ Query failed: ${message}`);
+      callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+      return;
+    }
+
+    const diversity = typeof call.request.diversity === 'number' && call.request.diversity !== 0
+      ? call.request.diversity
+      : undefined;
+    if (diversity !== undefined && !(diversity > 0 && diversity <= 1)) {
+      const message = 'Invalid query request: diversity must be between 0 and 1.';
+      console.error(`❌ Query failed: ${message}`);
+      callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+      return;
+    }
+
+    const relevanceMass = typeof call.request.relevance_mass === 'number' && call.request.relevance_mass !== 0
+      ? call.request.relevance_mass
+      : undefined;
+    if (relevanceMass !== undefined && !(relevanceMass > 0 && relevanceMass <= 1)) {
+      const message = 'Invalid query request: relevance_mass must be in (0, 1].';
+      console.error(`❌ Query failed: ${message}`);
+      callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+      return;
+    }
+
+    const confidenceMapping = call.request.confidence_mapping?.trim() || undefined;
+    if (confidenceMapping !== undefined && !isConfidenceMapping(confidenceMapping)) {
+      const message = `Invalid query request: confidence_mapping must be one of ${CONFIDENCE_MAPPINGS.join(', ')}.`;
+      console.error(`❌ Query failed: ${message}`);
+      callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+      return;
+    }
+
     try {
       const results = await retrieve(query, {
         topK,
         maxHops,
         relationshipFilter,
         includeConflicts,
+        minContentLength,
+        useCache: call.request.use_cache === true,
+        hopDecay,
+        degreeBoost: typeof call.request.degree_boost === 'number' && call.request.degree_boost !== 0 ? call.request.degree_boost : undefined,
+        exactDepth,
+        recordAccess: call.request.skip_access_tracking !== true,
+        scoreCombine,
+        minEdgeAccessCount: call.request.min_edge_access_count || undefined,
+        pathAccumulation,
+        diversity,
+        relevanceMass,
+        useConfidence: call.request.use_confidence === true,
+        confidenceMapping,
       });
       console.log(`✅ Query response results=${results.length}`);
-      callback(null, { results });
+      callback(null, { results: results.map(toGrpcResult) });
     } catch (error) {
       const message = error instanceof Error ? error.message : 'Unknown query error';
       console.error('❌ Query failed:', error);
@@ -238,6 +408,7 @@ export const healthHandler: grpc.handleUnaryCall<Record<string, never>, HealthRe
         total_connections: connectionsRow.total,
         collections: collections.collections.length,
         service_version: '2.0.0',
+        graph_version: getGraphVersion(DEFAULT_MEMORY_DB),
       };
 
       console.log(`✅ Health response chunks=${response.total_chunks} connections=${response.total_connections} collections=${response.collections}`);
@@ -249,3 +420,265 @@ export const healthHandler: grpc.handleUnaryCall<Record<string, never>, HealthRe
     }
   })();
 };
+
+export const getChunksHandler: grpc.handleUnaryCall<GetChunksRequest, GetChunksResponse> = (call, callback) => {
+  const chunkIds = Array.isArray(call.request.chunk_ids)
+    ? call.request.chunk_ids.map(id => id.trim())
+    : [];
+  const database = call.request.database?.trim() || DEFAULT_MEMORY_DB;
+
+  console.log(`➡️  GetChunks request ids=${chunkIds.length} database=${database}`);
+
+  try {
+    const rows = getChunksByIds(chunkIds, database);
+    const chunks = rows.map((row, index) => row
+      ? {
+        found: true,
+        chunk_id: row.chunk_id,
+        text: row.text,
+        source: row.source,
+        access_count: row.access_count ?? 0,
+        timestamp: row.timestamp,
+        confidence: row.confidence,
+      }
+      : {
+        found: false,
+        chunk_id: chunkIds[index],
+        text: '',
+        source: '',
+        access_count: 0,
+        timestamp: '',
+        confidence: 0,
+      });
+
+    console.log(`✅ GetChunks response found=${chunks.filter(c => c.found).length}/${chunks.length}`);
+    callback(null, { chunks });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : 'Unknown get chunks error';
+    console.error('❌ GetChunks failed:', error);
+    callback(makeGrpcError(grpc.status.INTERNAL, message));
+  }
+};
+
+export const exportGraphHandler: grpc.handleServerStreamingCall<ExportGraphRequest, GraphRecord> = (call) => {
+  void (async () => {
+    const database = call.request.database?.trim() || DEFAULT_MEMORY_DB;
+    const pageSize = Number(call.request.page_size) || undefined;
+    const requestedPrecision = call.request.weight_precision?.trim() || undefined;
+
+    console.log(`➡️  ExportGraph request database=${database}`);
+
+    if (requestedPrecision && requestedPrecision !== 'f64' && requestedPrecision !== 'u16') {
+      const message = `Invalid export request: weight_precision must be f64 or u16, got "${requestedPrecision}".`;
+      console.error(`❌ ExportGraph failed: ${message}`);
+      call.emit('error', makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+      return;
+    }
+
+    try {
+      let chunks = 0;
+      let connections = 0;
+      for (const record of iterateGraphExport(database, pageSize, requestedPrecision as WeightPrecision | undefined)) {
+        if (call.cancelled) return;
+        if (record.chunk) chunks++;
+        if (record.connection) connections++;
+        if (!call.write(record)) {
+          await new Promise<void>(resolve => call.once('drain', resolve));
+        }
+      }
+
+      console.log(`✅ ExportGraph response chunks=${chunks} connections=${connections}`);
+      call.end();
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown export error';
+      console.error('❌ ExportGraph failed:', error);
+      call.emit('error', makeGrpcError(grpc.status.INTERNAL, message));
+    }
+  })();
+};
+
+export const importGraphHandler: grpc.handleClientStreamingCall<GraphRecord, ImportGraphResult> = (call, callback) => {
+  void (async () => {
+    console.log('➡️  ImportGraph request');
+
+    try {
+      const result = await importGraphRecords(call as AsyncIterable<GraphRecord>, undefined, {
+        onProgress: processed => console.log(`   ImportGraph progress records=${processed}`),
+      });
+      console.log(
+        `✅ ImportGraph response chunks=${result.chunks_imported} (+${result.chunks_skipped} skipped) ` +
+        `connections=${result.connections_imported} (+${result.connections_skipped} skipped)`
+      );
+      callback(null, result);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown import error';
+      console.error('❌ ImportGraph failed:', error);
+      const code = error instanceof InvalidGraphRecordError ? grpc.status.INVALID_ARGUMENT : grpc.status.INTERNAL;
+      callback(makeGrpcError(code, message));
+    }
+  })();
+};
+
+export const getChunkReportHandler: grpc.handleUnaryCall<GetChunkReportRequest, ChunkReport> = (call, callback) => {
+  const chunkId = call.request.chunk_id?.trim() ?? '';
+  const database = call.request.database?.trim() || DEFAULT_MEMORY_DB;
+
+  console.log(`➡️  GetChunkReport request chunk=${chunkId} database=${database}`);
+
+  if (!chunkId) {
+    const message = 'Invalid report request: chunk_id is required.';
+    console.error(`❌ GetChunkReport failed: ${message}`);
+    callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+    return;
+  }
+
+  try {
+    const report = getChunkReport(chunkId, database);
+    if (!report) {
+      callback(makeGrpcError(grpc.status.NOT_FOUND, `Chunk not found: ${chunkId}`));
+      return;
+    }
+
+    console.log(`✅ GetChunkReport response in=${report.in_degree} out=${report.out_degree} consolidated=${report.consolidated}`);
+    callback(null, {
+      ...report,
+      last_accessed: report.last_accessed ?? '',
+      strongest_neighbor: report.strongest_neighbor ?? '',
+    } as ChunkReport);
+  } catch (error) {
+    const message = error instanceof Error ? error.message : 'Unknown report error';
+    console.error('❌ GetChunkReport failed:', error);
+    callback(makeGrpcError(grpc.status.INTERNAL, message));
+  }
+};
+
+export const listConnectionsHandler: grpc.handleServerStreamingCall<ListConnectionsRequest, ConnectionRow> = (call) => {
+  void (async () => {
+    const database = call.request.database?.trim() || DEFAULT_MEMORY_DB;
+    const minWeight = typeof call.request.min_weight === 'number' && call.request.min_weight > 0
+      ? call.request.min_weight
+      : undefined;
+    const relationships = Array.isArray(call.request.relationships) ? call.request.relationships : [];
+
+    console.log(`➡️  ListConnections request database=${database} min_weight=${minWeight ?? 0}`);
+
+    try {
+      let streamed = 0;
+      for (const connection of iterateConnections({ database, minWeight, relationships })) {
+        if (call.cancelled) return;
+        streamed++;
+        if (!call.write(connection)) {
+          await new Promise<void>(resolve => call.once('drain', resolve));
+        }
+      }
+
+      console.log(`✅ ListConnections response streamed=${streamed}`);
+      call.end();
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown list connections error';
+      console.error('❌ ListConnections failed:', error);
+      call.emit('error', makeGrpcError(grpc.status.INTERNAL, message));
+    }
+  })();
+};
+
+export const listUnconsolidatedHandler: grpc.handleUnaryCall<ListUnconsolidatedRequest, ListUnconsolidatedResponse> = (call, callback) => {
+  const database = call.request.database?.trim() || DEFAULT_MEMORY_DB;
+  const limit = call.request.limit && call.request.limit > 0 ? call.request.limit : 500;
+
+  console.log(`➡️  ListUnconsolidated request database=${database} limit=${limit}`);
+
+  try {
+    const chunkIds = listUnconsolidatedChunks(database, limit);
+    console.log(`✅ ListUnconsolidated response chunks=${chunkIds.length}`);
+    callback(null, { chunk_ids: chunkIds });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : 'Unknown list unconsolidated error';
+    console.error('❌ ListUnconsolidated failed:', error);
+    callback(makeGrpcError(grpc.status.INTERNAL, message));
+  }
+};
+
+export const deleteChunksHandler: grpc.handleUnaryCall<DeleteChunksRequest, DeleteChunksResponse> = (call, callback) => {
+  void (async () => {
+    const chunkIds = Array.isArray(call.request.chunk_ids)
+      ? call.request.chunk_ids.map(id => id.trim()).filter(Boolean)
+      : [];
+    const database = call.request.database?.trim() || DEFAULT_MEMORY_DB;
+
+    console.log(`➡️  DeleteChunks request ids=${chunkIds.length} database=${database}`);
+
+    if (chunkIds.length === 0) {
+      const message = 'Invalid delete request: chunk_ids is required.';
+      console.error(`❌ DeleteChunks failed: ${message}`);
+      callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+      return;
+    }
+
+    try {
+      const response = await deleteChunks(chunkIds, database);
+      console.log(`✅ DeleteChunks response deleted=${response.deleted_count} requested=${chunkIds.length}`);
+      callback(null, response);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown delete error';
+      console.error('❌ DeleteChunks failed:', error);
+      callback(makeGrpcError(grpc.status.INTERNAL, message));
+    }
+  })();
+};
+
+export const listRecentHandler: grpc.handleUnaryCall<ListRecentRequest, ListRecentResponse> = (call, callback) => {
+  const database = call.request.database?.trim() || DEFAULT_MEMORY_DB;
+  const limit = call.request.limit && call.request.limit > 0 ? call.request.limit : 50;
+  const since = call.request.since?.trim() || undefined;
+
+  console.log(`➡️  ListRecent request database=${database} limit=${limit} since=${since ?? '-'}`);
+
+  if (since && !Number.isFinite(Date.parse(since))) {
+    const message = `Invalid list recent request: since is not a timestamp: ${since}`;
+    console.error(`❌ ListRecent failed: ${message}`);
+    callback(makeGrpcError(grpc.status.INVALID_ARGUMENT, message));
+    return;
+  }
+
+  try {
+    const chunks = listRecentChunks(limit, since ? new Date(since).toISOString() : undefined, database);
+    console.log(`✅ ListRecent response chunks=${chunks.length}`);
+    callback(null, {
+      chunks: chunks.map(chunk => ({
+        found: true,
+        chunk_id: chunk.chunk_id,
+        text: chunk.text,
+        source: chunk.source,
+        access_count: chunk.access_count ?? 0,
+        timestamp: chunk.timestamp,
+      })),
+    });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : 'Unknown list recent error';
+    console.error('❌ ListRecent failed:', error);
+    callback(makeGrpcError(grpc.status.INTERNAL, message));
+  }
+};
+
+export const getGraphSummaryHandler: grpc.handleUnaryCall<GetGraphSummaryRequest, GraphSummaryResponse> = (call, callback) => {
+  const database = call.request.database?.trim() || DEFAULT_MEMORY_DB;
+
+  console.log(`➡️  GetGraphSummary request database=${database}`);
+
+  try {
+    const { weight_histogram, edge_age_distribution, ...summary } = getGraphSummary(database);
+    console.log(`✅ GetGraphSummary response chunks=${summary.total_chunks} connections=${summary.total_connections} concepts=${summary.total_concepts}`);
+    callback(null, {
+      ...summary,
+      weight_histogram_bounds: weight_histogram.bounds,
+      weight_histogram_counts: weight_histogram.counts,
+      edge_age_buckets_ms: edge_age_distribution.buckets_ms,
+      edge_age_counts: edge_age_distribution.counts,
+    });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : 'Unknown graph summary error';
+    console.error('❌ GetGraphSummary failed:', error);
+    callback(makeGrpcError(grpc.status.INTERNAL, message));
+  }
+};
@@ -5,6 +5,16 @@ import fs from 'fs';
 import { IncomingMessage, ServerResponse } from 'http';
 import { v4 as uuidv4 } from 'uuid';
 import Busboy from 'busboy';
+import type { ChannelOptions } from '@grpc/grpc-js';
+import type { Result } from '../retrieve';
+import {
+  MAX_QUERY_HOPS,
+  MAX_QUERY_TOP_K,
+  GRPC_KEEPALIVE_TIME_MS,
+  GRPC_KEEPALIVE_TIMEOUT_MS,
+  GRPC_MAX_CONNECTION_IDLE_MS,
+  GRPC_MAX_CONCURRENT_STREAMS,
+} from '../config';
 
 // ── Types ──────────────────────────────────────────────────────────────────
 
@@ -19,6 +29,10 @@ export type IngestRequest = {
   source?: string;
   text?: string;
   tags?: string[];
+  expected_version?: number | string;
+  ttl_ms?: number | string;
+  confidence?: number;
+  database?: string;
 };
 
 export type IngestResponse = {
@@ -27,6 +41,7 @@ export type IngestResponse = {
   chunks_skipped: number;
   connections_seeded: number;
   error: string;
+  novelty?: number;   // mean novelty of the stored chunks; unset when none was measured
 };
 
 export type IngestJobResponse = {
@@ -45,6 +60,19 @@ export type QueryRequest = {
   max_hops?: number;
   relationship_filter?: string[];
   include_conflicts?: boolean;
+  min_content_length?: number;
+  use_cache?: boolean;
+  hop_decay?: number;
+  degree_boost?: number;
+  exact_depth?: number;
+  skip_access_tracking?: boolean;
+  score_combine?: string;
+  min_edge_access_count?: number;
+  path_accumulation?: string;
+  diversity?: number;
+  relevance_mass?: number;
+  use_confidence?: boolean;
+  confidence_mapping?: string;
 };
 
 export type QueryResponse = {
@@ -56,6 +84,9 @@ export type QueryResponse = {
     graph_boosted: boolean;
     path: string[];
     conflicts: string[];
+    confidence: number;
+    retrieval_layer: string;
+    rerank_score: number;
   }>;
 };
 
@@ -65,6 +96,95 @@ export type HealthResponse = {
   total_connections: number;
   collections: number;
   service_version: string;
+  graph_version: number;
+};
+
+export type ExportGraphRequest = {
+  database?: string;
+  page_size?: number;
+  weight_precision?: string;
+};
+
+export type GetChunkReportRequest = {
+  chunk_id?: string;
+  database?: string;
+};
+
+export type ListConnectionsRequest = {
+  database?: string;
+  min_weight?: number;
+  relationships?: string[];
+};
+
+export type ListUnconsolidatedRequest = {
+  database?: string;
+  limit?: number;
+};
+
+export type ListUnconsolidatedResponse = {
+  chunk_ids: string[];
+};
+
+export type DeleteChunksRequest = {
+  chunk_ids?: string[];
+  database?: string;
+};
+
+export type DeleteChunksResponse = {
+  results: Array<{ chunk_id: string; deleted: boolean }>;
+  deleted_count: number;
+};
+
+export type ListRecentRequest = {
+  database?: string;
+  limit?: number;
+  since?: string;
+};
+
+export type ListRecentResponse = {
+  chunks: ChunkEntry[];
+};
+
+export type GetGraphSummaryRequest = {
+  database?: string;
+};
+
+export type GraphSummaryResponse = {
+  database: string;
+  graph_version: number;
+  total_chunks: number;
+  total_connections: number;
+  total_concepts: number;
+  frozen_connections: number;
+  average_weight: number;
+  consolidated_chunks: number;
+  consolidation_ratio: number;
+  relationships: Array<{ relationship: string; count: number; average_weight: number }>;
+  top_chunks: Array<{ chunk_id: string; degree: number }>;
+  weight_histogram_bounds: number[];
+  weight_histogram_counts: number[];
+  edge_age_buckets_ms: number[];
+  edge_age_counts: number[];
+  storage_bytes: number;
+};
+
+export type GetChunksRequest = {
+  chunk_ids?: string[];
+  database?: string;
+};
+
+export type ChunkEntry = {
+  found: boolean;
+  chunk_id: string;
+  text: string;
+  source: string;
+  access_count: number;
+  timestamp: string;
+  confidence: number;
+};
+
+export type GetChunksResponse = {
+  chunks: ChunkEntry[];
 };
 
 export type RelationshipCounts = {
@@ -82,6 +202,24 @@ export const DEFAULT_PORT = '50051';
 export const DEFAULT_HTTP_PORT = '3001';
 export const DUPLICATE_THRESHOLD = 0.97;
 
+/**
+ * Transport options for the gRPC server from the GRPC_* settings. Zero or
+ * invalid idle and stream limits are left out so grpc-js keeps its default.
+ */
+export function grpcServerOptions(): ChannelOptions {
+  const options: ChannelOptions = {
+    'grpc.keepalive_time_ms': GRPC_KEEPALIVE_TIME_MS,
+    'grpc.keepalive_timeout_ms': GRPC_KEEPALIVE_TIMEOUT_MS,
+  };
+  if (Number.isFinite(GRPC_MAX_CONNECTION_IDLE_MS) && GRPC_MAX_CONNECTION_IDLE_MS > 0) {
+    options['grpc.max_connection_idle_ms'] = GRPC_MAX_CONNECTION_IDLE_MS;
+  }
+  if (Number.isFinite(GRPC_MAX_CONCURRENT_STREAMS) && GRPC_MAX_CONCURRENT_STREAMS > 0) {
+    options['grpc.max_concurrent_streams'] = Math.floor(GRPC_MAX_CONCURRENT_STREAMS);
+  }
+  return options;
+}
+
 export const BASE_CORS_HEADERS: Record<string, string> = {
   'Access-Control-Allow-Origin': '*',
   'Access-Control-Allow-Methods': 'GET, POST, OPTIONS',
@@ -90,6 +228,25 @@ export const BASE_CORS_HEADERS: Record<string, string> = {
 
 // ── Utility functions ──────────────────────────────────────────────────────
 
+/**
+ * Wire form of a recall result. Every field of `Result` is mapped explicitly;
+ * optional scores become 0 because proto3 scalars have no "unset".
+ */
+export function toGrpcResult(result: Result): QueryResponse['results'][number] {
+  return {
+    text: result.text,
+    source: result.source,
+    score: result.score,
+    chunk_id: result.chunk_id,
+    graph_boosted: result.graph_boosted,
+    path: result.path,
+    conflicts: result.conflicts,
+    confidence: result.confidence ?? 0,
+    retrieval_layer: result.retrieval_layer,
+    rerank_score: result.rerankScore ?? 0,
+  };
+}
+
 export function setCorsHeaders(res: ServerResponse): void {
   for (const [key, value] of Object.entries(BASE_CORS_HEADERS)) {
     res.setHeader(key, value);
@@ -107,6 +264,27 @@ export function clampNumber(value: number, min: number, max: number): number {
   return Math.min(max, Math.max(min, value));
 }
 
+/**
+ * Server-side guard on client-controlled traversal size: returns an error
+ * message when the requested depth or result count exceeds the configured
+ * ceiling, or null when the query may run.
+ */
+export function checkQueryLimits(
+  requested: { topK?: number; maxHops?: number; exactDepth?: number },
+  limits: { maxTopK: number; maxHops: number } = { maxTopK: MAX_QUERY_TOP_K, maxHops: MAX_QUERY_HOPS },
+): string | null {
+  if (typeof requested.topK === 'number' && requested.topK > limits.maxTopK) {
+    return `top_k must be at most ${limits.maxTopK}`;
+  }
+  if (typeof requested.maxHops === 'number' && requested.maxHops > limits.maxHops) {
+    return `max_hops must be at most ${limits.maxHops}`;
+  }
+  if (typeof requested.exactDepth === 'number' && requested.exactDepth > limits.maxHops) {
+    return `exact_depth must be at most ${limits.maxHops}`;
+  }
+  return null;
+}
+
 export function parseBody(req: IncomingMessage): Promise<unknown> {
   return new Promise((resolve, reject) => {
     let raw = '';
@@ -6,12 +6,18 @@ import { handleQueryRoutes } from './routes/queryRoute';
 import { handleIngestRoutes } from './routes/ingestRoute';
 import { handleDbRoutes } from './routes/dbRoute';
 import { handleOverviewRoutes } from './routes/overviewRoute';
+import { handleConnectionRoutes } from './routes/connectionRoute';
+import { handleGraphRoutes } from './routes/graphRoute';
+import { resolveRequestId, runWithRequestId, REQUEST_ID_HEADER } from './requestContext';
 
-export function startHttpServer(): void {
+export function startHttpServer(): http.Server {
   const httpPort = process.env.HTTP_PORT || DEFAULT_HTTP_PORT;
 
   const httpServer = http.createServer((req, res) => {
-    void (async () => {
+    const requestId = resolveRequestId(req.headers[REQUEST_ID_HEADER]);
+    res.setHeader('X-Request-Id', requestId);
+
+    void runWithRequestId(requestId, async () => {
       try {
         const method = req.method ?? 'GET';
         const url = new URL(req.url ?? '/', `http://${req.headers.host ?? 'localhost'}`);
@@ -31,7 +37,9 @@ export function startHttpServer(): void {
           await handleHealthRoutes(req, res, url, method) ||
           await handleQueryRoutes(req, res, url, method) ||
           await handleIngestRoutes(req, res, url, method) ||
-          await handleOverviewRoutes(req, res, url, method);
+          await handleOverviewRoutes(req, res, url, method) ||
+          await handleConnectionRoutes(req, res, url, method) ||
+          await handleGraphRoutes(req, res, url, method);
 
         if (!handled) {
           sendJson(res, 404, { error: 'Not Found' });
@@ -40,10 +48,12 @@ export function startHttpServer(): void {
         const message = error instanceof Error ? error.message : 'Unknown API error';
         sendJson(res, 500, { error: message });
       }
-    })();
+    });
   });
 
   httpServer.listen(Number(httpPort), HOST, () => {
     console.log(`🌐 HTTP API listening on http://${HOST}:${httpPort}`);
   });
+
+  return httpServer;
 }
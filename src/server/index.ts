@@ -1,13 +1,24 @@
 // src/server/index.ts — Server startup orchestration
 import path from 'path';
+import http from 'http';
 import * as grpc from '@grpc/grpc-js';
 import * as protoLoader from '@grpc/proto-loader';
 import { initDB, ensureDefaultMemoryDatabase, db, DEFAULT_MEMORY_DB } from '../db';
 import { runConsolidationWorker } from '../consolidate';
 import { getAssociativeStatus } from '../associative';
 import { startHttpServer } from './httpServer';
-import { ingestHandler, queryHandler, healthHandler } from './grpc';
-import { HOST, DEFAULT_PORT } from './helpers';
+import { stopHttpServer, stopGrpcServer } from './shutdown';
+import { flushChunkAccess } from '../retrieve/accessLog';
+import {
+  ingestHandler, queryHandler, healthHandler, getChunksHandler,
+  exportGraphHandler, importGraphHandler, getChunkReportHandler,
+  listConnectionsHandler, listUnconsolidatedHandler, deleteChunksHandler, listRecentHandler,
+  getGraphSummaryHandler,
+} from './grpc';
+import { HOST, DEFAULT_PORT, grpcServerOptions } from './helpers';
+import { installRequestIdLogging, withRequestIds } from './requestContext';
+import { withUnaryTimeout } from './timeout';
+import { GRPC_UNARY_TIMEOUT_MS } from '../config';
 
 // When running as a pkg binary, __dirname points into the virtual snapshot and
 // the .proto file must be loaded from the real filesystem next to the binary.
@@ -15,7 +26,35 @@ const PROTO_PATH = (process as any).pkg
   ? path.join(path.dirname(process.execPath), 'hippocampus.proto')
   : path.join(__dirname, '..', 'proto', 'hippocampus.proto');
 
+// First signal drains both servers and flushes debounced access counts; a
+// second one exits immediately.
+function installShutdownHandlers(httpServer: http.Server, grpcServer: grpc.Server): void {
+  let shuttingDown = false;
+
+  const shutdown = async (signal: NodeJS.Signals) => {
+    if (shuttingDown) {
+      process.exit(1);
+    }
+    shuttingDown = true;
+    console.log(`🛑 ${signal} received, shutting down`);
+
+    try {
+      await Promise.all([stopHttpServer(httpServer), stopGrpcServer(grpcServer)]);
+      flushChunkAccess();
+      console.log('🧠 Hippocampus stopped');
+      process.exit(0);
+    } catch (error) {
+      console.error('❌ Shutdown failed:', error);
+      process.exit(1);
+    }
+  };
+
+  process.on('SIGINT', shutdown);
+  process.on('SIGTERM', shutdown);
+}
+
 async function startServer() {
+  installRequestIdLogging();
   await initDB();
   ensureDefaultMemoryDatabase();
 
@@ -33,7 +72,7 @@ async function startServer() {
   console.log(`   Associative MLP:   ✅ (trained on ${associative.trainedSamples} samples, influence: ${(associative.influence * 100).toFixed(1)}%)`);
 
   runConsolidationWorker(30000);
-  startHttpServer();
+  const httpServer = startHttpServer();
 
   const packageDef = protoLoader.loadSync(PROTO_PATH, {
     keepCase: true,
@@ -46,12 +85,26 @@ async function startServer() {
   const proto = grpc.loadPackageDefinition(packageDef) as any;
   const hippocampusService = proto.hippocampus.Hippocampus.service;
 
-  const grpcServer = new grpc.Server();
-  grpcServer.addService(hippocampusService, {
+  const grpcServer = new grpc.Server(grpcServerOptions());
+  // Ingest is exempt from the unary timeout: large files legitimately take minutes.
+  // DeleteChunks is too: the delete keeps running after a timeout and may still
+  // commit, so DEADLINE_EXCEEDED would misreport it.
+  grpcServer.addService(hippocampusService, withRequestIds({
     Ingest: ingestHandler,
-    Query: queryHandler,
-    Health: healthHandler,
-  } as any);
+    DeleteChunks: deleteChunksHandler,
+    ...withUnaryTimeout({
+      Query: queryHandler,
+      Health: healthHandler,
+      GetChunks: getChunksHandler,
+      GetChunkReport: getChunkReportHandler,
+      ListUnconsolidated: listUnconsolidatedHandler,
+      ListRecent: listRecentHandler,
+      GetGraphSummary: getGraphSummaryHandler,
+    }, GRPC_UNARY_TIMEOUT_MS),
+    ExportGraph: exportGraphHandler,
+    ImportGraph: importGraphHandler,
+    ListConnections: listConnectionsHandler,
+  }) as any);
 
   const port = process.env.GRPC_PORT || DEFAULT_PORT;
   const bindAddress = `${HOST}:${port}`;
@@ -65,6 +118,8 @@ async function startServer() {
     grpcServer.start();
     console.log(`🧠 Hippocampus gRPC server listening on ${bindAddress}`);
   });
+
+  installShutdownHandlers(httpServer, grpcServer);
 }
 
 startServer().catch((error) => {
@@ -0,0 +1,80 @@
+// src/server/requestContext.ts — Per-request correlation ids for log tracing
+//
+// Every HTTP request and gRPC call runs inside an AsyncLocalStorage context
+// holding its correlation id: the caller's `x-request-id` when it sent a sane
+// one, a fresh uuid otherwise. The id follows the work across awaits, so
+// logs written deep inside retrieve/ingest (or by an ingest job that outlives
+// the HTTP response) carry the id of the request that started them.
+import { AsyncLocalStorage } from 'async_hooks';
+import { randomUUID } from 'crypto';
+import * as grpc from '@grpc/grpc-js';
+
+export const REQUEST_ID_HEADER = 'x-request-id';
+
+type RequestContext = { requestId: string };
+
+const storage = new AsyncLocalStorage<RequestContext>();
+const REQUEST_ID_PATTERN = /^[A-Za-z0-9._:-]{1,128}$/;
+
+/** Use a caller-supplied id when it is a plain token, otherwise mint one. */
+export function resolveRequestId(candidate: unknown): string {
+  const value = Array.isArray(candidate) ? candidate[0] : candidate;
+  const text = typeof value === 'string' ? value.trim() : Buffer.isBuffer(value) ? value.toString('utf8').trim() : '';
+  return REQUEST_ID_PATTERN.test(text) ? text : randomUUID();
+}
+
+export function runWithRequestId<T>(requestId: string, task: () => T): T {
+  return storage.run({ requestId }, task);
+}
+
+export function currentRequestId(): string | undefined {
+  return storage.getStore()?.requestId;
+}
+
+let loggingInstalled = false;
+
+/**
+ * Prefix console output with `[req <id>]` whenever it is written inside a
+ * request context. Call once at startup; output outside a request (worker
+ * ticks, startup banners) is left as is.
+ */
+export function installRequestIdLogging(): void {
+  if (loggingInstalled) return;
+  loggingInstalled = true;
+
+  for (const method of ['log', 'info', 'warn', 'error'] as const) {
+    const original = console[method].bind(console);
+    console[method] = (...args: unknown[]) => {
+      const requestId = currentRequestId();
+      if (requestId) {
+        original(`[req ${requestId}]`, ...args);
+      } else {
+        original(...args);
+      }
+    };
+  }
+}
+
+type AnyCall = {
+  metadata: grpc.Metadata;
+  sendMetadata: (metadata: grpc.Metadata) => void;
+};
+
+/**
+ * Wrap every handler of a gRPC service implementation so each call runs under
+ * its correlation id, taken from the `x-request-id` metadata entry. The id is
+ * echoed back to the client in the response's initial metadata.
+ */
+export function withRequestIds<T extends Record<string, (call: any, ...rest: any[]) => void>>(handlers: T): T {
+  const wrapped: Record<string, (call: any, ...rest: any[]) => void> = {};
+  for (const [name, handler] of Object.entries(handlers)) {
+    wrapped[name] = (call: AnyCall, ...rest: unknown[]) => {
+      const requestId = resolveRequestId(call.metadata?.get(REQUEST_ID_HEADER));
+      const metadata = new grpc.Metadata();
+      metadata.set(REQUEST_ID_HEADER, requestId);
+      call.sendMetadata(metadata);
+      runWithRequestId(requestId, () => handler(call, ...rest));
+    };
+  }
+  return wrapped as T;
+}
@@ -0,0 +1,176 @@
+// src/server/routes/connectionRoute.ts — Connection (edge) inspection and tuning routes
+import { IncomingMessage, ServerResponse } from 'http';
+import { DEFAULT_MEMORY_DB, getGraphVersion, graphVersionMatches } from '../../db';
+import { getEdgeWeight, setEdgeWeight, adjustEdgeWeights, sweepDeadConnections, freezeEdge, unfreezeEdge } from '../../db/connections';
+import { sendJson, parseBody } from '../helpers';
+
+export async function handleConnectionRoutes(
+  req: IncomingMessage,
+  res: ServerResponse,
+  url: URL,
+  method: string,
+): Promise<boolean> {
+  if (method === 'GET' && url.pathname === '/api/connections/weight') {
+    try {
+      const source = url.searchParams.get('source')?.trim() ?? '';
+      const target = url.searchParams.get('target')?.trim() ?? '';
+      const relationship = url.searchParams.get('relationship')?.trim() || undefined;
+      const database = url.searchParams.get('database')?.trim() || DEFAULT_MEMORY_DB;
+
+      if (!source || !target) {
+        sendJson(res, 400, { error: 'source and target are required' });
+        return true;
+      }
+
+      const weight = getEdgeWeight(source, target, { relationship, database });
+      if (weight === null) {
+        sendJson(res, 404, { error: 'Connection not found' });
+        return true;
+      }
+
+      sendJson(res, 200, { source, target, relationship: relationship ?? null, weight });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'POST' && url.pathname === '/api/connections/weight') {
+    try {
+      const body = await parseBody(req) as {
+        source?: string;
+        target?: string;
+        weight?: number;
+        relationship?: string;
+        database?: string;
+        expectedVersion?: number;
+      };
+      const source = body.source?.trim() ?? '';
+      const target = body.target?.trim() ?? '';
+      const relationship = typeof body.relationship === 'string' && body.relationship.trim()
+        ? body.relationship.trim()
+        : undefined;
+      const database = typeof body.database === 'string' && body.database.trim()
+        ? body.database.trim()
+        : DEFAULT_MEMORY_DB;
+
+      if (!source || !target || typeof body.weight !== 'number' || !Number.isFinite(body.weight)) {
+        sendJson(res, 400, { error: 'source, target and a numeric weight are required' });
+        return true;
+      }
+
+      const expectedVersion = typeof body.expectedVersion === 'number' ? body.expectedVersion : undefined;
+      if (!graphVersionMatches(expectedVersion, database)) {
+        sendJson(res, 409, {
+          error: 'Graph version mismatch',
+          expected_version: expectedVersion,
+          graph_version: getGraphVersion(database),
+        });
+        return true;
+      }
+
+      const weight = setEdgeWeight(source, target, body.weight, { relationship, database });
+      if (weight === null) {
+        sendJson(res, 404, { error: 'Connection not found' });
+        return true;
+      }
+
+      sendJson(res, 200, { source, target, relationship: relationship ?? null, weight });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'POST' && url.pathname === '/api/connections/adjust') {
+    try {
+      const body = await parseBody(req) as {
+        pairs?: Array<{ source?: string; target?: string }>;
+        delta?: number;
+        relationship?: string;
+        database?: string;
+      };
+      const pairs = Array.isArray(body.pairs)
+        ? body.pairs
+          .map(pair => ({ source: pair?.source?.trim() ?? '', target: pair?.target?.trim() ?? '' }))
+          .filter(pair => pair.source && pair.target)
+        : [];
+
+      if (pairs.length === 0 || typeof body.delta !== 'number' || !Number.isFinite(body.delta)) {
+        sendJson(res, 400, { error: 'pairs (source/target) and a numeric delta are required' });
+        return true;
+      }
+
+      const result = adjustEdgeWeights(pairs, body.delta, {
+        relationship: typeof body.relationship === 'string' && body.relationship.trim() ? body.relationship.trim() : undefined,
+        database: typeof body.database === 'string' && body.database.trim() ? body.database.trim() : DEFAULT_MEMORY_DB,
+      });
+      sendJson(res, 200, result);
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'POST' && (url.pathname === '/api/connections/freeze' || url.pathname === '/api/connections/unfreeze')) {
+    try {
+      const body = await parseBody(req) as {
+        source?: string;
+        target?: string;
+        relationship?: string;
+        database?: string;
+      };
+      const source = body.source?.trim() ?? '';
+      const target = body.target?.trim() ?? '';
+      const relationship = typeof body.relationship === 'string' && body.relationship.trim()
+        ? body.relationship.trim()
+        : undefined;
+      const database = typeof body.database === 'string' && body.database.trim()
+        ? body.database.trim()
+        : DEFAULT_MEMORY_DB;
+
+      if (!source || !target) {
+        sendJson(res, 400, { error: 'source and target are required' });
+        return true;
+      }
+
+      const frozen = url.pathname === '/api/connections/freeze';
+      const edges = frozen
+        ? freezeEdge(source, target, { relationship, database })
+        : unfreezeEdge(source, target, { relationship, database });
+      if (edges === 0) {
+        sendJson(res, 404, { error: 'Connection not found' });
+        return true;
+      }
+
+      sendJson(res, 200, { source, target, relationship: relationship ?? null, frozen, edges });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'POST' && url.pathname === '/api/connections/sweep') {
+    try {
+      const body = await parseBody(req) as { database?: string };
+      const database = typeof body.database === 'string' && body.database.trim() ? body.database.trim() : undefined;
+      const removed = sweepDeadConnections(database);
+      sendJson(res, 200, { database: database ?? null, removed });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  return false;
+}
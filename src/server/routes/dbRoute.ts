@@ -2,7 +2,7 @@
 import { IncomingMessage, ServerResponse } from 'http';
 import { sendJson, parseBody } from '../helpers';
 import { ensureDefaultMemoryDatabase } from '../../db';
-import { createDatabase, deleteDatabase, listDatabases, normalizeDatabaseName } from '../../db/memoryDatabase';
+import { createDatabase, deleteDatabase, clearDatabase, listDatabases, normalizeDatabaseName, getDatabaseByName, getDatabaseConfig, updateDatabaseConfig } from '../../db/memoryDatabase';
 
 export async function handleDbRoutes(
   req: IncomingMessage,
@@ -65,5 +65,63 @@ export async function handleDbRoutes(
     }
   }
 
+  if (method === 'POST' && url.pathname === '/api/db/clear') {
+    try {
+      const body = await parseBody(req) as { name?: string };
+      const rawName = typeof body.name === 'string' ? body.name : '';
+      const name = normalizeDatabaseName(rawName);
+
+      const cleared = await clearDatabase(name);
+      sendJson(res, 200, { cleared: true, name, ...cleared });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 400, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'GET' && url.pathname === '/api/db/config') {
+    try {
+      ensureDefaultMemoryDatabase();
+      const name = normalizeDatabaseName(url.searchParams.get('name'));
+      if (!getDatabaseByName(name)) {
+        sendJson(res, 404, { error: `Database not found: ${name}` });
+        return true;
+      }
+
+      sendJson(res, 200, { name, config: getDatabaseConfig(name) });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'POST' && url.pathname === '/api/db/config') {
+    try {
+      ensureDefaultMemoryDatabase();
+      const body = await parseBody(req) as { name?: string; config?: unknown };
+      const name = normalizeDatabaseName(typeof body.name === 'string' ? body.name : '');
+      if (!body.config || typeof body.config !== 'object' || Array.isArray(body.config)) {
+        sendJson(res, 400, { error: 'config must be an object' });
+        return true;
+      }
+      if (!getDatabaseByName(name)) {
+        sendJson(res, 404, { error: `Database not found: ${name}` });
+        return true;
+      }
+
+      const config = updateDatabaseConfig(name, body.config as Record<string, unknown>);
+      sendJson(res, 200, { name, config });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 400, { error: message });
+      return true;
+    }
+  }
+
   return false;
 }
@@ -0,0 +1,148 @@
+// src/server/routes/graphRoute.ts — Bulk graph import and maintenance routes
+import { IncomingMessage, ServerResponse } from 'http';
+import { DEFAULT_MEMORY_DB } from '../../db';
+import { importGraph, exportAdjacency, isImportDedupPolicy } from '../../db/graphTransfer';
+import { validateGraph } from '../../db/connections';
+import type { ImportChunkInput, ImportConnectionInput } from '../../db/graphTransfer';
+import { requestConsolidation, runMaintenance, previewMaintenance, parseConsolidationPhases, buildSimilarityEdges } from '../../consolidate';
+import { sendJson, parseBody } from '../helpers';
+
+export async function handleGraphRoutes(
+  req: IncomingMessage,
+  res: ServerResponse,
+  url: URL,
+  method: string,
+): Promise<boolean> {
+  if (method === 'POST' && url.pathname === '/api/graph/import') {
+    try {
+      const body = await parseBody(req) as {
+        database?: string;
+        chunks?: ImportChunkInput[];
+        connections?: ImportConnectionInput[];
+        unknownEndpoints?: string;
+        dedup?: string;
+      };
+      const database = typeof body.database === 'string' && body.database.trim()
+        ? body.database.trim()
+        : DEFAULT_MEMORY_DB;
+
+      if (!Array.isArray(body.chunks) && !Array.isArray(body.connections)) {
+        sendJson(res, 400, { error: 'chunks or connections are required' });
+        return true;
+      }
+
+      const invalidChunk = (body.chunks ?? []).some(chunk => typeof chunk?.text !== 'string' || !chunk.text.trim());
+      if (invalidChunk) {
+        sendJson(res, 400, { error: 'every chunk needs non-empty text' });
+        return true;
+      }
+
+      if (body.dedup !== undefined && !isImportDedupPolicy(body.dedup)) {
+        sendJson(res, 400, { error: 'dedup must be one of none, content, content_merge' });
+        return true;
+      }
+
+      const unknownEndpoints = body.unknownEndpoints === 'placeholder' ? 'placeholder' : 'reject';
+      const result = await importGraph({ chunks: body.chunks, connections: body.connections }, database, unknownEndpoints, body.dedup);
+      sendJson(res, 200, result);
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 400, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'POST' && url.pathname === '/api/graph/consolidate') {
+    try {
+      const startedAt = Date.now();
+      await requestConsolidation();
+      sendJson(res, 200, { ok: true, duration_ms: Date.now() - startedAt });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'POST' && url.pathname === '/api/graph/maintain') {
+    let phases: ReturnType<typeof parseConsolidationPhases> | undefined;
+    let dryRun = false;
+    try {
+      const body = await parseBody(req) as { phases?: string; dryRun?: boolean };
+      phases = typeof body.phases === 'string' ? parseConsolidationPhases(body.phases) : undefined;
+      dryRun = body.dryRun === true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Invalid request';
+      sendJson(res, 400, { error: message });
+      return true;
+    }
+
+    try {
+      sendJson(res, 200, dryRun ? await previewMaintenance({ phases }) : await runMaintenance({ phases }));
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'POST' && url.pathname === '/api/graph/similarity-edges') {
+    try {
+      const body = await parseBody(req) as { database?: string; threshold?: number; maxPerChunk?: number };
+      const database = typeof body.database === 'string' && body.database.trim()
+        ? body.database.trim()
+        : DEFAULT_MEMORY_DB;
+      const threshold = typeof body.threshold === 'number' ? body.threshold : 0.5;
+      const maxPerChunk = typeof body.maxPerChunk === 'number' ? body.maxPerChunk : 3;
+
+      if (!Number.isFinite(threshold) || threshold <= 0 || threshold > 1 || !Number.isFinite(maxPerChunk) || maxPerChunk < 1) {
+        sendJson(res, 400, { error: 'threshold must be in (0, 1] and maxPerChunk at least 1' });
+        return true;
+      }
+
+      sendJson(res, 200, { database, ...buildSimilarityEdges(threshold, maxPerChunk, database) });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'GET' && url.pathname === '/api/graph/validate') {
+    try {
+      const database = url.searchParams.get('database')?.trim() || DEFAULT_MEMORY_DB;
+      const issues = validateGraph(database);
+      sendJson(res, 200, { database, ok: issues.length === 0, issues });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'GET' && url.pathname === '/api/graph/adjacency') {
+    try {
+      const database = url.searchParams.get('database')?.trim() || DEFAULT_MEMORY_DB;
+      const format = url.searchParams.get('format')?.trim() || 'coo';
+      if (format !== 'coo' && format !== 'dense') {
+        sendJson(res, 400, { error: 'format must be coo or dense' });
+        return true;
+      }
+
+      const adjacency = format === 'dense' ? exportAdjacency(database, 'dense') : exportAdjacency(database, 'coo');
+      sendJson(res, 200, { database, format, ...adjacency });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 400, { error: message });
+      return true;
+    }
+  }
+
+  return false;
+}
@@ -1,10 +1,14 @@
 // src/server/routes/healthRoute.ts — Health, stats, graph, concepts, and chunks routes
 import { IncomingMessage, ServerResponse } from 'http';
-import { db, DEFAULT_MEMORY_DB } from '../../db';
-import { sendJson, clampNumber, type RelationshipCounts } from '../helpers';
+import { db, DEFAULT_MEMORY_DB, getGraphVersion } from '../../db';
+import { getEdgeAgeDistribution, DEFAULT_EDGE_AGE_BUCKETS_MS, countFrozenEdges } from '../../db/connections';
+import { sendJson, clampNumber, setCorsHeaders, type RelationshipCounts } from '../helpers';
+import { writeSseEvent, statsSseConnections, type StatsEventPayload } from '../sse';
+import { STATS_STREAM_INTERVAL_MS } from '../../config';
 import { clusterIntoConcepts } from '../../consolidate/cluster';
 import { abstractConcepts } from '../../consolidate';
 import { syncConceptEmbeddings } from '../../concepts/sync';
+import { getRecallCacheStats } from '../../retrieve/cache';
 
 function getRelationshipCounts(database: string): RelationshipCounts {
   const base: RelationshipCounts = {
@@ -31,8 +35,27 @@ function getRelationshipCounts(database: string): RelationshipCounts {
   return base;
 }
 
+const MIN_STATS_STREAM_INTERVAL_MS = 250;
+
+function countRows(table: 'chunks' | 'connections' | 'concepts', database: string): number {
+  const row = db.prepare(`SELECT COUNT(*) AS total FROM ${table} WHERE database_id = ?`).get(database) as { total: number };
+  return row.total;
+}
+
+function statsEvent(database: string): StatsEventPayload {
+  return {
+    type: 'stats',
+    database,
+    at: new Date().toISOString(),
+    total_chunks: countRows('chunks', database),
+    total_connections: countRows('connections', database),
+    total_concepts: countRows('concepts', database),
+    graph_version: getGraphVersion(database),
+  };
+}
+
 export async function handleHealthRoutes(
-  _req: IncomingMessage,
+  req: IncomingMessage,
   res: ServerResponse,
   url: URL,
   method: string,
@@ -70,9 +93,16 @@ export async function handleHealthRoutes(
         total_chunks: totalChunksRow.total,
         total_connections: totalConnectionsRow.total,
         total_concepts: totalConceptsRow.total,
+        frozen_connections: countFrozenEdges(database),
         relationship_counts: relationshipCounts,
         top_sources: topSources,
         recent_chunks: recentChunks,
+        graph_version: getGraphVersion(database),
+        recall_cache: getRecallCacheStats(),
+        edge_age_distribution: {
+          buckets_ms: DEFAULT_EDGE_AGE_BUCKETS_MS,
+          counts: getEdgeAgeDistribution(DEFAULT_EDGE_AGE_BUCKETS_MS, database),
+        },
       });
       return true;
     } catch (error) {
@@ -82,6 +112,39 @@ export async function handleHealthRoutes(
     }
   }
 
+  // Live counters for dashboards: one `stats` event on connect, then one per
+  // interval until the client disconnects.
+  if (method === 'GET' && url.pathname === '/api/stats/stream') {
+    const database = url.searchParams.get('database')?.trim() || DEFAULT_MEMORY_DB;
+    const rawInterval = Number(url.searchParams.get('intervalMs') ?? STATS_STREAM_INTERVAL_MS);
+    const intervalMs = Math.max(MIN_STATS_STREAM_INTERVAL_MS, Number.isFinite(rawInterval) ? Math.floor(rawInterval) : STATS_STREAM_INTERVAL_MS);
+
+    setCorsHeaders(res);
+    res.statusCode = 200;
+    res.setHeader('Content-Type', 'text/event-stream');
+    res.setHeader('Cache-Control', 'no-cache');
+    res.setHeader('Connection', 'keep-alive');
+    res.setHeader('Access-Control-Allow-Origin', '*');
+    res.flushHeaders?.();
+
+    const push = () => {
+      try {
+        writeSseEvent(res, statsEvent(database));
+      } catch (error) {
+        const message = error instanceof Error ? error.message : String(error);
+        console.warn(`⚠️  Stats stream update failed: ${message}`);
+      }
+    };
+    push();
+    const timer = setInterval(push, intervalMs);
+    statsSseConnections.add(res);
+    req.on('close', () => {
+      clearInterval(timer);
+      statsSseConnections.delete(res);
+    });
+    return true;
+  }
+
   if (method === 'GET' && url.pathname === '/api/chunks') {
     try {
       const database = url.searchParams.get('database')?.trim() || DEFAULT_MEMORY_DB;
@@ -110,7 +173,7 @@ export async function handleHealthRoutes(
 
       const whereClause = filters.length > 0 ? `WHERE ${filters.join(' AND ')}` : '';
       const rows = db.prepare(`
-        SELECT chunk_id, text, source, page, timestamp, access_count, last_accessed, tags, is_duplicate, contradiction_flag
+        SELECT chunk_id, text, source, page, timestamp, access_count, last_accessed, tags, is_duplicate, contradiction_flag, novelty
         FROM chunks
         ${whereClause}
         ORDER BY timestamp DESC
@@ -2,7 +2,7 @@
 import fs from 'fs';
 import { IncomingMessage, ServerResponse } from 'http';
 import { v4 as uuidv4 } from 'uuid';
-import { ingest, ingestText } from '../../ingest';
+import { ingest, ingestText, isConfidence } from '../../ingest';
 import { parseUrl } from '../../ingest/parser';
 import {
   setCorsHeaders, sendJson, parseBody, parseTags, parseMultipartUpload,
@@ -138,7 +138,7 @@ export async function handleIngestRoutes(
 
   if (method === 'POST' && url.pathname === '/api/ingest/url') {
     try {
-      const body = await parseBody(req) as { url?: string; tags?: string[] | string; database?: string };
+      const body = await parseBody(req) as { url?: string; tags?: string[] | string; database?: string; ttlMs?: number; confidence?: number };
       const urlValue = body.url?.trim() ?? '';
       const database = typeof body.database === 'string' ? body.database.trim() : url.searchParams.get('database')?.trim();
 
@@ -147,6 +147,11 @@ export async function handleIngestRoutes(
         return true;
       }
 
+      if (body.confidence !== undefined && !isConfidence(body.confidence)) {
+        sendJson(res, 400, { error: 'confidence must be between 0 and 1' });
+        return true;
+      }
+
       const tags = parseTags(body.tags);
       const jobId = uuidv4();
       ingestJobSnapshots.set(jobId, {
@@ -171,6 +176,8 @@ export async function handleIngestRoutes(
             onProgress: (event) => {
               emitIngestProgress(jobId, event);
             },
+            ttlMs: typeof body.ttlMs === 'number' && Number.isFinite(body.ttlMs) ? body.ttlMs : undefined,
+            confidence: body.confidence,
           }, database);
         } catch (error) {
           const message = error instanceof Error ? error.message : 'Unknown ingest error';
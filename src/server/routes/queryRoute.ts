@@ -1,8 +1,13 @@
 // src/server/routes/queryRoute.ts — Query and query-answer routes
 import { IncomingMessage, ServerResponse } from 'http';
-import { retrieve, Result } from '../../retrieve';
+import { retrieve, retrieveIds, Result } from '../../retrieve';
+import { retrieveGrouped } from '../../retrieve/grouped';
 import { queryAnswer } from '../../answer/query';
-import { sendJson, parseBody } from '../helpers';
+import { DEFAULT_MEMORY_DB } from '../../db';
+import { searchChunks } from '../../db/chunks';
+import type { SearchMode } from '../../retrieve/textSearch';
+import { isScoreCombine, SCORE_COMBINE_MODES, isPathAccumulation, PATH_ACCUMULATION_MODES, isConfidenceMapping, CONFIDENCE_MAPPINGS } from '../../retrieve/combine';
+import { sendJson, parseBody, checkQueryLimits } from '../helpers';
 
 export async function handleQueryRoutes(
   req: IncomingMessage,
@@ -42,6 +47,19 @@ export async function handleQueryRoutes(
         maxHops?: number;
         relationshipFilter?: string[];
         includeConflicts?: boolean;
+        minContentLength?: number;
+        useCache?: boolean;
+        hopDecay?: number;
+        degreeBoost?: number;
+        exactDepth?: number;
+        recordAccess?: boolean;
+        scoreCombine?: string;
+        minEdgeAccessCount?: number;
+        pathAccumulation?: string;
+        diversity?: number;
+        relevanceMass?: number;
+        useConfidence?: boolean;
+        confidenceMapping?: string;
       };
       const query = body.query?.trim() ?? '';
       const database = body.database && typeof body.database === 'string'
@@ -51,16 +69,75 @@ export async function handleQueryRoutes(
         ? Math.floor(body.top_k)
         : 5;
 
+      const maxHops = typeof body.maxHops === 'number' && Number.isFinite(body.maxHops)
+        ? Math.floor(body.maxHops)
+        : undefined;
+      const exactDepth = typeof body.exactDepth === 'number' && Number.isFinite(body.exactDepth)
+        ? Math.floor(body.exactDepth)
+        : undefined;
+      const limitError = checkQueryLimits({ topK, maxHops, exactDepth });
+      if (limitError) {
+        sendJson(res, 400, { error: limitError });
+        return true;
+      }
+
+      if (body.hopDecay !== undefined && !(typeof body.hopDecay === 'number' && body.hopDecay > 0 && body.hopDecay < 1)) {
+        sendJson(res, 400, { error: 'hopDecay must be between 0 and 1 (exclusive)' });
+        return true;
+      }
+
+      if (body.pathAccumulation !== undefined && !isPathAccumulation(body.pathAccumulation)) {
+        sendJson(res, 400, { error: `pathAccumulation must be one of ${PATH_ACCUMULATION_MODES.join(', ')}` });
+        return true;
+      }
+
+      if (body.scoreCombine !== undefined && !isScoreCombine(body.scoreCombine)) {
+        sendJson(res, 400, { error: `scoreCombine must be one of ${SCORE_COMBINE_MODES.join(', ')}` });
+        return true;
+      }
+
+      if (body.diversity !== undefined && !(typeof body.diversity === 'number' && body.diversity >= 0 && body.diversity <= 1)) {
+        sendJson(res, 400, { error: 'diversity must be between 0 and 1' });
+        return true;
+      }
+
+      if (body.relevanceMass !== undefined && !(typeof body.relevanceMass === 'number' && body.relevanceMass > 0 && body.relevanceMass <= 1)) {
+        sendJson(res, 400, { error: 'relevanceMass must be in (0, 1]' });
+        return true;
+      }
+
+      if (body.confidenceMapping !== undefined && !isConfidenceMapping(body.confidenceMapping)) {
+        sendJson(res, 400, { error: `confidenceMapping must be one of ${CONFIDENCE_MAPPINGS.join(', ')}` });
+        return true;
+      }
+
       const results: Result[] = await retrieve(query, {
         topK,
         database,
-        maxHops: typeof body.maxHops === 'number' && Number.isFinite(body.maxHops)
-          ? Math.floor(body.maxHops)
-          : undefined,
+        maxHops,
         relationshipFilter: Array.isArray(body.relationshipFilter)
           ? body.relationshipFilter
           : undefined,
         includeConflicts: typeof body.includeConflicts === 'boolean' ? body.includeConflicts : true,
+        minContentLength: typeof body.minContentLength === 'number' && Number.isFinite(body.minContentLength)
+          ? Math.floor(body.minContentLength)
+          : undefined,
+        useCache: body.useCache === true,
+        hopDecay: body.hopDecay,
+        degreeBoost: typeof body.degreeBoost === 'number' && Number.isFinite(body.degreeBoost)
+          ? body.degreeBoost
+          : undefined,
+        exactDepth,
+        recordAccess: body.recordAccess !== false,
+        scoreCombine: body.scoreCombine,
+        pathAccumulation: body.pathAccumulation,
+        minEdgeAccessCount: typeof body.minEdgeAccessCount === 'number' && Number.isFinite(body.minEdgeAccessCount)
+          ? Math.floor(body.minEdgeAccessCount)
+          : undefined,
+        diversity: body.diversity,
+        relevanceMass: body.relevanceMass,
+        useConfidence: body.useConfidence === true,
+        confidenceMapping: body.confidenceMapping,
       });
       sendJson(res, 200, results);
       return true;
@@ -71,5 +148,108 @@ export async function handleQueryRoutes(
     }
   }
 
+  if (method === 'POST' && url.pathname === '/api/query/ids') {
+    try {
+      const body = await parseBody(req) as { query?: string; top_k?: number; database?: string; maxHops?: number; recordAccess?: boolean };
+      const query = body.query?.trim() ?? '';
+      const topK = typeof body.top_k === 'number' && Number.isFinite(body.top_k) && body.top_k > 0
+        ? Math.floor(body.top_k)
+        : 5;
+      const maxHops = typeof body.maxHops === 'number' && Number.isFinite(body.maxHops)
+        ? Math.floor(body.maxHops)
+        : undefined;
+
+      if (!query) {
+        sendJson(res, 400, { error: 'query is required' });
+        return true;
+      }
+
+      const limitError = checkQueryLimits({ topK, maxHops });
+      if (limitError) {
+        sendJson(res, 400, { error: limitError });
+        return true;
+      }
+
+      const ids = await retrieveIds(query, {
+        topK,
+        maxHops,
+        database: typeof body.database === 'string' ? body.database.trim() : undefined,
+        recordAccess: body.recordAccess !== false,
+      });
+      sendJson(res, 200, ids);
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'POST' && url.pathname === '/api/query/grouped') {
+    try {
+      const body = await parseBody(req) as { query?: string; top_k?: number; database?: string; recordAccess?: boolean };
+      const query = body.query?.trim() ?? '';
+      const topK = typeof body.top_k === 'number' && Number.isFinite(body.top_k) && body.top_k > 0
+        ? Math.floor(body.top_k)
+        : 10;
+
+      if (!query) {
+        sendJson(res, 400, { error: 'query is required' });
+        return true;
+      }
+
+      const limitError = checkQueryLimits({ topK });
+      if (limitError) {
+        sendJson(res, 400, { error: limitError });
+        return true;
+      }
+
+      const groups = await retrieveGrouped(query, {
+        topK,
+        database: typeof body.database === 'string' ? body.database.trim() : undefined,
+        recordAccess: body.recordAccess !== false,
+      });
+      sendJson(res, 200, groups);
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
+  if (method === 'GET' && url.pathname === '/api/search') {
+    try {
+      const query = url.searchParams.get('q')?.trim() ?? '';
+      const modeName = url.searchParams.get('mode')?.trim() || 'substring';
+      const database = url.searchParams.get('database')?.trim() || DEFAULT_MEMORY_DB;
+      const limitParam = Number(url.searchParams.get('limit') ?? '20');
+      const maxDistance = Number(url.searchParams.get('maxDistance') ?? '1');
+
+      if (!query) {
+        sendJson(res, 400, { error: 'q is required' });
+        return true;
+      }
+
+      let mode: SearchMode;
+      if (modeName === 'substring' || modeName === 'prefix') {
+        mode = { kind: modeName };
+      } else if (modeName === 'fuzzy' && Number.isFinite(maxDistance) && maxDistance >= 0) {
+        mode = { kind: 'fuzzy', maxDistance };
+      } else {
+        sendJson(res, 400, { error: 'mode must be substring, prefix or fuzzy (with maxDistance >= 0)' });
+        return true;
+      }
+
+      const limit = Number.isFinite(limitParam) && limitParam > 0 ? Math.floor(limitParam) : 20;
+      sendJson(res, 200, searchChunks(query, mode, database, limit));
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'Unknown API error';
+      sendJson(res, 500, { error: message });
+      return true;
+    }
+  }
+
   return false;
 }
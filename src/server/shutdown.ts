@@ -0,0 +1,46 @@
+// src/server/shutdown.ts — Graceful shutdown of the HTTP and gRPC servers
+import http from 'http';
+import type * as grpc from '@grpc/grpc-js';
+import { closeAllSseConnections } from './sse';
+import { SHUTDOWN_GRACE_MS } from '../config';
+
+/**
+ * Stop accepting HTTP connections and resolve once the server has closed.
+ * Open SSE streams are ended at once (they never finish on their own), idle
+ * keep-alive sockets are dropped, and requests still running after `graceMs`
+ * have their sockets destroyed.
+ */
+export function stopHttpServer(server: http.Server, graceMs: number = SHUTDOWN_GRACE_MS): Promise<void> {
+  return new Promise((resolve) => {
+    if (!server.listening) {
+      resolve();
+      return;
+    }
+
+    const force = setTimeout(() => server.closeAllConnections(), Math.max(0, graceMs));
+    force.unref();
+
+    server.close(() => {
+      clearTimeout(force);
+      resolve();
+    });
+    closeAllSseConnections();
+    server.closeIdleConnections();
+  });
+}
+
+/** Let in-flight gRPC calls finish, cancelling whatever is left after `graceMs`. */
+export function stopGrpcServer(server: grpc.Server, graceMs: number = SHUTDOWN_GRACE_MS): Promise<void> {
+  return new Promise((resolve) => {
+    const force = setTimeout(() => {
+      server.forceShutdown();
+      resolve();
+    }, Math.max(0, graceMs));
+    force.unref();
+
+    server.tryShutdown(() => {
+      clearTimeout(force);
+      resolve();
+    });
+  });
+}
@@ -51,11 +51,22 @@ export type ChunkEventPayload = {
   etaSeconds: number;
 };
 
-export type SseEventPayload = StartEventPayload | ChunkEventPayload | DoneEventPayload | ErrorEventPayload;
+export type StatsEventPayload = {
+  type: 'stats';
+  database: string;
+  at: string;
+  total_chunks: number;
+  total_connections: number;
+  total_concepts: number;
+  graph_version: number;
+};
+
+export type SseEventPayload = StartEventPayload | ChunkEventPayload | DoneEventPayload | ErrorEventPayload | StatsEventPayload;
 
 // ── Shared state ───────────────────────────────────────────────────────────
 
 export const ingestSseConnections = new Map<string, ServerResponse>();
+export const statsSseConnections = new Set<ServerResponse>();
 export const ingestJobResults = new Map<string, DoneEventPayload | ErrorEventPayload>();
 export const ingestJobSnapshots = new Map<string, JobSnapshot>();
 export const MAX_COMPLETED_JOBS = 20;
@@ -91,6 +102,19 @@ export function closeSseForJob(jobId: string): void {
   }
 }
 
+/** End every open ingest and stats stream. Returns how many were closed. */
+export function closeAllSseConnections(): number {
+  const streams = [...ingestSseConnections.values(), ...statsSseConnections];
+  ingestSseConnections.clear();
+  statsSseConnections.clear();
+  for (const stream of streams) {
+    if (!stream.writableEnded) {
+      stream.end();
+    }
+  }
+  return streams.length;
+}
+
 export function emitIngestProgress(jobId: string, event: ProgressEvent): void {
   const existingSnapshot = ingestJobSnapshots.get(jobId);
   const stream = ingestSseConnections.get(jobId);
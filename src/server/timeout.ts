@@ -0,0 +1,42 @@
+// src/server/timeout.ts — Server-side deadline for unary gRPC calls
+import * as grpc from '@grpc/grpc-js';
+
+type UnaryHandler = (call: any, callback: grpc.sendUnaryData<any>) => void;
+
+/**
+ * Wrap unary handlers so a call that has not answered within `timeoutMs`
+ * fails with DEADLINE_EXCEEDED, whatever deadline the client set. The handler
+ * itself keeps running (there is no way to abort it); its late answer is
+ * dropped. A timeout of 0 or less returns the handlers unchanged. Only wrap
+ * handlers whose side effects are safe to keep after the client was told the
+ * call failed: a timed-out Query still records access and Hebbian updates, a
+ * write would still commit.
+ */
+export function withUnaryTimeout<T extends Record<string, UnaryHandler>>(handlers: T, timeoutMs: number): T {
+  if (!Number.isFinite(timeoutMs) || timeoutMs <= 0) return handlers;
+
+  const wrapped: Record<string, UnaryHandler> = {};
+  for (const [name, handler] of Object.entries(handlers)) {
+    wrapped[name] = (call, callback) => {
+      let settled = false;
+      const timer = setTimeout(() => {
+        if (settled) return;
+        settled = true;
+        console.error(`❌ ${name} timed out after ${timeoutMs}ms`);
+        callback({
+          name: grpc.status[grpc.status.DEADLINE_EXCEEDED],
+          message: `${name} exceeded the ${timeoutMs}ms server timeout`,
+          code: grpc.status.DEADLINE_EXCEEDED,
+        } as grpc.ServiceError);
+      }, timeoutMs);
+
+      handler(call, (error, value, trailer, flags) => {
+        if (settled) return;
+        settled = true;
+        clearTimeout(timer);
+        callback(error, value, trailer, flags);
+      });
+    };
+  }
+  return wrapped as T;
+}
@@ -0,0 +1,159 @@
+// src/tests/combine.test.ts
+import { combineRelevance, isScoreCombine, SCORE_COMBINE_MODES, accumulatePathScore, PATH_ACCUMULATION_MODES, cutByRelevanceMass, mapConfidence, isConfidenceMapping, CONFIDENCE_MAPPINGS } from '../retrieve/combine';
+import type { ScoreCombine, PathAccumulation } from '../retrieve/combine';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+// [content, association] components per candidate
+const candidates: Record<string, [number, number]> = {
+  balanced: [0.35, 0.35],
+  contentHeavy: [0.9, 0.2],
+  contentOnly: [0.95, 0],
+};
+
+function rank(mode: ScoreCombine): string {
+  return Object.entries(candidates)
+    .map(([name, [content, association]]) => ({ name, score: combineRelevance(content, association, mode) }))
+    .sort((a, b) => b.score - a.score)
+    .map(entry => entry.name)
+    .join(',');
+}
+
+// Walk seed → ... along `weights` the way multiHopExpand does
+function walkPath(weights: number[], mode: PathAccumulation, seedScore: number = 1, hopDecay: number = 0.9): number {
+  let score = seedScore;
+  let decayProduct = 1;
+  let minWeight = Number.POSITIVE_INFINITY;
+  let weightSum = 0;
+
+  weights.forEach((edgeWeight, index) => {
+    const hops = index + 1;
+    const hopFactor = Math.pow(hopDecay, hops);
+    decayProduct *= hopFactor;
+    minWeight = Math.min(minWeight, edgeWeight);
+    weightSum += edgeWeight;
+    score = accumulatePathScore({ previousScore: score, seedScore, edgeWeight, hopFactor, decayProduct, minWeight, weightSum, hops }, mode);
+  });
+
+  return score;
+}
+
+function runTests(): void {
+  let passed = 0;
+  let failed = 0;
+
+  const test = (name: string, fn: () => void) => {
+    try {
+      fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('combine tests:\n');
+
+  // Test 1: default keeps the additive blend
+  test('weighted_sum is the default additive blend', () => {
+    assert(combineRelevance(0.3, 0.2) === 0.5, 'default should add the components');
+    assert(rank('weighted_sum') === 'contentHeavy,contentOnly,balanced', `got ${rank('weighted_sum')}`);
+  });
+
+  // Test 2: max
+  test('max lets a single strong signal win', () => {
+    assert(rank('max') === 'contentOnly,contentHeavy,balanced', `got ${rank('max')}`);
+  });
+
+  // Test 3: product
+  test('product zeroes candidates missing either signal', () => {
+    assert(combineRelevance(0.95, 0, 'product') === 0, 'no association should score 0');
+    assert(rank('product') === 'contentHeavy,balanced,contentOnly', `got ${rank('product')}`);
+  });
+
+  // Test 4: harmonic mean
+  test('harmonic_mean favours balanced components', () => {
+    assert(combineRelevance(0, 0, 'harmonic_mean') === 0, 'zero inputs should not divide by zero');
+    assert(rank('harmonic_mean') === 'balanced,contentHeavy,contentOnly', `got ${rank('harmonic_mean')}`);
+  });
+
+  // Test 5: every mode ranks differently
+  test('each mode produces a distinct ranking', () => {
+    const rankings = new Set(SCORE_COMBINE_MODES.map(rank));
+    assert(rankings.size === SCORE_COMBINE_MODES.length, `expected ${SCORE_COMBINE_MODES.length} rankings, got ${rankings.size}`);
+  });
+
+  // Test 6: validation
+  test('rejects unknown mode names', () => {
+    assert(isScoreCombine('max'), 'max is valid');
+    assert(!isScoreCombine('average'), 'average is not a mode');
+  });
+
+  // Test 7: path accumulation on a path with one weak link
+  test('path accumulation modes score a weak link differently', () => {
+    const weakLink = [0.9, 0.1, 0.9];
+    const scores = Object.fromEntries(PATH_ACCUMULATION_MODES.map(mode => [mode, walkPath(weakLink, mode)]));
+    assert(new Set(Object.values(scores)).size === PATH_ACCUMULATION_MODES.length, `expected distinct scores, got ${JSON.stringify(scores)}`);
+    assert(scores.multiplicative < scores.min_edge && scores.min_edge < scores.average, `expected multiplicative < min_edge < average, got ${JSON.stringify(scores)}`);
+  });
+
+  // Test 8: multiplicative keeps the historical formula
+  test('multiplicative matches score * decay^depth * weight per hop', () => {
+    const expected = 1 * Math.pow(0.9, 1) * 0.5 * Math.pow(0.9, 2) * 0.4;
+    assert(Math.abs(walkPath([0.5, 0.4], 'multiplicative') - expected) < 1e-12, 'multiplicative should chain weights and decay');
+  });
+
+  // Test 9: min_edge is the bottleneck
+  test('min_edge scores strong paths by their weakest link', () => {
+    const strong = walkPath([0.9, 0.8], 'min_edge');
+    assert(Math.abs(strong - Math.pow(0.9, 1) * Math.pow(0.9, 2) * 0.8) < 1e-12, 'min_edge should use the weakest edge');
+    assert(strong > walkPath([0.9, 0.8], 'multiplicative'), 'a strong bottleneck should beat chained weights');
+  });
+
+  // Test 10: relevance mass keeps the smallest sufficient prefix
+  test('relevance mass returns the shortest prefix covering the fraction', () => {
+    const ranked = [0.5, 0.2, 0.15, 0.1, 0.05].map((score, index) => ({ id: index, score }));
+    const total = ranked.reduce((sum, result) => sum + result.score, 0);
+    for (const mass of [0.3, 0.7, 0.8, 0.95]) {
+      const kept = cutByRelevanceMass(ranked, mass);
+      const covered = kept.reduce((sum, result) => sum + result.score, 0);
+      const withoutLast = covered - kept[kept.length - 1].score;
+      assert(covered >= mass * total - 1e-12, `mass ${mass}: expected at least ${mass * total}, covered ${covered}`);
+      assert(withoutLast < mass * total, `mass ${mass}: dropping the last result should fall short`);
+    }
+    assert(cutByRelevanceMass(ranked, 0.5).length === 1, 'a dominant top result covers half on its own');
+    assert(cutByRelevanceMass(ranked, 1).length === ranked.length, 'the full mass keeps everything');
+  });
+
+  // Test 11: confidence mappings are bounded and monotonic
+  test('confidence mappings stay in [0, 1] and never decrease with relevance', () => {
+    const scores = [-2, -0.5, 0, 0.05, 0.2, 0.35, 0.5, 0.75, 1, 1.5, 3, 10, 1e6];
+    for (const mapping of CONFIDENCE_MAPPINGS) {
+      const mapped = scores.map(score => mapConfidence(score, mapping));
+      mapped.forEach((confidence, index) => {
+        assert(confidence >= 0 && confidence <= 1, `${mapping}(${scores[index]}) = ${confidence} is out of range`);
+        if (index > 0) assert(confidence >= mapped[index - 1], `${mapping} decreased between ${scores[index - 1]} and ${scores[index]}`);
+      });
+      assert(mapConfidence(Number.NaN, mapping) === 0, `${mapping} should map NaN to 0`);
+    }
+    assert(mapConfidence(0.4, 'linear_clamp') === 0.4, 'linear_clamp should pass in-range scores through');
+    assert(Math.abs(mapConfidence(0.5, 'sigmoid') - 0.5) < 1e-12, 'sigmoid should be centred on 0.5');
+  });
+
+  // Test 12: mapping validation
+  test('rejects unknown confidence mappings', () => {
+    assert(isConfidenceMapping('tanh'), 'tanh is valid');
+    assert(!isConfidenceMapping('softmax'), 'softmax is not a mapping');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+runTests();
@@ -0,0 +1,63 @@
+// src/tests/contentLimit.test.ts
+import { limitChunkContent, ContentTooLargeError } from '../ingest/filters';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+function runTests(): void {
+  let passed = 0;
+  let failed = 0;
+
+  const test = (name: string, fn: () => void) => {
+    try {
+      fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('content limit tests:\n');
+
+  // Test 1: the boundary length is allowed under both policies
+  test('text of exactly the limit passes unchanged', () => {
+    const text = 'x'.repeat(10);
+    assert(limitChunkContent(text, 10, 'reject') === text, 'reject should accept the boundary length');
+    assert(limitChunkContent(text, 10, 'truncate') === text, 'truncate should leave the boundary length alone');
+  });
+
+  // Test 2: one character over is rejected
+  test('reject policy throws one past the limit', () => {
+    let thrown: unknown = null;
+    try {
+      limitChunkContent('x'.repeat(11), 10, 'reject');
+    } catch (error) {
+      thrown = error;
+    }
+    assert(thrown instanceof ContentTooLargeError, 'expected ContentTooLargeError');
+    assert((thrown as Error).message.includes('11') && (thrown as Error).message.includes('10'), 'message should name length and limit');
+  });
+
+  // Test 3: truncation cuts to exactly the limit
+  test('truncate policy cuts to the limit', () => {
+    const truncated = limitChunkContent('abcdefghijkl', 10, 'truncate');
+    assert(truncated === 'abcdefghij', `expected the first 10 characters, got "${truncated}"`);
+  });
+
+  // Test 4: a zero limit disables the check
+  test('a limit of 0 is unlimited', () => {
+    const text = 'x'.repeat(10_000);
+    assert(limitChunkContent(text, 0, 'reject') === text, 'no limit should accept anything');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+runTests();
@@ -0,0 +1,90 @@
+// src/tests/debounce.test.ts
+import { createDebouncedRunner } from '../consolidate/debounce';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+const sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
+async function runTests(): Promise<void> {
+  let passed = 0;
+  let failed = 0;
+
+  const test = async (name: string, fn: () => Promise<void>) => {
+    try {
+      await fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('debounce tests:\n');
+
+  // Test 1: concurrent callers coalesce
+  await test('concurrent requests share a single run', async () => {
+    let runs = 0;
+    const request = createDebouncedRunner(async () => {
+      runs++;
+      await sleep(10);
+      return runs;
+    }, 20);
+
+    const results = await Promise.all([request(), request(), request(), request(), request()]);
+    assert(runs === 1, `expected 1 run, got ${runs}`);
+    assert(results.every(result => result === 1), 'every caller should see the same result');
+  });
+
+  // Test 2: requests after the window start a new run
+  await test('requests after the window trigger a new run', async () => {
+    let runs = 0;
+    const request = createDebouncedRunner(async () => ++runs, 5);
+
+    await request();
+    await request();
+    assert(runs === 2, `expected 2 runs, got ${runs}`);
+  });
+
+  // Test 3: a request during a run waits for it instead of overlapping
+  await test('runs never overlap', async () => {
+    let active = 0;
+    let maxActive = 0;
+    const request = createDebouncedRunner(async () => {
+      active++;
+      maxActive = Math.max(maxActive, active);
+      await sleep(30);
+      active--;
+    }, 5);
+
+    const first = request();
+    await sleep(15);
+    const second = request();
+    await Promise.all([first, second]);
+    assert(maxActive === 1, `expected no overlap, saw ${maxActive} concurrent runs`);
+  });
+
+  // Test 4: failures propagate to every waiting caller
+  await test('a failed run rejects all callers and does not block the next', async () => {
+    let calls = 0;
+    const request = createDebouncedRunner(async () => {
+      calls++;
+      if (calls === 1) throw new Error('boom');
+      return calls;
+    }, 5);
+
+    const outcomes = await Promise.allSettled([request(), request()]);
+    assert(outcomes.every(outcome => outcome.status === 'rejected'), 'both callers should see the failure');
+    assert(await request() === 2, 'the next request should run again');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+void runTests();
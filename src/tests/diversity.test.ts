@@ -0,0 +1,78 @@
+// src/tests/diversity.test.ts
+import { contentSimilarity, diversifyResults, selectResults } from '../retrieve/diversity';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+// Three near-copies of one passage outscore two distinct passages
+const results = [
+  { id: 'ltp-1', score: 0.92, text: 'Long-term potentiation strengthens synapses after repeated stimulation.' },
+  { id: 'ltp-2', score: 0.91, text: 'Long-term potentiation strengthens synapses after repeated stimulation of neurons.' },
+  { id: 'ltp-3', score: 0.90, text: 'After repeated stimulation, long-term potentiation strengthens synapses.' },
+  { id: 'sleep', score: 0.80, text: 'Sleep spindles coordinate hippocampal replay with cortical activity.' },
+  { id: 'place', score: 0.78, text: 'Place cells fire when an animal occupies a specific location.' },
+];
+
+function ids(list: Array<{ id: string }>): string {
+  return list.map(item => item.id).join(',');
+}
+
+function runTests(): void {
+  let passed = 0;
+  let failed = 0;
+
+  const test = (name: string, fn: () => void) => {
+    try {
+      fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('diversity tests:\n');
+
+  // Test 1: similarity basics
+  test('content similarity is word-set overlap', () => {
+    assert(contentSimilarity('Place cells fire', 'place CELLS fire') === 1, 'case and order should not matter');
+    assert(contentSimilarity('Place cells fire', 'Sleep spindles') === 0, 'disjoint texts should score 0');
+    assert(contentSimilarity(results[0].text, results[1].text) >= 0.8, 'near-copies should score high');
+  });
+
+  // Test 2: no diversity keeps the relevance order
+  test('diversity 0 keeps the input order', () => {
+    assert(ids(diversifyResults(results, 0)) === ids(results), 'expected the original order');
+    assert(ids(diversifyResults(results, 0, 2)) === 'ltp-1,ltp-2', 'expected the limit to apply');
+  });
+
+  // Test 3: high diversity spreads the near-duplicates out
+  test('high diversity moves distinct passages ahead of near-duplicates', () => {
+    const top3 = diversifyResults(results, 0.7, 3);
+    assert(top3[0].id === 'ltp-1', 'the most relevant result should stay first');
+    assert(top3.filter(item => item.id.startsWith('ltp')).length === 1, `expected one copy in the top 3, got ${ids(top3)}`);
+    assert(top3.every(item => results.some(original => original.id === item.id && original.score === item.score)), 'scores should be unchanged');
+  });
+
+  // Test 4: relevance mass is measured over the whole pool, before MMR and topK
+  test('relevance mass is cut before diversity and the topK cap', () => {
+    // Half of 4.31 needs three results, so topK 2 keeps two; measured on the top 2 alone it would keep one
+    const capped = selectResults(results, { topK: 2, diversity: 0, relevanceMass: 0.5 });
+    assert(ids(capped) === 'ltp-1,ltp-2', `expected topK to cap the mass cut, got ${ids(capped)}`);
+
+    // 0.8 of the pool is the three copies plus sleep; MMR reorders them but cannot pull in place
+    const diverse = selectResults(results, { topK: 5, diversity: 0.7, relevanceMass: 0.8 });
+    assert(diverse.length === 4 && !diverse.some(item => item.id === 'place'), `expected the mass cut to fix the set, got ${ids(diverse)}`);
+    assert(diverse[1].id === 'sleep', `expected MMR to lift the distinct passage, got ${ids(diverse)}`);
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+runTests();
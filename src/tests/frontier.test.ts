@@ -0,0 +1,75 @@
+// src/tests/frontier.test.ts
+import { Frontier } from '../retrieve/frontier';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+function drain<T>(frontier: Frontier<T>): T[] {
+  const items: T[] = [];
+  for (let item = frontier.shift(); item !== undefined; item = frontier.shift()) items.push(item);
+  return items;
+}
+
+function runTests(): void {
+  let passed = 0;
+  let failed = 0;
+
+  const test = (name: string, fn: () => void) => {
+    try {
+      fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('frontier tests:\n');
+
+  // Test 1: without a cap the frontier is a plain FIFO queue
+  test('unbounded frontier keeps insertion order', () => {
+    const frontier = new Frontier<string>();
+    ['a', 'b', 'c'].forEach((item, index) => frontier.push(item, 1 - index));
+    assert(frontier.size === 3, `expected 3 queued, got ${frontier.size}`);
+    assert(drain(frontier).join(',') === 'a,b,c', 'expected FIFO order');
+    assert(frontier.size === 0 && frontier.shift() === undefined, 'expected an empty queue');
+  });
+
+  // Test 2: past the cap the weakest queued item goes, the oldest on ties
+  test('capped frontier drops the lowest score, oldest first on ties', () => {
+    const frontier = new Frontier<string>(2);
+    assert(frontier.push('a', 0.5) === 0 && frontier.push('b', 0.9) === 0, 'expected no drops under the cap');
+    assert(frontier.push('c', 0.5) === 1, 'expected one drop past the cap');
+    assert(drain(frontier).join(',') === 'b,c', 'expected the older of the tied weakest to be dropped');
+
+    const tied = new Frontier<string>(1);
+    tied.push('x', 0.3);
+    tied.push('y', 0.3);
+    assert(drain(tied).join(',') === 'y', 'expected the older tie to go');
+  });
+
+  // Test 3: items already taken never count against the cap or get dropped
+  test('shifted items leave the cap and the heap', () => {
+    const frontier = new Frontier<number>(3);
+    let dropped = 0;
+    const taken: number[] = [];
+    for (let i = 0; i < 5000; i++) {
+      dropped += frontier.push(i, (i * 7919) % 101);
+      if (i % 2 === 0) taken.push(frontier.shift()!);
+      assert(frontier.size <= 3, `size ${frontier.size} exceeded the cap at push ${i}`);
+    }
+    const rest = drain(frontier);
+    assert(taken.length + rest.length + dropped === 5000, 'every item should be taken, drained or dropped exactly once');
+    assert(new Set([...taken, ...rest]).size === taken.length + rest.length, 'no item should come out twice');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+runTests();
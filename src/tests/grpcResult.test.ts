@@ -0,0 +1,95 @@
+// src/tests/grpcResult.test.ts
+import path from 'path';
+import * as protoLoader from '@grpc/proto-loader';
+import { toGrpcResult } from '../server/helpers';
+import type { Result } from '../retrieve';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+// Same loader options as the server, so the round trip matches the wire format
+const packageDef = protoLoader.loadSync(path.join(__dirname, '..', 'proto', 'hippocampus.proto'), {
+  keepCase: true,
+  longs: String,
+  enums: String,
+  defaults: true,
+  oneofs: true,
+});
+const query = (packageDef['hippocampus.Hippocampus'] as protoLoader.ServiceDefinition).Query;
+
+function roundTrip(result: Result): Record<string, unknown> {
+  const bytes = query.responseSerialize({ results: [toGrpcResult(result)] });
+  const decoded = query.responseDeserialize(bytes) as { results: Array<Record<string, unknown>> };
+  return decoded.results[0];
+}
+
+const close = (a: unknown, b: number) => typeof a === 'number' && Math.abs(a - b) < 1e-6;
+
+function runTests(): void {
+  let passed = 0;
+  let failed = 0;
+
+  const test = (name: string, fn: () => void) => {
+    try {
+      fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('gRPC result tests:\n');
+
+  // Test 1: a fully populated result survives serialization field for field
+  test('every result field round-trips through the proto', () => {
+    const result: Result = {
+      text: 'The hippocampus binds episodes together.',
+      source: 'notes.md',
+      score: 0.625,
+      chunk_id: 'chunk-b',
+      graph_boosted: true,
+      retrieval_layer: 'graph',
+      path: ['chunk-a', 'chunk-b'],
+      conflicts: ['chunk-c'],
+      rerankScore: 0.875,
+      confidence: 0.75,
+    };
+    const decoded = roundTrip(result);
+
+    assert(decoded.text === result.text && decoded.source === result.source, 'text and source should survive');
+    assert(decoded.chunk_id === 'chunk-b' && decoded.graph_boosted === true, 'id and graph flag should survive');
+    assert(close(decoded.score, 0.625), `score should survive (float), got ${decoded.score}`);
+    assert(decoded.retrieval_layer === 'graph', `retrieval_layer should survive, got ${decoded.retrieval_layer}`);
+    assert(JSON.stringify(decoded.path) === JSON.stringify(result.path), 'association path should keep its order');
+    assert(JSON.stringify(decoded.conflicts) === JSON.stringify(result.conflicts), 'conflicts should survive');
+    assert(close(decoded.rerank_score, 0.875), `rerank_score should survive, got ${decoded.rerank_score}`);
+    assert(close(decoded.confidence, 0.75), `confidence should survive, got ${decoded.confidence}`);
+  });
+
+  // Test 2: optional scores that were never computed arrive as 0
+  test('unset optional scores serialize as 0', () => {
+    const decoded = roundTrip({
+      text: 'seed',
+      source: 'notes.md',
+      score: 1,
+      chunk_id: 'chunk-a',
+      graph_boosted: false,
+      retrieval_layer: 'vector',
+      path: [],
+      conflicts: [],
+    });
+    assert(decoded.rerank_score === 0 && decoded.confidence === 0, 'missing scores should decode as 0');
+    assert(decoded.retrieval_layer === 'vector' && Array.isArray(decoded.path) && (decoded.path as unknown[]).length === 0, 'layer and empty path should survive');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+runTests();
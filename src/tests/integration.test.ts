@@ -2,15 +2,33 @@
 import fs from 'fs';
 import os from 'os';
 import path from 'path';
+import http from 'http';
+import * as grpc from '@grpc/grpc-js';
+import * as protoLoader from '@grpc/proto-loader';
 import Database from 'better-sqlite3';
 import { v4 as uuidv4 } from 'uuid';
 import { embed } from '../embed';
-import { initDB, db, qdrant, COLLECTION } from '../db';
+import { initDB, db, qdrant, COLLECTION, getGraphVersion, graphVersionMatches, GraphVersionConflictError, getSynchronousMode, setSynchronousMode, isSynchronousMode, checkpointWal, type SynchronousMode } from '../db';
+import { getChunksByIds, getChunkReport, searchChunks, onChunkEvicted, sweepExpiredChunks, deleteChunks, listUnconsolidatedChunks, listRecentChunks, ensureChunkCapacity, selectEvictionCandidates, writeWithinCapacity, CapacityExceededError } from '../db/chunks';
+import { getEdgeWeight, setEdgeWeight, getEdgeAgeDistribution, iterateConnections, validateGraph, adjustEdgeWeights, sweepDeadConnections, freezeEdge, unfreezeEdge, planEdgeAdmission, insertWithinEdgeLimit, countIncidentEdges } from '../db/connections';
+import { iterateGraphExport, importGraphRecords, importGraph, exportAdjacency, dequantizeWeight, InvalidGraphRecordError, type GraphRecord, type ImportGraphResult } from '../db/graphTransfer';
+import { createDatabase, deleteDatabase, clearDatabase, getDatabaseByName, getDatabaseConfig, updateDatabaseConfig } from '../db/memoryDatabase';
+import { getGraphSummary } from '../db/summary';
+import { handleHealthRoutes } from '../server/routes/healthRoute';
+import { grpcServerOptions } from '../server/helpers';
+import type { IngestRequest, IngestResponse } from '../server/helpers';
+import { ingestHandler, exportGraphHandler, importGraphHandler } from '../server/grpc';
 import { parseFile } from '../ingest/parser';
 import { semanticChunkText } from '../ingest/chunking/semantic';
-import { ingest } from '../ingest';
-import { retrieve } from '../retrieve';
-import { reinforceConnections, decayConnections, abstractConcepts, hebbianStrengthen } from '../consolidate';
+import { ingest, ingestText } from '../ingest';
+import { retrieve, retrieveIds, retrieveByVector, multiHopExpand, UnknownSeedError } from '../retrieve';
+import type { ExpansionStats, Result } from '../retrieve';
+import { matchQuality } from '../retrieve/textSearch';
+import type { ChunkSearchStats, EvictionReason } from '../db/chunks';
+import { retrieveGrouped } from '../retrieve/grouped';
+import { getRecallCacheStats } from '../retrieve/cache';
+import { recordChunkAccess, flushChunkAccess } from '../retrieve/accessLog';
+import { consolidateAll, runMaintenance, previewMaintenance, parseConsolidationPhases, reinforceConnections, decayConnections, pruneStaleConnections, abstractConcepts, hebbianStrengthen, hebbianWeight, buildSimilarityEdges } from '../consolidate';
 import { getAssociativeStatus, loadOrInitAssociativeMemory, predictAssociativeScores, trainAssociativeMemory } from '../associative';
 import { ollama } from '../consolidate/concepts';
 
@@ -753,6 +771,2427 @@ Graph-based retrieval can follow those links to related chunks.
   db.prepare(`DELETE FROM memory_databases WHERE name = ?`).run(assocDb);
   console.log('   ✅ Incremental associative training stability working');
 
+  console.log('23. Testing minimum content length filter in retrieval...');
+  const lengthDb = `length_db_${testRunId}`;
+  const lengthMarker = `lengthmarker_${testRunId}`;
+  const shortChunkId = `test_length_short_${testRunId}`;
+  const longChunkId = `test_length_long_${testRunId}`;
+  const shortText = `${lengthMarker} recall`;
+  const longText = `Consolidated notes about ${lengthMarker} describe how repeated recall strengthens hippocampal traces.`;
+  const shortPointId = uuidv4();
+  const longPointId = uuidv4();
+  const lengthTimestamp = new Date().toISOString();
+
+  const [shortVec, longVec] = await Promise.all([embed(shortText), embed(longText)]);
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [
+      { id: shortPointId, vector: shortVec, payload: { text: shortText, source: 'length_test', chunk_id: shortChunkId, database_id: lengthDb } },
+      { id: longPointId, vector: longVec, payload: { text: longText, source: 'length_test', chunk_id: longChunkId, database_id: lengthDb } },
+    ],
+  });
+
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(shortChunkId, shortText, 'length_test', lengthTimestamp, lengthDb);
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(longChunkId, longText, 'length_test', lengthTimestamp, lengthDb);
+
+  const lengthResults = await retrieve(`${lengthMarker} recall hippocampal traces`, {
+    topK: 10,
+    database: lengthDb,
+    includeConflicts: false,
+    minContentLength: shortText.length + 1,
+  });
+  expect(!lengthResults.some(r => r.chunk_id === shortChunkId), 'expected chunk shorter than minContentLength to be excluded');
+  expect(lengthResults.some(r => r.chunk_id === longChunkId), 'expected chunk longer than minContentLength to remain');
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(lengthDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [shortPointId, longPointId] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Minimum content length filter working');
+
+  console.log('24. Testing bulk chunk lookup...');
+  const bulkDb = `bulk_db_${testRunId}`;
+  const bulkChunkA = `test_bulk_a_${testRunId}`;
+  const bulkChunkB = `test_bulk_b_${testRunId}`;
+  const bulkMissing = `test_bulk_missing_${testRunId}`;
+  const bulkTimestamp = new Date().toISOString();
+
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(bulkChunkA, 'bulk lookup chunk A', 'bulk_test', bulkTimestamp, bulkDb);
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(bulkChunkB, 'bulk lookup chunk B', 'bulk_test', bulkTimestamp, bulkDb);
+
+  const bulkRows = getChunksByIds([bulkChunkB, bulkMissing, bulkChunkA], bulkDb);
+  expect(bulkRows.length === 3, `expected one entry per requested id, got ${bulkRows.length}`);
+  expect(bulkRows[0]?.chunk_id === bulkChunkB, 'expected first entry to be chunk B');
+  expect(bulkRows[1] === null, 'expected missing id to map to null in place');
+  expect(bulkRows[2]?.text === 'bulk lookup chunk A', 'expected third entry to be chunk A');
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(bulkDb);
+  console.log('   ✅ Bulk chunk lookup working');
+
+  console.log('25. Testing edge weight getter/setter...');
+  const weightDb = `weight_db_${testRunId}`;
+  const weightChunkA = `test_weight_a_${testRunId}`;
+  const weightChunkB = `test_weight_b_${testRunId}`;
+  const weightEdge = `test_weight_edge_${testRunId}`;
+
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `).run(weightEdge, weightChunkA, weightChunkB, 'supports', 0.3, 0.5, new Date().toISOString(), weightDb);
+
+  const storedWeight = setEdgeWeight(weightChunkA, weightChunkB, 0.72, { database: weightDb });
+  expect(storedWeight === 0.72, `expected set weight to return 0.72, got ${storedWeight}`);
+  expect(getEdgeWeight(weightChunkA, weightChunkB, { database: weightDb, relationship: 'supports' }) === 0.72, 'expected weight to read back as 0.72');
+
+  const clampedHigh = setEdgeWeight(weightChunkA, weightChunkB, 4.2, { database: weightDb });
+  expect(clampedHigh === 1.0, `expected out-of-range weight to clamp to 1.0, got ${clampedHigh}`);
+  const clampedLow = setEdgeWeight(weightChunkA, weightChunkB, -1, { database: weightDb });
+  expect(clampedLow === 0.05, `expected negative weight to clamp to 0.05, got ${clampedLow}`);
+  expect(getEdgeWeight(weightChunkB, weightChunkA, { database: weightDb }) === null, 'expected reverse direction to be a missing edge');
+  expect(setEdgeWeight(weightChunkB, weightChunkA, 0.5, { database: weightDb }) === null, 'expected setting a missing edge to return null');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(weightDb);
+  console.log('   ✅ Edge weight getter/setter working');
+
+  console.log('26. Testing recall cache hits and invalidation...');
+  const cacheDb = `cache_db_${testRunId}`;
+  const cacheChunkId = `test_cache_${testRunId}`;
+  const cacheText = `Recall cache marker ${testRunId} keeps repeated hippocampal queries cheap.`;
+  const cachePointId = uuidv4();
+  const cacheTimestamp = new Date().toISOString();
+
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [{
+      id: cachePointId,
+      vector: await embed(cacheText),
+      payload: { text: cacheText, source: 'cache_test', chunk_id: cacheChunkId, database_id: cacheDb },
+    }],
+  });
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(cacheChunkId, cacheText, 'cache_test', cacheTimestamp, cacheDb);
+
+  const cacheQuery = `recall cache marker ${testRunId}`;
+  await retrieve(cacheQuery, { database: cacheDb, useCache: true });
+  const cacheBefore = getRecallCacheStats();
+  await retrieve(cacheQuery, { database: cacheDb, useCache: true });
+  const cacheAfterRepeat = getRecallCacheStats();
+  expect(cacheAfterRepeat.hits === cacheBefore.hits + 1, 'expected identical recall to be served from cache');
+
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(`${cacheChunkId}_extra`, 'graph mutation', 'cache_test', cacheTimestamp, cacheDb);
+  await retrieve(cacheQuery, { database: cacheDb, useCache: true });
+  const cacheAfterMutation = getRecallCacheStats();
+  expect(cacheAfterMutation.hits === cacheAfterRepeat.hits, 'expected graph mutation to invalidate cached recall');
+  expect(cacheAfterMutation.misses === cacheAfterRepeat.misses + 1, 'expected recall after mutation to miss the cache');
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(cacheDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [cachePointId] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Recall cache working');
+
+  console.log('27. Testing graph version counter...');
+  const versionDb = `version_db_${testRunId}`;
+  const versionChunkA = `test_version_a_${testRunId}`;
+  const versionChunkB = `test_version_b_${testRunId}`;
+  const versionEdge = `test_version_edge_${testRunId}`;
+  const versionTimestamp = new Date().toISOString();
+
+  const v0 = getGraphVersion(versionDb);
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(versionChunkA, 'version chunk A', 'version_test', versionTimestamp, versionDb);
+  const v1 = getGraphVersion(versionDb);
+  expect(v1 > v0, `expected chunk insert to bump version (${v0} -> ${v1})`);
+
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `).run(versionEdge, versionChunkA, versionChunkB, 'supports', 0.5, 0.5, versionTimestamp, versionDb);
+  const v2 = getGraphVersion(versionDb);
+  expect(v2 > v1, `expected connection insert to bump version (${v1} -> ${v2})`);
+
+  db.prepare(`UPDATE chunks SET access_count = access_count + 1, last_accessed = ? WHERE chunk_id = ?`).run(versionTimestamp, versionChunkA);
+  db.prepare(`SELECT * FROM chunks WHERE database_id = ?`).all(versionDb);
+  expect(getGraphVersion(versionDb) === v2, 'expected access bookkeeping and reads to leave version unchanged');
+
+  setEdgeWeight(versionChunkA, versionChunkB, 0.9, { database: versionDb });
+  const v3 = getGraphVersion(versionDb);
+  expect(v3 > v2, `expected weight change to bump version (${v2} -> ${v3})`);
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(versionDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(versionDb);
+  expect(getGraphVersion(versionDb) > v3, 'expected deletes to bump version');
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(versionDb);
+  console.log('   ✅ Graph version counter working');
+
+  console.log('28. Testing expected-version guard...');
+  const casDb = `cas_db_${testRunId}`;
+  const casChunk = `test_cas_${testRunId}`;
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(casChunk, 'cas chunk', 'cas_test', new Date().toISOString(), casDb);
+  const casVersion = getGraphVersion(casDb);
+  expect(graphVersionMatches(undefined, casDb), 'expected missing expected_version to skip the check');
+  expect(graphVersionMatches(casVersion, casDb), 'expected current version to be accepted');
+
+  db.prepare(`UPDATE chunks SET text = ? WHERE chunk_id = ?`).run('cas chunk edited', casChunk);
+  expect(!graphVersionMatches(casVersion, casDb), 'expected stale version to be rejected');
+  expect(graphVersionMatches(getGraphVersion(casDb), casDb), 'expected refreshed version to be accepted');
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(casDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(casDb);
+  console.log('   ✅ Expected-version guard working');
+
+  console.log('29. Testing edge age distribution...');
+  const ageDb = `age_db_${testRunId}`;
+  const ageNow = Date.now();
+  const hourMs = 60 * 60 * 1000;
+  const ageInsert = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  const ageOffsetsHours = [0.5, 0.5, 5, 50, 500];
+  ageOffsetsHours.forEach((hours, index) => {
+    ageInsert.run(
+      `test_age_edge_${index}_${testRunId}`,
+      `test_age_src_${index}_${testRunId}`,
+      `test_age_dst_${index}_${testRunId}`,
+      'related_to',
+      0.5,
+      0.5,
+      new Date(ageNow - hours * hourMs).toISOString(),
+      ageDb,
+    );
+  });
+
+  const ageCounts = getEdgeAgeDistribution([hourMs, 24 * hourMs, 7 * 24 * hourMs], ageDb, ageNow);
+  expect(JSON.stringify(ageCounts) === JSON.stringify([2, 1, 1, 1]), `unexpected age buckets ${JSON.stringify(ageCounts)}`);
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(ageDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(ageDb);
+  console.log('   ✅ Edge age distribution working');
+
+  console.log('30. Testing chunk capacity policies...');
+  const capDb = `cap_db_${testRunId}`;
+  const capIds = [0, 1, 2].map(index => `test_cap_${index}_${testRunId}`);
+  const capInsert = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, last_accessed, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  capIds.forEach((chunkId, index) => {
+    const createdAt = new Date(Date.now() - (3 - index) * 1000).toISOString();
+    capInsert.run(chunkId, `capacity chunk ${index}`, 'cap_test', createdAt, index === 0 ? 5 : 0, index === 0 ? new Date().toISOString() : null, capDb);
+  });
+  capInsert.run(`test_cap_other_${testRunId}`, 'other', 'cap_test', new Date().toISOString(), 0, null, `${capDb}_other`);
+
+  expect(await ensureChunkCapacity(1, capDb, 4, 'reject') === 0, 'expected room below the cap');
+
+  let capRejected = false;
+  try {
+    await ensureChunkCapacity(1, capDb, 3, 'reject');
+  } catch (error) {
+    capRejected = error instanceof CapacityExceededError;
+  }
+  expect(capRejected, 'expected reject policy to throw CapacityExceededError at the cap');
+
+  const capEvicted = await ensureChunkCapacity(1, capDb, 3, 'evict');
+  expect(capEvicted === 1, `expected one eviction, got ${capEvicted}`);
+  const capRemaining = (db.prepare(`SELECT chunk_id FROM chunks WHERE database_id = ?`).all(capDb) as Array<{ chunk_id: string }>)
+    .map(row => row.chunk_id);
+  expect(capRemaining.length === 2, `expected 2 chunks after eviction, got ${capRemaining.length}`);
+  expect(capRemaining.includes(capIds[0]), 'expected the recently accessed chunk to survive eviction');
+  expect(!capRemaining.includes(capIds[1]), 'expected the oldest unaccessed chunk to be evicted');
+  expect(getChunksByIds([`test_cap_other_${testRunId}`], `${capDb}_other`)[0] !== null, 'expected other databases to be untouched');
+
+  db.prepare(`DELETE FROM chunks WHERE database_id IN (?, ?)`).run(capDb, `${capDb}_other`);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id IN (?, ?)`).run(capDb, `${capDb}_other`);
+  console.log('   ✅ Chunk capacity policies working');
+
+  console.log('31. Testing graph export/import round trip...');
+  const exportDb = `export_db_${testRunId}`;
+  const importDb = `import_db_${testRunId}`;
+  const exportChunkA = uuidv4();
+  const exportChunkB = uuidv4();
+  const exportEdge = `test_export_edge_${testRunId}`;
+  const exportTimestamp = new Date().toISOString();
+  const exportChunkInsert = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, database_id)
+    VALUES (?, ?, ?, ?, ?, ?)
+  `);
+  exportChunkInsert.run(exportChunkA, 'Exported chunk about river deltas.', 'export_test', exportTimestamp, 4, exportDb);
+  exportChunkInsert.run(exportChunkB, 'Exported chunk about sediment transport.', 'export_test', exportTimestamp, 0, exportDb);
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `).run(exportEdge, exportChunkA, exportChunkB, 'supports', 0.77, 0.6, exportTimestamp, exportDb);
+
+  const exportedRecords = [...iterateGraphExport(exportDb, 1)];
+  expect(exportedRecords.length === 3, `expected 3 exported records, got ${exportedRecords.length}`);
+  expect(Boolean(exportedRecords[0].chunk && exportedRecords[1].chunk && exportedRecords[2].connection), 'expected chunks before connections');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(exportDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(exportDb);
+
+  const importResult = await importGraphRecords(exportedRecords.map(record => ({ ...record, database: importDb })));
+  expect(importResult.chunks_imported === 2, `expected 2 imported chunks, got ${importResult.chunks_imported}`);
+  expect(importResult.connections_imported === 1, `expected 1 imported connection, got ${importResult.connections_imported}`);
+  expect(getEdgeWeight(exportChunkA, exportChunkB, { database: importDb }) === 0.77, 'expected imported edge weight to be preserved');
+  const importedA = getChunksByIds([exportChunkA], importDb)[0];
+  expect(importedA?.access_count === 4, 'expected imported chunk access_count to be preserved');
+
+  const reimport = await importGraphRecords(exportedRecords.map(record => ({ ...record, database: importDb })));
+  expect(reimport.chunks_skipped === 2 && reimport.connections_skipped === 1, 'expected re-import to skip existing ids');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(importDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(importDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id IN (?, ?)`).run(exportDb, importDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [exportChunkA, exportChunkB] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Graph export/import round trip working');
+
+  console.log('32. Testing configurable hop decay...');
+  const decayDb = `decay_db_${testRunId}`;
+  const decaySeedId = `test_decay_seed_${testRunId}`;
+  const decayMidId = `test_decay_mid_${testRunId}`;
+  const decayFarId = `test_decay_far_${testRunId}`;
+  const decayMarker = `decaymarker_${testRunId}`;
+  const decaySeedText = `Spreading activation ${decayMarker} starts at the cue and flows outward.`;
+  const decayPointId = uuidv4();
+  const decayTimestamp = new Date().toISOString();
+
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [{
+      id: decayPointId,
+      vector: await embed(decaySeedText),
+      payload: { text: decaySeedText, source: 'decay_test', chunk_id: decaySeedId, database_id: decayDb },
+    }],
+  });
+  const decayChunkInsert = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `);
+  decayChunkInsert.run(decaySeedId, decaySeedText, 'decay_test', decayTimestamp, decayDb);
+  decayChunkInsert.run(decayMidId, `Intermediate association for ${decayMarker}.`, 'decay_test', decayTimestamp, decayDb);
+  decayChunkInsert.run(decayFarId, `Distant association two hops from ${decayMarker}.`, 'decay_test', decayTimestamp, decayDb);
+  const decayEdgeInsert = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  decayEdgeInsert.run(`test_decay_edge_1_${testRunId}`, decaySeedId, decayMidId, 'supports', 1.0, 0.9, decayTimestamp, decayDb);
+  decayEdgeInsert.run(`test_decay_edge_2_${testRunId}`, decayMidId, decayFarId, 'supports', 1.0, 0.9, decayTimestamp, decayDb);
+
+  const decayQueryOptions = { topK: 10, database: decayDb, maxHops: 2, includeConflicts: false };
+  const highDecayFar = (await retrieve(decaySeedText, { ...decayQueryOptions, hopDecay: 0.95 }))
+    .find(r => r.chunk_id === decayFarId);
+  const lowDecayFar = (await retrieve(decaySeedText, { ...decayQueryOptions, hopDecay: 0.1 }))
+    .find(r => r.chunk_id === decayFarId);
+  expect(!!highDecayFar, 'expected a high hop decay to reach the two-hop chunk');
+  expect(!lowDecayFar || lowDecayFar.score < (highDecayFar?.score ?? 0), 'expected a low hop decay to weaken the two-hop chunk');
+
+  let decayRejected = false;
+  try {
+    await retrieve(decaySeedText, { ...decayQueryOptions, hopDecay: 1 });
+  } catch {
+    decayRejected = true;
+  }
+  expect(decayRejected, 'expected hopDecay outside (0, 1) to be rejected');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(decayDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(decayDb);
+  db.prepare(`DELETE FROM co_access_events WHERE database_id = ?`).run(decayDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(decayDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [decayPointId] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Configurable hop decay working');
+
+  console.log('33. Testing in-degree boost...');
+  const boostDb = `boost_db_${testRunId}`;
+  const boostPlainId = `test_boost_plain_${testRunId}`;
+  const boostHubId = `test_boost_hub_${testRunId}`;
+  const boostText = `Degree boost ${testRunId} favours well connected memories.`;
+  const boostPlainPoint = uuidv4();
+  const boostHubPoint = uuidv4();
+  const boostTimestamp = new Date().toISOString();
+  const boostVec = await embed(boostText);
+
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [
+      { id: boostPlainPoint, vector: boostVec, payload: { text: boostText, source: 'boost_test', chunk_id: boostPlainId, database_id: boostDb } },
+      { id: boostHubPoint, vector: boostVec, payload: { text: boostText, source: 'boost_test', chunk_id: boostHubId, database_id: boostDb } },
+    ],
+  });
+  const boostChunkInsert = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `);
+  boostChunkInsert.run(boostPlainId, boostText, 'boost_test', boostTimestamp, boostDb);
+  boostChunkInsert.run(boostHubId, boostText, 'boost_test', boostTimestamp, boostDb);
+  const boostEdgeInsert = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  for (let i = 0; i < 5; i++) {
+    boostEdgeInsert.run(`test_boost_edge_${i}_${testRunId}`, `test_boost_feeder_${i}_${testRunId}`, boostHubId, 'related_to', 0.5, 0.5, boostTimestamp, boostDb);
+  }
+
+  const boosted = await retrieve(boostText, { topK: 5, database: boostDb, maxHops: 0, degreeBoost: 1 });
+  const boostedHub = boosted.find(r => r.chunk_id === boostHubId);
+  const boostedPlain = boosted.find(r => r.chunk_id === boostPlainId);
+  expect(boosted[0]?.chunk_id === boostHubId, 'expected high in-degree chunk to rank first with degreeBoost');
+  expect(!boostedPlain || (boostedHub?.score ?? 0) > boostedPlain.score, 'expected hub score above the unconnected duplicate');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(boostDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(boostDb);
+  db.prepare(`DELETE FROM co_access_events WHERE database_id = ?`).run(boostDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(boostDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [boostPlainPoint, boostHubPoint] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ In-degree boost working');
+
+  console.log('34. Testing database clear...');
+  const clearDb = `clear_db_${testRunId}`;
+  const clearChunk = uuidv4();
+  const clearText = `Clearable memory ${testRunId}.`;
+  createDatabase(clearDb, 'clear test db');
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [{ id: clearChunk, vector: await embed(clearText), payload: { text: clearText, source: 'clear_test', chunk_id: clearChunk, database_id: clearDb } }],
+  });
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(clearChunk, clearText, 'clear_test', new Date().toISOString(), clearDb);
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `).run(`test_clear_edge_${testRunId}`, clearChunk, clearChunk, 'related_to', 0.5, 0.5, new Date().toISOString(), clearDb);
+  expect(getGraphVersion(clearDb) > 0, 'expected version to move before clear');
+  const versionBeforeClear = getGraphVersion(clearDb);
+
+  const clearResult = await clearDatabase(clearDb);
+  expect(clearResult.chunks === 1 && clearResult.connections === 1, `unexpected clear counts ${JSON.stringify(clearResult)}`);
+  const clearCounts = db.prepare(`
+    SELECT
+      (SELECT COUNT(*) FROM chunks WHERE database_id = ?) AS chunks,
+      (SELECT COUNT(*) FROM connections WHERE database_id = ?) AS connections,
+      (SELECT COUNT(*) FROM concepts WHERE database_id = ?) AS concepts
+  `).get(clearDb, clearDb, clearDb) as { chunks: number; connections: number; concepts: number };
+  expect(clearCounts.chunks === 0 && clearCounts.connections === 0 && clearCounts.concepts === 0, 'expected all rows gone after clear');
+  expect(getGraphVersion(clearDb) > versionBeforeClear, 'expected clear to move the graph version on, not reset it');
+  expect(!graphVersionMatches(versionBeforeClear, clearDb), 'expected a version from before the clear to be stale');
+  const clearedHits = await qdrant.search(COLLECTION, {
+    vector: await embed(clearText),
+    limit: 5,
+    filter: { must: [{ key: 'database_id', match: { value: clearDb } }] },
+  });
+  expect(clearedHits.length === 0, 'expected no vectors left for the cleared database');
+  expect((await retrieve(clearText, { database: clearDb })).length === 0, 'expected nothing to be recalled after clear');
+
+  deleteDatabase(clearDb);
+  console.log('   ✅ Database clear working');
+
+  console.log('35. Testing exact-depth recall...');
+  const depthDb = `depth_db_${testRunId}`;
+  const depthIds = [0, 1, 2, 3].map(index => `test_depth_${index}_${testRunId}`);
+  const depthSeedText = `Exact depth chain ${testRunId} begins here.`;
+  const depthPointId = uuidv4();
+  const depthTimestamp = new Date().toISOString();
+
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [{
+      id: depthPointId,
+      vector: await embed(depthSeedText),
+      payload: { text: depthSeedText, source: 'depth_test', chunk_id: depthIds[0], database_id: depthDb },
+    }],
+  });
+  const depthChunkInsert = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `);
+  depthIds.forEach((chunkId, index) => {
+    depthChunkInsert.run(chunkId, index === 0 ? depthSeedText : `Chain link ${index} for ${testRunId}.`, 'depth_test', depthTimestamp, depthDb);
+  });
+  const depthEdgeInsert = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  for (let i = 0; i < depthIds.length - 1; i++) {
+    depthEdgeInsert.run(`test_depth_edge_${i}_${testRunId}`, depthIds[i], depthIds[i + 1], 'supports', 1.0, 0.9, depthTimestamp, depthDb);
+  }
+
+  const depthResults = await retrieve(depthSeedText, { topK: 10, database: depthDb, maxHops: 3, exactDepth: 2, includeConflicts: false });
+  expect(depthResults.length === 1, `expected a single exact-depth result, got ${depthResults.length}`);
+  expect(depthResults[0]?.chunk_id === depthIds[2], 'expected only the chunk two hops away');
+
+  // A weak shortcut reaches the target in two hops; a cap of one drops it from
+  // the queue, which used to leave the target at depth 3 via the chain
+  const [depthShort, depthTarget] = ['short', 'target'].map(name => `test_depth_${name}_${testRunId}`);
+  depthChunkInsert.run(depthShort, `Depth shortcut for ${testRunId}.`, 'depth_test', depthTimestamp, depthDb);
+  depthChunkInsert.run(depthTarget, `Depth target for ${testRunId}.`, 'depth_test', depthTimestamp, depthDb);
+  depthEdgeInsert.run(`test_depth_short_${testRunId}`, depthIds[0], depthShort, 'supports', 0.5, 0.9, depthTimestamp, depthDb);
+  depthEdgeInsert.run(`test_depth_short_target_${testRunId}`, depthShort, depthTarget, 'supports', 1.0, 0.9, depthTimestamp, depthDb);
+  depthEdgeInsert.run(`test_depth_chain_target_${testRunId}`, depthIds[2], depthTarget, 'supports', 1.0, 0.9, depthTimestamp, depthDb);
+  const depthIdsAt = async (maxFrontier: number) => (await retrieve(depthSeedText, { topK: 10, database: depthDb, maxHops: 3, exactDepth: 2, includeConflicts: false, recordAccess: false, maxFrontier }))
+    .map(result => result.chunk_id).sort().join(',');
+  const uncappedDepth = await depthIdsAt(0);
+  expect(uncappedDepth === [depthIds[2], depthTarget].sort().join(','), `expected the chain link and the shortcut target at depth 2, got ${uncappedDepth}`);
+  expect(await depthIdsAt(1) === uncappedDepth, 'expected a frontier cap to leave exact-depth results unchanged');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(depthDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(depthDb);
+  db.prepare(`DELETE FROM co_access_events WHERE database_id = ?`).run(depthDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(depthDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [depthPointId] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Exact-depth recall working');
+
+  console.log('36. Testing deterministic eviction order...');
+  const evictDb = `evict_db_${testRunId}`;
+  const evictTimestamp = new Date().toISOString();
+  const evictIds = ['d', 'b', 'a', 'c'].map(letter => `test_evict_${letter}_${testRunId}`);
+  const evictInsert = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `);
+  evictIds.forEach(chunkId => evictInsert.run(chunkId, `evict ${chunkId}`, 'evict_test', evictTimestamp, evictDb));
+
+  const firstPick = selectEvictionCandidates(2, evictDb);
+  const secondPick = selectEvictionCandidates(2, evictDb);
+  expect(JSON.stringify(firstPick) === JSON.stringify(secondPick), 'expected identical eviction candidates across runs');
+  expect(
+    JSON.stringify(firstPick) === JSON.stringify([`test_evict_a_${testRunId}`, `test_evict_b_${testRunId}`]),
+    `expected ties broken by chunk id, got ${JSON.stringify(firstPick)}`,
+  );
+
+  let evictCapped = false;
+  try {
+    await ensureChunkCapacity(3, evictDb, 4, 'evict', 2);
+  } catch (error) {
+    evictCapped = error instanceof CapacityExceededError;
+  }
+  expect(evictCapped, 'expected overflow beyond maxEvictions to be rejected');
+  expect((db.prepare(`SELECT COUNT(*) AS total FROM chunks WHERE database_id = ?`).get(evictDb) as { total: number }).total === 4, 'expected no partial eviction when capped');
+  expect(await ensureChunkCapacity(2, evictDb, 4, 'evict', 2) === 2, 'expected eviction within the cap to proceed');
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(evictDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(evictDb);
+  console.log('   ✅ Deterministic eviction order working');
+
+  console.log('37. Testing chunk strength report...');
+  const reportDb = `report_db_${testRunId}`;
+  const [reportHub, reportIn, reportOut, reportFar] = ['hub', 'in', 'out', 'far'].map(name => `test_report_${name}_${testRunId}`);
+  const reportTimestamp = new Date().toISOString();
+  const reportChunkInsert = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, last_accessed, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  reportChunkInsert.run(reportHub, 'report hub', 'report_test', reportTimestamp, 7, reportTimestamp, reportDb);
+  for (const chunkId of [reportIn, reportOut, reportFar]) {
+    reportChunkInsert.run(chunkId, `report ${chunkId}`, 'report_test', reportTimestamp, 0, null, reportDb);
+  }
+  const reportEdgeInsert = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  reportEdgeInsert.run(`test_report_edge_1_${testRunId}`, reportIn, reportHub, 'supports', 0.4, 0.5, reportTimestamp, reportDb);
+  reportEdgeInsert.run(`test_report_edge_2_${testRunId}`, reportHub, reportOut, 'supports', 0.8, 0.5, reportTimestamp, reportDb);
+  reportEdgeInsert.run(`test_report_edge_3_${testRunId}`, reportOut, reportFar, 'supports', 0.9, 0.5, reportTimestamp, reportDb);
+  db.prepare(`
+    INSERT INTO concepts (concept_id, label, summary, member_chunks, created_at, last_updated, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `).run(`test_report_concept_${testRunId}`, 'report', 'report concept', JSON.stringify([reportHub, reportIn]), reportTimestamp, reportTimestamp, reportDb);
+
+  const hubReport = getChunkReport(reportHub, reportDb);
+  expect(!!hubReport, 'expected a report for an existing chunk');
+  expect(hubReport?.access_count === 7 && hubReport?.last_accessed === reportTimestamp, 'expected access fields to match');
+  expect(hubReport?.in_degree === 1 && hubReport?.out_degree === 1, 'expected one incoming and one outgoing edge');
+  expect(Math.abs((hubReport?.avg_edge_weight ?? 0) - 0.6) < 1e-9, `expected avg weight 0.6, got ${hubReport?.avg_edge_weight}`);
+  expect(hubReport?.strongest_neighbor === reportOut && hubReport?.strongest_weight === 0.8, 'expected the outgoing edge to be strongest');
+  expect(hubReport?.consolidated === true, 'expected concept membership to mark chunk consolidated');
+  const farReport = getChunkReport(reportFar, reportDb);
+  expect(farReport?.consolidated === false && farReport?.in_degree === 1 && farReport?.out_degree === 0, 'expected leaf chunk report');
+  expect(getChunkReport(`missing_${testRunId}`, reportDb) === null, 'expected null report for unknown chunk');
+
+  db.prepare(`DELETE FROM concepts WHERE database_id = ?`).run(reportDb);
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(reportDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(reportDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(reportDb);
+  console.log('   ✅ Chunk strength report working');
+
+  console.log('38. Testing connection listing...');
+  const listDb = `list_db_${testRunId}`;
+  const listTimestamp = new Date().toISOString();
+  const listEdgeInsert = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  const listEdges: Array<[string, number]> = [['supports', 0.9], ['supports', 0.2], ['related_to', 0.6], ['contradicts', 0.7], ['related_to', 0.1]];
+  listEdges.forEach(([relationship, weight], index) => {
+    listEdgeInsert.run(`test_list_edge_${index}_${testRunId}`, `test_list_src_${index}_${testRunId}`, `test_list_dst_${index}_${testRunId}`, relationship, weight, 0.5, listTimestamp, listDb);
+  });
+
+  const listTotal = (db.prepare(`SELECT COUNT(*) AS total FROM connections WHERE database_id = ?`).get(listDb) as { total: number }).total;
+  expect([...iterateConnections({ database: listDb, pageSize: 2 })].length === listTotal, 'expected paged listing to stream every connection');
+  const listStrong = [...iterateConnections({ database: listDb, minWeight: 0.5 })];
+  expect(listStrong.length === 3 && listStrong.every(edge => edge.weight >= 0.5), 'expected min weight filter to keep 3 edges');
+  const listSupports = [...iterateConnections({ database: listDb, minWeight: 0.5, relationships: ['supports', 'related_to'] })];
+  expect(listSupports.length === 2, `expected combined filters to keep 2 edges, got ${listSupports.length}`);
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(listDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(listDb);
+  console.log('   ✅ Connection listing working');
+
+  console.log('39. Testing batched Hebbian co-access counts...');
+  const batchDb = `hebb_batch_db_${testRunId}`;
+  const batchChunkA = `test_hebb_batch_a_${testRunId}`;
+  const batchChunkB = `test_hebb_batch_b_${testRunId}`;
+  const batchEdge = `test_hebb_batch_edge_${testRunId}`;
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, access_count, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+  `).run(batchEdge, batchChunkA, batchChunkB, 'co_accessed', 0.5, 0.5, new Date().toISOString(), 2, batchDb);
+
+  const batchSince = Date.now() - 1;
+  const batchEventInsert = db.prepare(`
+    INSERT INTO co_access_events (event_id, chunk_ids, query_hash, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `);
+  for (let i = 0; i < 3; i++) {
+    const ids = i % 2 === 0 ? [batchChunkA, batchChunkB] : [batchChunkB, batchChunkA];
+    batchEventInsert.run(uuidv4(), JSON.stringify(ids), `hebb_batch_${i}`, Date.now() + i, batchDb);
+  }
+  await hebbianStrengthen(batchSince);
+
+  const batchRow = db.prepare(`SELECT weight, access_count FROM connections WHERE edge_id = ?`).get(batchEdge) as { weight: number; access_count: number };
+  expect(batchRow.access_count === 5, `expected access_count to grow by 3, got ${batchRow.access_count}`);
+  expect(Math.abs(batchRow.weight - hebbianWeight(0.5, 3)) < 1e-9, `expected weight ${hebbianWeight(0.5, 3)}, got ${batchRow.weight}`);
+
+  db.prepare(`DELETE FROM co_access_events WHERE database_id = ?`).run(batchDb);
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(batchDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(batchDb);
+  console.log('   ✅ Batched Hebbian co-access counts working');
+
+  console.log('40. Testing weighted graph import...');
+  const weightedDb = `weighted_db_${testRunId}`;
+  const weightedA = uuidv4();
+  const weightedB = uuidv4();
+  const weightedC = uuidv4();
+
+  let weightedRejected = false;
+  try {
+    await importGraph({
+      chunks: [{ chunk_id: weightedA, text: 'Weighted import node A.' }],
+      connections: [{ source_chunk: weightedA, target_chunk: `missing_${testRunId}`, weight: 0.5 }],
+    }, weightedDb);
+  } catch {
+    weightedRejected = true;
+  }
+  expect(weightedRejected, 'expected unknown endpoints to reject the import');
+  expect(getChunksByIds([weightedA], weightedDb)[0] === null, 'expected a rejected import to write nothing');
+
+  const weightedResult = await importGraph({
+    chunks: [
+      { chunk_id: weightedA, text: 'Weighted import node A about glaciers.' },
+      { chunk_id: weightedB, text: 'Weighted import node B about moraines.' },
+      { chunk_id: weightedC, text: 'Weighted import node C about fjords.' },
+    ],
+    connections: [
+      { source_chunk: weightedA, target_chunk: weightedB, relationship: 'supports', weight: 0.83 },
+      { source_chunk: weightedB, target_chunk: weightedC, relationship: 'example_of', weight: 0.12 },
+    ],
+  }, weightedDb);
+  expect(weightedResult.chunks_imported === 3 && weightedResult.connections_imported === 2, `unexpected import result ${JSON.stringify(weightedResult)}`);
+  expect(getEdgeWeight(weightedA, weightedB, { relationship: 'supports', database: weightedDb }) === 0.83, 'expected imported weight 0.83 to be kept');
+  expect(getEdgeWeight(weightedB, weightedC, { relationship: 'example_of', database: weightedDb }) === 0.12, 'expected imported weight 0.12 to be kept');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(weightedDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(weightedDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(weightedDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [weightedA, weightedB, weightedC] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Weighted graph import working');
+
+  console.log('41. Testing side-effect-free retrieval...');
+  const readOnlyDb = `readonly_db_${testRunId}`;
+  const readOnlyChunk = `test_readonly_${testRunId}`;
+  const readOnlyText = `Read only recall ${testRunId} must not touch access counters.`;
+  const readOnlyPoint = uuidv4();
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [{ id: readOnlyPoint, vector: await embed(readOnlyText), payload: { text: readOnlyText, source: 'readonly_test', chunk_id: readOnlyChunk, database_id: readOnlyDb } }],
+  });
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, database_id)
+    VALUES (?, ?, ?, ?, ?, ?)
+  `).run(readOnlyChunk, readOnlyText, 'readonly_test', new Date().toISOString(), 3, readOnlyDb);
+  const readOnlyEvents = () => (db.prepare(`SELECT COUNT(*) AS total FROM co_access_events WHERE database_id = ?`).get(readOnlyDb) as { total: number }).total;
+  const readOnlyAccess = () => (db.prepare(`SELECT access_count FROM chunks WHERE chunk_id = ?`).get(readOnlyChunk) as { access_count: number }).access_count;
+
+  const readOnlyResults = await retrieve(readOnlyText, { database: readOnlyDb, recordAccess: false });
+  expect(readOnlyResults.some(r => r.chunk_id === readOnlyChunk), 'expected the chunk to be recalled');
+  expect(readOnlyAccess() === 3, 'expected access_count unchanged with recordAccess=false');
+  expect(readOnlyEvents() === 0, 'expected no co-access event with recordAccess=false');
+
+  await retrieve(readOnlyText, { database: readOnlyDb });
+  expect(readOnlyAccess() === 4, 'expected default retrieval to keep recording access');
+
+  db.prepare(`DELETE FROM co_access_events WHERE database_id = ?`).run(readOnlyDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(readOnlyDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(readOnlyDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [readOnlyPoint] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Side-effect-free retrieval working');
+
+  console.log('42. Testing unconsolidated chunk listing...');
+  const unconDb = `uncon_db_${testRunId}`;
+  const [unconA, unconB, unconC, unconD, unconLone] = ['a', 'b', 'c', 'd', 'lone'].map(name => `test_uncon_${name}_${testRunId}`);
+  const unconTimestamp = new Date().toISOString();
+  const unconChunkInsert = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `);
+  for (const chunkId of [unconA, unconB, unconC, unconD, unconLone]) {
+    unconChunkInsert.run(chunkId, `uncon ${chunkId}`, 'uncon_test', unconTimestamp, unconDb);
+  }
+  const unconEdgeInsert = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  unconEdgeInsert.run(`test_uncon_edge_ab_${testRunId}`, unconA, unconB, 'supports', 0.8, 0.5, unconTimestamp, unconDb);
+  unconEdgeInsert.run(`test_uncon_edge_cd_${testRunId}`, unconC, unconD, 'supports', 0.4, 0.5, unconTimestamp, unconDb);
+  db.prepare(`
+    INSERT INTO concepts (concept_id, label, summary, member_chunks, created_at, last_updated, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `).run(`test_uncon_concept_${testRunId}`, 'stable', 'stable cluster', JSON.stringify([unconA, unconB]), unconTimestamp, unconTimestamp, unconDb);
+
+  const unconsolidated = listUnconsolidatedChunks(unconDb).sort();
+  expect(JSON.stringify(unconsolidated) === JSON.stringify([unconC, unconD].sort()), `expected only the unclustered pair, got ${JSON.stringify(unconsolidated)}`);
+
+  db.prepare(`DELETE FROM concepts WHERE database_id = ?`).run(unconDb);
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(unconDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(unconDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(unconDb);
+  console.log('   ✅ Unconsolidated chunk listing working');
+
+  console.log('43. Testing hub protection during eviction...');
+  const hubDb = `hub_db_${testRunId}`;
+  const hubStale = `test_hub_stale_${testRunId}`;
+  const hubFresh = `test_hub_fresh_${testRunId}`;
+  const hubChunkInsert = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `);
+  hubChunkInsert.run(hubStale, 'stale but central', 'hub_test', new Date(Date.now() - 60_000).toISOString(), hubDb);
+  hubChunkInsert.run(hubFresh, 'fresh leaf', 'hub_test', new Date().toISOString(), hubDb);
+  const hubEdgeInsert = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  for (let i = 0; i < 3; i++) {
+    hubEdgeInsert.run(`test_hub_edge_${i}_${testRunId}`, `test_hub_spoke_${i}_${testRunId}`, hubStale, 'related_to', 0.5, 0.5, new Date().toISOString(), hubDb);
+  }
+
+  expect(selectEvictionCandidates(1, hubDb, 0)[0] === hubStale, 'expected stale hub to be first victim without protection');
+  expect(selectEvictionCandidates(1, hubDb, 3)[0] === hubFresh, 'expected protected hub to be skipped');
+  expect(selectEvictionCandidates(2, hubDb, 3).length === 1, 'expected only unprotected chunks to be candidates');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(hubDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(hubDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(hubDb);
+  console.log('   ✅ Hub protection during eviction working');
+
+  console.log('44. Testing placeholder endpoints on import...');
+  const placeholderDb = `placeholder_db_${testRunId}`;
+  const placeholderKnown = uuidv4();
+  const placeholderLater = uuidv4();
+
+  const edgeFirst = await importGraph({
+    chunks: [{ chunk_id: placeholderKnown, text: 'Placeholder import known node about tides.' }],
+    connections: [{ source_chunk: placeholderKnown, target_chunk: placeholderLater, relationship: 'supports', weight: 0.6 }],
+  }, placeholderDb, 'placeholder');
+  expect(edgeFirst.placeholders_created === 1 && edgeFirst.connections_imported === 1, `unexpected placeholder import result ${JSON.stringify(edgeFirst)}`);
+  const placeholderRow = getChunksByIds([placeholderLater], placeholderDb)[0];
+  expect(placeholderRow !== null && placeholderRow.text === '', 'expected an empty placeholder chunk');
+
+  const filled = await importGraph({
+    chunks: [{ chunk_id: placeholderLater, text: 'Placeholder import late node about currents.' }],
+  }, placeholderDb);
+  expect(filled.chunks_imported === 1, `expected the placeholder to be filled, got ${JSON.stringify(filled)}`);
+  const filledRow = db.prepare(`SELECT text, metadata FROM chunks WHERE chunk_id = ?`).get(placeholderLater) as { text: string; metadata: string };
+  expect(filledRow.text.includes('currents') && !filledRow.metadata.includes('placeholder'), 'expected placeholder content and flag to be replaced');
+  expect(getEdgeWeight(placeholderKnown, placeholderLater, { relationship: 'supports', database: placeholderDb }) === 0.6, 'expected the early edge to survive filling');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(placeholderDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(placeholderDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(placeholderDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [placeholderKnown, placeholderLater] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Placeholder endpoints working');
+
+  console.log('45. Testing graph validation...');
+  const validateDb = `validate_db_${testRunId}`;
+  const validateA = `test_validate_a_${testRunId}`;
+  const validateB = `test_validate_b_${testRunId}`;
+  const validateNow = new Date().toISOString();
+  const insertValidateChunk = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `);
+  insertValidateChunk.run(validateA, 'Validation chunk A.', 'validate_test', validateNow, validateDb);
+  insertValidateChunk.run(validateB, 'Validation chunk B.', 'validate_test', validateNow, validateDb);
+  expect(validateGraph(validateDb).length === 0, 'expected an empty graph to validate cleanly');
+
+  const insertValidateEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertValidateEdge.run(`validate_e1_${testRunId}`, validateA, `missing_${testRunId}`, 'supports', 0.5, validateNow, validateDb);
+  insertValidateEdge.run(`validate_e2_${testRunId}`, validateA, validateA, 'related_to', 0.5, validateNow, validateDb);
+  insertValidateEdge.run(`validate_e3_${testRunId}`, validateA, validateB, 'supports', 0.5, validateNow, validateDb);
+  insertValidateEdge.run(`validate_e4_${testRunId}`, validateB, validateA, 'supports', 0.5, validateNow, validateDb);
+
+  const validateIssues = validateGraph(validateDb);
+  const issueOf = (kind: string) => validateIssues.filter(issue => issue.kind === kind);
+  expect(issueOf('dangling_edge').length === 1 && issueOf('dangling_edge')[0].edge_id === `validate_e1_${testRunId}`, 'expected the dangling edge to be reported');
+  expect(issueOf('self_loop').length === 1, 'expected the self-loop to be reported');
+  expect(issueOf('duplicate_reciprocal').length === 1, 'expected one reciprocal pair to be reported once');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(validateDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(validateDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(validateDb);
+  console.log('   ✅ Graph validation working');
+
+  console.log('46. Testing chunk TTL...');
+  const ttlDb = `ttl_db_${testRunId}`;
+  const ttlExpired = `test_ttl_expired_${testRunId}`;
+  const ttlLive = `test_ttl_live_${testRunId}`;
+  const ttlText = `Expiring cache entry ${testRunId} about lighthouse keepers.`;
+  const ttlPoints = [uuidv4(), uuidv4()];
+  const ttlVector = await embed(ttlText);
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [
+      { id: ttlPoints[0], vector: ttlVector, payload: { text: ttlText, source: 'ttl_test', chunk_id: ttlExpired, database_id: ttlDb } },
+      { id: ttlPoints[1], vector: ttlVector, payload: { text: `${ttlText} Still fresh.`, source: 'ttl_test', chunk_id: ttlLive, database_id: ttlDb } },
+    ],
+  });
+  const insertTtlChunk = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, expires_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?)
+  `);
+  insertTtlChunk.run(ttlExpired, ttlText, 'ttl_test', new Date().toISOString(), new Date(Date.now() - 60_000).toISOString(), ttlDb);
+  insertTtlChunk.run(ttlLive, `${ttlText} Still fresh.`, 'ttl_test', new Date().toISOString(), new Date(Date.now() + 3_600_000).toISOString(), ttlDb);
+
+  const ttlResults = await retrieve(ttlText, { database: ttlDb, recordAccess: false });
+  expect(!ttlResults.some(r => r.chunk_id === ttlExpired), 'expected the expired chunk to be hidden before the sweep');
+  expect(ttlResults.some(r => r.chunk_id === ttlLive), 'expected the unexpired chunk to be recalled');
+  const ttlByVector = await retrieveByVector(ttlVector, 10, ttlDb);
+  expect(!ttlByVector.some(r => r.chunk_id === ttlExpired) && ttlByVector.some(r => r.chunk_id === ttlLive), 'expected vector-only recall to hide the expired chunk');
+  const ttlLiteral = searchChunks('lighthouse keepers', { kind: 'substring' }, ttlDb);
+  expect(!ttlLiteral.some(hit => hit.chunk_id === ttlExpired) && ttlLiteral.some(hit => hit.chunk_id === ttlLive), 'expected literal search to hide the expired chunk');
+  expect(!listRecentChunks(10, undefined, ttlDb).some(row => row.chunk_id === ttlExpired), 'expected the recent feed to hide the expired chunk');
+  expect(getChunksByIds([ttlExpired, ttlLive], ttlDb).map(row => row !== null).join(',') === 'false,true', 'expected batch lookup to hide the expired chunk');
+  expect(getChunksByIds([ttlExpired], ttlDb, { includeExpired: true })[0] !== null, 'expected includeExpired to still find the expired chunk');
+  expect(getChunkReport(ttlExpired, ttlDb) === null && getChunkReport(ttlLive, ttlDb) !== null, 'expected the chunk report to hide the expired chunk');
+  buildSimilarityEdges(0.1, 2, ttlDb);
+  const ttlEdges = db.prepare(`SELECT COUNT(*) AS n FROM connections WHERE database_id = ? AND (source_chunk = ? OR target_chunk = ?)`)
+    .get(ttlDb, ttlExpired, ttlExpired) as { n: number };
+  expect(ttlEdges.n === 0, `expected no similarity edges to the expired chunk, got ${ttlEdges.n}`);
+
+  // A cached result must not outlive the earliest expiry among its chunks
+  db.prepare(`UPDATE chunks SET expires_at = ? WHERE chunk_id = ?`).run(new Date(Date.now() + 1_000).toISOString(), ttlLive);
+  const ttlCached = await retrieve(ttlText, { database: ttlDb, recordAccess: false, useCache: true });
+  expect(ttlCached.some(r => r.chunk_id === ttlLive), 'expected the soon-expiring chunk to be recalled and cached');
+  await new Promise(resolve => setTimeout(resolve, 1_100));
+  const ttlAfterExpiry = await retrieve(ttlText, { database: ttlDb, recordAccess: false, useCache: true });
+  expect(!ttlAfterExpiry.some(r => r.chunk_id === ttlLive), 'expected the cache entry to end when its chunk expired');
+  db.prepare(`UPDATE chunks SET expires_at = ? WHERE chunk_id = ?`).run(new Date(Date.now() + 3_600_000).toISOString(), ttlLive);
+
+  const ttlSwept = await sweepExpiredChunks(ttlDb);
+  expect(ttlSwept === 1, `expected one expired chunk to be swept, got ${ttlSwept}`);
+  expect(getChunksByIds([ttlExpired, ttlLive], ttlDb, { includeExpired: true }).map(row => row !== null).join(',') === 'false,true', 'expected only the expired chunk to be removed');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(ttlDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(ttlDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(ttlDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: ttlPoints });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Chunk TTL working');
+
+  console.log('47. Testing batch chunk deletion...');
+  const batchDeleteDb = `batch_delete_db_${testRunId}`;
+  const batchDeleteIds = [0, 1, 2].map(index => `test_batch_delete_${index}_${testRunId}`);
+  const batchDeleteNow = new Date().toISOString();
+  for (const chunkId of batchDeleteIds) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(chunkId, `Batch delete chunk ${chunkId}`, 'batch_delete_test', batchDeleteNow, batchDeleteDb);
+  }
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `).run(`batch_delete_edge_${testRunId}`, batchDeleteIds[0], batchDeleteIds[2], 'related_to', 0.4, batchDeleteNow, batchDeleteDb);
+  const insertBatchConcept = db.prepare(`
+    INSERT OR REPLACE INTO concepts (concept_id, label, summary, member_chunks, created_at, last_updated, version, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, 1, ?)
+  `);
+  const keptConcept = `batch_delete_kept_concept_${testRunId}`;
+  const emptiedConcept = `batch_delete_emptied_concept_${testRunId}`;
+  insertBatchConcept.run(keptConcept, 'kept', 'Keeps one member.', JSON.stringify([batchDeleteIds[0], batchDeleteIds[2]]), batchDeleteNow, batchDeleteNow, batchDeleteDb);
+  insertBatchConcept.run(emptiedConcept, 'emptied', 'Loses every member.', JSON.stringify([batchDeleteIds[0], batchDeleteIds[1]]), batchDeleteNow, batchDeleteNow, batchDeleteDb);
+
+  const missingDeleteId = `missing_batch_delete_${testRunId}`;
+  const batchDeleted = await deleteChunks([batchDeleteIds[0], missingDeleteId, batchDeleteIds[1]], batchDeleteDb);
+  expect(batchDeleted.deleted_count === 2, `expected 2 deletions, got ${batchDeleted.deleted_count}`);
+  expect(batchDeleted.results.map(result => `${result.chunk_id}:${result.deleted}`).join(',')
+    === `${batchDeleteIds[0]}:true,${missingDeleteId}:false,${batchDeleteIds[1]}:true`, 'expected per-id results in request order');
+  expect(getChunksByIds(batchDeleteIds, batchDeleteDb).map(row => row !== null).join(',') === 'false,false,true', 'expected only the requested chunks to be gone');
+  const batchDeleteEdges = db.prepare(`SELECT COUNT(*) AS total FROM connections WHERE database_id = ?`).get(batchDeleteDb) as { total: number };
+  expect(batchDeleteEdges.total === 0, 'expected edges touching deleted chunks to be removed');
+  const batchConcepts = db.prepare(`SELECT concept_id, member_chunks, version FROM concepts WHERE database_id = ?`).all(batchDeleteDb) as Array<{ concept_id: string; member_chunks: string; version: number }>;
+  expect(batchConcepts.length === 1 && batchConcepts[0].concept_id === keptConcept, 'expected a concept left without members to be dropped');
+  expect(batchConcepts[0].member_chunks === JSON.stringify([batchDeleteIds[2]]) && batchConcepts[0].version === 2, 'expected deleted chunks to leave member_chunks and the version to move on');
+
+  db.prepare(`DELETE FROM concepts WHERE database_id = ?`).run(batchDeleteDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(batchDeleteDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(batchDeleteDb);
+  console.log('   ✅ Batch chunk deletion working');
+
+  console.log('48. Testing recall grouped by concept...');
+  const groupedDb = `grouped_db_${testRunId}`;
+  const groupedChunks = [
+    { id: `test_grouped_tide_a_${testRunId}`, text: `Grouped recall ${testRunId}: ocean tides follow the moon.`, concept: `grouped_tides_${testRunId}` },
+    { id: `test_grouped_tide_b_${testRunId}`, text: `Grouped recall ${testRunId}: spring tides are the largest tides.`, concept: `grouped_tides_${testRunId}` },
+    { id: `test_grouped_volcano_${testRunId}`, text: `Grouped recall ${testRunId}: volcanoes vent magma.`, concept: `grouped_volcanoes_${testRunId}` },
+  ];
+  const groupedPoints = groupedChunks.map(() => uuidv4());
+  const groupedNow = new Date().toISOString();
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: await Promise.all(groupedChunks.map(async (chunk, index) => ({
+      id: groupedPoints[index],
+      vector: await embed(chunk.text),
+      payload: { text: chunk.text, source: 'grouped_test', chunk_id: chunk.id, database_id: groupedDb },
+    }))),
+  });
+  for (const chunk of groupedChunks) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(chunk.id, chunk.text, 'grouped_test', groupedNow, groupedDb);
+  }
+  for (const conceptId of [...new Set(groupedChunks.map(chunk => chunk.concept))]) {
+    const members = groupedChunks.filter(chunk => chunk.concept === conceptId).map(chunk => chunk.id);
+    db.prepare(`
+      INSERT OR REPLACE INTO concepts (concept_id, label, summary, member_chunks, created_at, last_updated, database_id)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `).run(conceptId, conceptId.includes('tides') ? '' : 'Volcanoes', 'grouped test concept', JSON.stringify(members), groupedNow, groupedNow, groupedDb);
+  }
+
+  const recallGroups = await retrieveGrouped(`Grouped recall ${testRunId}`, { database: groupedDb, topK: 10, recordAccess: false });
+  const tideGroup = recallGroups.find(group => group.concept_id === `grouped_tides_${testRunId}`);
+  const volcanoGroup = recallGroups.find(group => group.concept_id === `grouped_volcanoes_${testRunId}`);
+  expect(Boolean(tideGroup) && Boolean(volcanoGroup), 'expected one group per concept');
+  expect(tideGroup!.results.every(result => result.chunk_id.includes('_tide_')), 'expected only tide chunks in the tide group');
+  expect(volcanoGroup!.results.length === 1 && volcanoGroup!.label === 'Volcanoes', 'expected the stored concept label to be kept');
+  expect(tideGroup!.label.includes('tides'), `expected a shared-token label for the unlabeled concept, got "${tideGroup!.label}"`);
+
+  db.prepare(`DELETE FROM concepts WHERE database_id = ?`).run(groupedDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(groupedDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(groupedDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: groupedPoints });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Grouped recall working');
+
+  console.log('49. Testing minimum edge activation for traversal...');
+  const activationDb = `activation_db_${testRunId}`;
+  const activationSeed = `test_activation_seed_${testRunId}`;
+  const activationNoisy = `test_activation_noisy_${testRunId}`;
+  const activationSolid = `test_activation_solid_${testRunId}`;
+  const activationText = `Activation threshold seed ${testRunId}.`;
+  const activationPoint = uuidv4();
+  const activationNow = new Date().toISOString();
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [{ id: activationPoint, vector: await embed(activationText), payload: { text: activationText, source: 'activation_test', chunk_id: activationSeed, database_id: activationDb } }],
+  });
+  const insertActivationChunk = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `);
+  insertActivationChunk.run(activationSeed, activationText, 'activation_test', activationNow, activationDb);
+  insertActivationChunk.run(activationNoisy, `Once-activated neighbour ${testRunId}.`, 'activation_test', activationNow, activationDb);
+  insertActivationChunk.run(activationSolid, `Frequently activated neighbour ${testRunId}.`, 'activation_test', activationNow, activationDb);
+  const insertActivationEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, access_count, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertActivationEdge.run(`activation_noisy_${testRunId}`, activationSeed, activationNoisy, 'supports', 0.9, 1, activationNow, activationDb);
+  insertActivationEdge.run(`activation_solid_${testRunId}`, activationSeed, activationSolid, 'supports', 0.9, 8, activationNow, activationDb);
+
+  const activationAll = await retrieve(activationText, { database: activationDb, maxHops: 1, includeConflicts: false, recordAccess: false });
+  expect(activationAll.some(r => r.chunk_id === activationNoisy), 'expected the once-activated edge to be traversed by default');
+  const activationFiltered = await retrieve(activationText, { database: activationDb, maxHops: 1, includeConflicts: false, recordAccess: false, minEdgeAccessCount: 3 });
+  expect(!activationFiltered.some(r => r.chunk_id === activationNoisy), 'expected the once-activated edge to be skipped');
+  expect(activationFiltered.some(r => r.chunk_id === activationSolid), 'expected the frequently activated edge to survive');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(activationDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(activationDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(activationDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [activationPoint] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Minimum edge activation working');
+
+  console.log('50. Testing decay exemption for in-focus edges...');
+  const focusDb = `focus_db_${testRunId}`;
+  const focusIds = ['a', 'b', 'c', 'd'].map(letter => `test_focus_${letter}_${testRunId}`);
+  const focusNow = new Date().toISOString();
+  const focusStale = new Date(Date.now() - 30 * 24 * 60 * 60 * 1000).toISOString();
+  focusIds.forEach((chunkId, index) => {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, last_accessed, database_id)
+      VALUES (?, ?, ?, ?, ?, ?)
+    `).run(chunkId, `Focus chunk ${chunkId}`, 'focus_test', focusNow, index < 2 ? focusNow : focusStale, focusDb);
+  });
+  const insertFocusEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertFocusEdge.run(`focus_edge_hot_${testRunId}`, focusIds[0], focusIds[1], 'related_to', 0.7, focusNow, focusDb);
+  insertFocusEdge.run(`focus_edge_idle_${testRunId}`, focusIds[2], focusIds[3], 'related_to', 0.7, focusNow, focusDb);
+
+  decayConnections(7, 60 * 60 * 1000);
+  const focusWeight = (edgeId: string) => (db.prepare(`SELECT weight FROM connections WHERE edge_id = ?`).get(edgeId) as { weight: number }).weight;
+  expect(focusWeight(`focus_edge_hot_${testRunId}`) === 0.7, 'expected the in-focus edge to keep its weight');
+  expect(focusWeight(`focus_edge_idle_${testRunId}`) < 0.7, 'expected the idle edge to decay');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(focusDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(focusDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(focusDb);
+  console.log('   ✅ In-focus decay exemption working');
+
+  console.log('51. Testing edge recency refresh on access...');
+  const recencyDb = `recency_db_${testRunId}`;
+  const recencySeed = `test_recency_seed_${testRunId}`;
+  const recencyNeighbor = `test_recency_neighbor_${testRunId}`;
+  const recencyText = `Edge recency refresh ${testRunId} on recall.`;
+  const recencyPoint = uuidv4();
+  const recencyOld = new Date(Date.now() - 30 * 24 * 60 * 60 * 1000).toISOString();
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [{ id: recencyPoint, vector: await embed(recencyText), payload: { text: recencyText, source: 'recency_test', chunk_id: recencySeed, database_id: recencyDb } }],
+  });
+  for (const chunkId of [recencySeed, recencyNeighbor]) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(chunkId, chunkId === recencySeed ? recencyText : `Recency neighbour ${testRunId}.`, 'recency_test', recencyOld, recencyDb);
+  }
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, last_reinforced, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `).run(`recency_edge_${testRunId}`, recencySeed, recencyNeighbor, 'supports', 0.5, recencyOld, recencyOld, recencyDb);
+  const recencyClock = () => (db.prepare(`SELECT last_reinforced, weight FROM connections WHERE edge_id = ?`).get(`recency_edge_${testRunId}`) as { last_reinforced: string; weight: number });
+
+  await retrieve(recencyText, { database: recencyDb, maxHops: 0, includeConflicts: false, refreshEdgeRecency: false });
+  expect(recencyClock().last_reinforced === recencyOld, 'expected the edge clock to stay put when refresh is off');
+  await retrieve(recencyText, { database: recencyDb, maxHops: 0, includeConflicts: false, refreshEdgeRecency: true });
+  expect(Date.parse(recencyClock().last_reinforced) > Date.parse(recencyOld), 'expected recall to refresh the incident edge clock');
+  expect(recencyClock().weight === 0.5, 'expected the refresh to leave the weight alone');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(recencyDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(recencyDb);
+  db.prepare(`DELETE FROM co_access_events WHERE database_id = ?`).run(recencyDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(recencyDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [recencyPoint] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Edge recency refresh working');
+
+  console.log('52. Testing bulk weight adjustment...');
+  const adjustDb = `adjust_db_${testRunId}`;
+  const adjustIds = ['a', 'b', 'c'].map(letter => `test_adjust_${letter}_${testRunId}`);
+  const adjustNow = new Date().toISOString();
+  const insertAdjustEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertAdjustEdge.run(`adjust_ab_${testRunId}`, adjustIds[0], adjustIds[1], 'related_to', 0.5, adjustNow, adjustDb);
+  insertAdjustEdge.run(`adjust_bc_${testRunId}`, adjustIds[1], adjustIds[2], 'related_to', 0.1, adjustNow, adjustDb);
+
+  const weakened = adjustEdgeWeights([
+    { source: adjustIds[0], target: adjustIds[1] },
+    { source: adjustIds[1], target: adjustIds[2] },
+    { source: adjustIds[2], target: adjustIds[0] },
+  ], -0.2, { database: adjustDb });
+  expect(weakened.adjusted === 2, `expected 2 adjusted edges, got ${weakened.adjusted}`);
+  expect(weakened.missing.length === 1 && weakened.missing[0].source === adjustIds[2], 'expected the missing pair to be reported');
+  expect(Math.abs((getEdgeWeight(adjustIds[0], adjustIds[1], { database: adjustDb }) ?? 0) - 0.3) < 1e-9, 'expected 0.5 - 0.2 = 0.3');
+  expect(getEdgeWeight(adjustIds[1], adjustIds[2], { database: adjustDb }) === 0.05, 'expected weakening to clamp at the minimum weight');
+
+  adjustEdgeWeights([{ source: adjustIds[0], target: adjustIds[1] }], 5, { database: adjustDb });
+  expect(getEdgeWeight(adjustIds[0], adjustIds[1], { database: adjustDb }) === 1, 'expected strengthening to clamp at the maximum weight');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(adjustDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(adjustDb);
+  console.log('   ✅ Bulk weight adjustment working');
+
+  console.log('53. Testing recall frontier cap...');
+  const frontierDb = `frontier_db_${testRunId}`;
+  const frontierSeed = `test_frontier_seed_${testRunId}`;
+  const frontierIds = Array.from({ length: 30 }, (_, index) => `test_frontier_${index}_${testRunId}`);
+  const frontierNow = new Date().toISOString();
+  const insertFrontierEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  db.prepare(`INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id) VALUES (?, ?, ?, ?, ?)`).run(frontierSeed, 'Frontier seed chunk.', 'frontier_test', frontierNow, frontierDb);
+  // Dense: the seed fans out to 30 nodes (weights 0.40..0.98) which all interconnect
+  db.transaction(() => {
+    frontierIds.forEach((target, index) => {
+      insertFrontierEdge.run(`frontier_seed_${index}_${testRunId}`, frontierSeed, target, 'related_to', 0.4 + index * 0.02, frontierNow, frontierDb);
+      frontierIds.forEach((other, otherIndex) => {
+        if (other !== target) insertFrontierEdge.run(`frontier_${index}_${otherIndex}_${testRunId}`, target, other, 'related_to', 0.5, frontierNow, frontierDb);
+      });
+    });
+  })();
+
+  const frontierSeeds = [{ chunkId: frontierSeed, score: 1, hopDepth: 0, path: [frontierSeed], vectorScore: 1 }];
+  const unboundedStats: ExpansionStats = { peakFrontier: 0, dropped: 0, enqueued: 0 };
+  const unbounded = await multiHopExpand(frontierSeeds, new Set(), { database: frontierDb, maxHops: 3, stats: unboundedStats });
+  const cappedStats: ExpansionStats = { peakFrontier: 0, dropped: 0, enqueued: 0 };
+  const capped = await multiHopExpand(frontierSeeds, new Set(), { database: frontierDb, maxHops: 3, maxFrontier: 5, stats: cappedStats });
+  expect(unboundedStats.peakFrontier > 5, `expected the dense graph to exceed the cap without one, peak ${unboundedStats.peakFrontier}`);
+  expect(cappedStats.peakFrontier <= 5 && cappedStats.dropped > 0, `expected the frontier to stay within 5, peak ${cappedStats.peakFrontier}`);
+  const topOf = (candidates: typeof capped) => candidates.filter(c => c.hopDepth > 0).sort((a, b) => b.score - a.score)[0]?.chunkId;
+  expect(topOf(capped) === topOf(unbounded) && topOf(capped) === frontierIds[29], 'expected the strongest neighbour to stay on top under the cap');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(frontierDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(frontierDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(frontierDb);
+  console.log('   ✅ Recall frontier cap working');
+
+  console.log('54. Testing recently learned listing...');
+  const recentDb = `recent_db_${testRunId}`;
+  const recentIds = [0, 1, 2, 3].map(index => `test_recent_${index}_${testRunId}`);
+  const recentBase = Date.parse('2026-01-01T00:00:00.000Z');
+  recentIds.forEach((chunkId, index) => {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(chunkId, `Recent chunk ${index}`, 'recent_test', new Date(recentBase + index * 60_000).toISOString(), recentDb);
+  });
+
+  const newest = listRecentChunks(3, undefined, recentDb).map(chunk => chunk.chunk_id);
+  expect(newest.join(',') === [recentIds[3], recentIds[2], recentIds[1]].join(','), `expected newest-first order, got ${newest.join(',')}`);
+  const sinceSecond = listRecentChunks(10, new Date(recentBase + 60_000).toISOString(), recentDb).map(chunk => chunk.chunk_id);
+  expect(sinceSecond.join(',') === [recentIds[3], recentIds[2]].join(','), `expected only chunks after since, got ${sinceSecond.join(',')}`);
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(recentDb);
+  console.log('   ✅ Recently learned listing working');
+
+  console.log('55. Testing dead connection sweep...');
+  const sweepDb = `sweep_db_${testRunId}`;
+  const sweepNow = new Date().toISOString();
+  const sweepEdges: Array<[string, number]> = [
+    [`sweep_zero_${testRunId}`, 0],
+    [`sweep_floor_${testRunId}`, 0.05],
+    [`sweep_live_${testRunId}`, 0.4],
+  ];
+  for (const [edgeId, weight] of sweepEdges) {
+    db.prepare(`
+      INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `).run(edgeId, `sweep_a_${testRunId}`, `sweep_b_${testRunId}`, 'related_to', weight, sweepNow, sweepDb);
+  }
+
+  const swept = sweepDeadConnections(sweepDb);
+  expect(swept === 2, `expected 2 dead edges removed, got ${swept}`);
+  const sweepRemaining = db.prepare(`SELECT edge_id FROM connections WHERE database_id = ?`).all(sweepDb) as Array<{ edge_id: string }>;
+  expect(sweepRemaining.length === 1 && sweepRemaining[0].edge_id === `sweep_live_${testRunId}`, 'expected only the live edge to remain');
+  expect(sweepDeadConnections(sweepDb) === 0, 'expected a second sweep to find nothing');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(sweepDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(sweepDb);
+  console.log('   ✅ Dead connection sweep working');
+
+  console.log('56. Testing graph summary...');
+  const summaryDb = `summary_db_${testRunId}`;
+  const summaryIds = [0, 1, 2, 3].map(index => `test_summary_${index}_${testRunId}`);
+  const summaryNow = new Date().toISOString();
+  for (const chunkId of summaryIds) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(chunkId, `Summary chunk ${chunkId}`, 'summary_test', summaryNow, summaryDb);
+  }
+  const summaryEdges: Array<[number, number, string, number]> = [
+    [0, 1, 'supports', 0.9],
+    [0, 2, 'supports', 0.5],
+    [0, 3, 'related_to', 0.1],
+  ];
+  summaryEdges.forEach(([from, to, relationship, weight], index) => {
+    db.prepare(`
+      INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `).run(`summary_edge_${index}_${testRunId}`, summaryIds[from], summaryIds[to], relationship, weight, summaryNow, summaryDb);
+  });
+  db.prepare(`
+    INSERT INTO concepts (concept_id, label, summary, member_chunks, created_at, last_updated, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `).run(`test_summary_concept_${testRunId}`, 'summary', 'summary concept', JSON.stringify(summaryIds.slice(0, 2)), summaryNow, summaryNow, summaryDb);
+
+  const summary = getGraphSummary(summaryDb);
+  expect(summary.total_chunks === 4 && summary.total_connections === 3 && summary.total_concepts === 1, 'expected summary totals to match the fixture');
+  expect(summary.consolidated_chunks === 2 && summary.consolidation_ratio === summary.consolidated_chunks / summary.total_chunks, 'expected consolidation ratio to match counts');
+  expect(summary.relationships.reduce((sum, row) => sum + row.count, 0) === summary.total_connections, 'expected relationship counts to add up');
+  expect(summary.weight_histogram.counts.reduce((sum, value) => sum + value, 0) === summary.total_connections, 'expected the weight histogram to cover every edge');
+  expect(summary.edge_age_distribution.counts.reduce((sum, value) => sum + value, 0) === summary.total_connections, 'expected the age distribution to cover every edge');
+  expect(Math.abs(summary.average_weight - 0.5) < 1e-9, `expected average weight 0.5, got ${summary.average_weight}`);
+  expect(summary.top_chunks[0]?.chunk_id === summaryIds[0] && summary.top_chunks[0]?.degree === 3, 'expected the hub to lead by degree');
+  expect(summary.storage_bytes > 0 && summary.graph_version >= 0, 'expected storage and version to be populated');
+
+  db.prepare(`DELETE FROM concepts WHERE database_id = ?`).run(summaryDb);
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(summaryDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(summaryDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(summaryDb);
+  console.log('   ✅ Graph summary working');
+
+  console.log('57. Testing concurrent identical ingests...');
+  const racingDb = `racing_db_${testRunId}`;
+  createDatabase(racingDb, 'concurrent ingest test db');
+  const racingText = `Grid cells in the entorhinal cortex fire in a hexagonal lattice as an animal explores (${testRunId}).`;
+  const racingResults = await Promise.all(
+    [0, 1, 2, 3, 4].map(index => ingestText(`racing_${index}`, racingText, ['test'], {}, racingDb)),
+  );
+  const racingStored = db.prepare(`SELECT COUNT(*) AS total FROM chunks WHERE database_id = ?`).get(racingDb) as { total: number };
+  expect(racingStored.total === 1, `expected exactly one stored chunk, got ${racingStored.total}`);
+  expect(racingResults.reduce((sum, result) => sum + result.chunks_stored, 0) === 1, 'expected exactly one ingest to report storing the chunk');
+  expect(racingResults.reduce((sum, result) => sum + result.chunks_skipped, 0) === racingResults.length - 1, 'expected the others to report a duplicate');
+
+  await clearDatabase(racingDb);
+  deleteDatabase(racingDb);
+  console.log('   ✅ Concurrent identical ingests working');
+
+  console.log('58. Testing access write policies...');
+  const accessDb = `access_policy_db_${testRunId}`;
+  const accessChunk = `test_access_policy_${testRunId}`;
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, database_id)
+    VALUES (?, ?, ?, ?, 0, ?)
+  `).run(accessChunk, 'Access policy chunk', 'access_policy_test', new Date().toISOString(), accessDb);
+  const readAccess = () => db.prepare(`SELECT access_count, last_accessed FROM chunks WHERE chunk_id = ?`).get(accessChunk) as { access_count: number; last_accessed: string | null };
+
+  recordChunkAccess([accessChunk], accessDb, 'never');
+  expect(readAccess().access_count === 0 && readAccess().last_accessed === null, 'expected never to leave the store untouched');
+  recordChunkAccess([accessChunk], accessDb, 'every');
+  expect(readAccess().access_count === 1 && readAccess().last_accessed !== null, 'expected every to write immediately');
+  recordChunkAccess([accessChunk], accessDb, 'debounced');
+  recordChunkAccess([accessChunk], accessDb, 'debounced');
+  expect(readAccess().access_count === 1, 'expected debounced accesses to wait for a flush');
+  expect(flushChunkAccess() === 1 && readAccess().access_count === 3, `expected the flush to add both accesses, got ${readAccess().access_count}`);
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(accessDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(accessDb);
+  console.log('   ✅ Access write policies working');
+
+  console.log('59. Testing adjacency export...');
+  const adjacencyDb = `adjacency_db_${testRunId}`;
+  const adjacencyIds = ['a', 'b', 'c'].map(name => `test_adjacency_${name}_${testRunId}`);
+  const adjacencyNow = new Date().toISOString();
+  for (const chunkId of adjacencyIds) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(chunkId, `Adjacency chunk ${chunkId}`, 'adjacency_test', adjacencyNow, adjacencyDb);
+  }
+  const adjacencyEdges: Array<[number, number, string, number]> = [
+    [0, 1, 'supports', 0.7],
+    [0, 1, 'related_to', 0.4],
+    [2, 0, 'example_of', 0.25],
+  ];
+  adjacencyEdges.forEach(([from, to, relationship, weight], index) => {
+    db.prepare(`
+      INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `).run(`adjacency_edge_${index}_${testRunId}`, adjacencyIds[from], adjacencyIds[to], relationship, weight, adjacencyNow, adjacencyDb);
+  });
+
+  const dense = exportAdjacency(adjacencyDb, 'dense');
+  expect(dense.chunk_ids.join(',') === adjacencyIds.join(','), 'expected chunk ids in sorted order');
+  expect(dense.matrix[0][1] === 0.7 && dense.matrix[2][0] === 0.25, 'expected weights at their row/column');
+  expect(dense.matrix[1][0] === 0 && dense.matrix[1].every(value => value === 0), 'expected absent edges to be zero');
+  const coo = exportAdjacency(adjacencyDb);
+  expect(JSON.stringify(coo.entries) === JSON.stringify([[0, 1, 0.7], [2, 0, 0.25]]), `unexpected coo entries ${JSON.stringify(coo.entries)}`);
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(adjacencyDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(adjacencyDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(adjacencyDb);
+  console.log('   ✅ Adjacency export working');
+
+  console.log('60. Testing stale connection pruning...');
+  const pruneDb = `prune_db_${testRunId}`;
+  const pruneOld = new Date(Date.now() - 40 * 24 * 60 * 60 * 1000).toISOString();
+  const pruneRecent = new Date().toISOString();
+  const pruneEdges: Array<[string, number, string]> = [
+    [`prune_stale_weak_${testRunId}`, 0.08, pruneOld],
+    [`prune_stale_strong_${testRunId}`, 0.8, pruneOld],
+    [`prune_recent_weak_${testRunId}`, 0.08, pruneRecent],
+  ];
+  for (const [edgeId, weight, reinforced] of pruneEdges) {
+    db.prepare(`
+      INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, last_reinforced, database_id)
+      VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+    `).run(edgeId, `prune_a_${testRunId}`, `prune_b_${testRunId}`, 'related_to', weight, pruneOld, reinforced, pruneDb);
+  }
+
+  expect(pruneStaleConnections(0, 0.15, pruneDb) === 0, 'expected a zero window to disable pruning');
+  expect(pruneStaleConnections(30, 0.15, pruneDb) === 1, 'expected one stale edge pruned');
+  const pruneRemaining = (db.prepare(`SELECT edge_id FROM connections WHERE database_id = ? ORDER BY edge_id`).all(pruneDb) as Array<{ edge_id: string }>)
+    .map(row => row.edge_id);
+  expect(pruneRemaining.join(',') === [`prune_recent_weak_${testRunId}`, `prune_stale_strong_${testRunId}`].join(','), `expected only the stale weak edge gone, got ${pruneRemaining.join(',')}`);
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(pruneDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(pruneDb);
+  console.log('   ✅ Stale connection pruning working');
+
+  console.log('61. Testing configurable consolidation phases...');
+  const phaseDb = `phase_db_${testRunId}`;
+  const phaseHot = `test_phase_hot_${testRunId}`;
+  const phaseTarget = `test_phase_target_${testRunId}`;
+  const phaseEdge = `test_phase_edge_${testRunId}`;
+  const phaseNow = new Date().toISOString();
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, database_id)
+    VALUES (?, ?, ?, ?, ?, ?)
+  `).run(phaseHot, 'Frequently recalled phase chunk', 'phase_test', phaseNow, 10, phaseDb);
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, database_id)
+    VALUES (?, ?, ?, ?, ?, ?)
+  `).run(phaseTarget, 'Phase target chunk', 'phase_test', phaseNow, 0, phaseDb);
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, last_reinforced, avg_sim, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+  `).run(phaseEdge, phaseHot, phaseTarget, 'related_to', 0.5, 0.5, phaseNow, phaseNow, 1.0, phaseDb);
+  const phaseWeight = () => (db.prepare(`SELECT weight FROM connections WHERE edge_id = ?`).get(phaseEdge) as { weight: number }).weight;
+
+  expect(parseConsolidationPhases('').length > 1 && parseConsolidationPhases(' decay, prune ').join(',') === 'decay,prune', 'expected phase lists to parse in order');
+  let unknownPhaseRejected = false;
+  try {
+    parseConsolidationPhases('decay,dream');
+  } catch {
+    unknownPhaseRejected = true;
+  }
+  expect(unknownPhaseRejected, 'expected an unknown phase to be rejected');
+
+  await consolidateAll([]);
+  expect(phaseWeight() === 0.5, 'expected an empty cycle to leave weights alone');
+  await consolidateAll(['reinforce']);
+  expect(phaseWeight() > 0.5, `expected a reinforcing cycle to strengthen the edge, got ${phaseWeight()}`);
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(phaseDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(phaseDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(phaseDb);
+  console.log('   ✅ Configurable consolidation phases working');
+
+  console.log('62. Testing id-only recall...');
+  const idsQuery = 'how does the hippocampus consolidate memories?';
+  const fullRecall = await retrieve(idsQuery, { topK: 5, recordAccess: false });
+  const idRecall = await retrieveIds(idsQuery, { topK: 5, recordAccess: false });
+  expect(idRecall.length === fullRecall.length, `expected ${fullRecall.length} ids, got ${idRecall.length}`);
+  expect(idRecall.every((entry, index) => entry.chunk_id === fullRecall[index].chunk_id && entry.score === fullRecall[index].score), 'expected ids and scores to match the full recall');
+  expect(idRecall.every(entry => Object.keys(entry).sort().join(',') === 'chunk_id,score'), 'expected nothing but ids and scores');
+  // Id-only results skip most text, so they must not be served to a full recall from the cache
+  await retrieveIds(idsQuery, { topK: 5, recordAccess: false, useCache: true });
+  const cachedFull = await retrieve(idsQuery, { topK: 5, recordAccess: false, useCache: true });
+  expect(cachedFull.length === fullRecall.length && cachedFull.every(result => result.text.length > 0), 'expected a full recall after an id-only one to keep its text');
+  console.log('   ✅ Id-only recall working');
+
+  console.log('63. Testing indexed text search...');
+  const textDb = `text_db_${testRunId}`;
+  const textNow = new Date().toISOString();
+  const textBodies = [
+    'Long-term potentiation strengthens synapses during learning.',
+    'Sleep spindles help consolidate memory traces overnight.',
+    'The hippocampus replays memory traces during sleep.',
+    ...Array.from({ length: 20 }, (_, index) => `Unrelated filler note number ${index} about cortex anatomy.`),
+  ];
+  textBodies.forEach((text, index) => {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(`test_text_${index}_${testRunId}`, text, 'text_search_test', textNow, textDb);
+  });
+
+  const textQueries: Array<[string, 'substring' | 'prefix']> = [
+    ['memory traces', 'substring'],
+    ['consolidate memory tr', 'substring'],
+    ['the hippocampus rep', 'prefix'],
+  ];
+  for (const [query, kind] of textQueries) {
+    const textStats: ChunkSearchStats = { candidates: 0, indexed: false };
+    const indexed = searchChunks(query, { kind }, textDb, 50, textStats);
+    const linear = textBodies
+      .map((text, index) => ({ chunk_id: `test_text_${index}_${testRunId}`, quality: matchQuality(text, query, { kind }) }))
+      .filter(hit => hit.quality !== null);
+    expect(textStats.indexed, `expected "${query}" to use the token index`);
+    expect(indexed.length === linear.length && linear.every(hit => indexed.some(found => found.chunk_id === hit.chunk_id)), `expected "${query}" to match the linear scan`);
+    expect(textStats.candidates < textBodies.length / 2, `expected few candidates for "${query}", read ${textStats.candidates}`);
+  }
+  db.prepare(`UPDATE chunks SET text = ? WHERE chunk_id = ?`).run('Edited note about memory traces.', `test_text_3_${testRunId}`);
+  expect(searchChunks('memory traces', { kind: 'substring' }, textDb).length === 3, 'expected edited text to be searchable');
+  const ignoredInsert = db.prepare(`
+    INSERT OR IGNORE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(`test_text_2_${testRunId}`, '', 'import placeholder', textNow, `other_${textDb}`);
+  expect(ignoredInsert.changes === 0, 'expected the clashing insert to be ignored');
+  expect(searchChunks('memory traces', { kind: 'substring' }, textDb).length === 3, 'expected an ignored insert to leave the existing chunk searchable');
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(`test_text_2_${testRunId}`, 'Replaced note about cortex folding.', 'text_search_test', textNow, textDb);
+  expect(searchChunks('memory traces', { kind: 'substring' }, textDb).length === 2, 'expected a replaced chunk to drop its old text from the index');
+  expect(searchChunks('cortex folding', { kind: 'substring' }, textDb).length === 1, 'expected the replacement text to be searchable');
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(textDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(textDb);
+  expect(searchChunks('memory traces', { kind: 'substring' }, textDb).length === 0, 'expected deleted chunks to leave the index');
+  console.log('   ✅ Indexed text search working');
+
+  console.log('64. Testing recall improvement epsilon...');
+  const epsilonDb = `epsilon_db_${testRunId}`;
+  const epsilonSeed = `test_epsilon_seed_${testRunId}`;
+  const epsilonHubs = Array.from({ length: 8 }, (_, index) => `test_epsilon_hub_${index}_${testRunId}`);
+  const epsilonLeaves = Array.from({ length: 4 }, (_, index) => `test_epsilon_leaf_${index}_${testRunId}`);
+  const epsilonNow = new Date().toISOString();
+  const insertEpsilonEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  db.prepare(`INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id) VALUES (?, ?, ?, ?, ?)`).run(epsilonSeed, 'Epsilon seed chunk.', 'epsilon_test', epsilonNow, epsilonDb);
+  // Each successive hub reaches every leaf with a marginally better score
+  db.transaction(() => {
+    epsilonHubs.forEach((hub, hubIndex) => {
+      insertEpsilonEdge.run(`epsilon_seed_${hubIndex}_${testRunId}`, epsilonSeed, hub, 'related_to', 0.9 + hubIndex * 0.01, epsilonNow, epsilonDb);
+      epsilonLeaves.forEach((leaf, leafIndex) => {
+        insertEpsilonEdge.run(`epsilon_${hubIndex}_${leafIndex}_${testRunId}`, hub, leaf, 'related_to', 0.9, epsilonNow, epsilonDb);
+      });
+    });
+  })();
+
+  const epsilonSeeds = [{ chunkId: epsilonSeed, score: 1, hopDepth: 0, path: [epsilonSeed], vectorScore: 1 }];
+  const exactStats: ExpansionStats = { peakFrontier: 0, dropped: 0, enqueued: 0 };
+  const exactExpansion = await multiHopExpand(epsilonSeeds, new Set(), { database: epsilonDb, maxHops: 3, maxFrontier: 0, stats: exactStats });
+  const lazyStats: ExpansionStats = { peakFrontier: 0, dropped: 0, enqueued: 0 };
+  const lazyExpansion = await multiHopExpand(epsilonSeeds, new Set(), { database: epsilonDb, maxHops: 3, maxFrontier: 0, improvementEpsilon: 0.05, stats: lazyStats });
+  expect(lazyStats.enqueued < exactStats.enqueued, `expected fewer queue pushes with epsilon, ${lazyStats.enqueued} vs ${exactStats.enqueued}`);
+  expect(lazyExpansion.length === exactExpansion.length, 'expected the same nodes to be reached');
+  const exactScores = new Map(exactExpansion.map(candidate => [candidate.chunkId, candidate.score]));
+  expect(lazyExpansion.every(candidate => Math.abs(candidate.score - (exactScores.get(candidate.chunkId) ?? 0)) <= 0.05), 'expected scores within tolerance of the exact expansion');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(epsilonDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(epsilonDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(epsilonDb);
+  console.log('   ✅ Recall improvement epsilon working');
+
+  console.log('65. Testing combined maintenance run...');
+  const maintainDb = `maintain_db_${testRunId}`;
+  const maintainNow = new Date().toISOString();
+  const maintainExpired = `test_maintain_expired_${testRunId}`;
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, expires_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?)
+  `).run(maintainExpired, 'A note that has already expired.', 'maintain_test', maintainNow, new Date(Date.now() - 60_000).toISOString(), maintainDb);
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `).run(`maintain_dead_${testRunId}`, `maintain_a_${testRunId}`, `maintain_b_${testRunId}`, 'related_to', 0, maintainNow, maintainDb);
+  // A live edge of our own, so the decay count does not rest on earlier steps' edges
+  const maintainOld = new Date(Date.now() - 30 * 24 * 60 * 60 * 1000).toISOString();
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, last_reinforced, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `).run(`maintain_live_${testRunId}`, `maintain_c_${testRunId}`, `maintain_d_${testRunId}`, 'related_to', 0.8, maintainOld, maintainOld, maintainDb);
+
+  const edgesBeforeMaintenance = (db.prepare('SELECT COUNT(*) AS count FROM connections').get() as { count: number }).count;
+  const maintenance = await runMaintenance({ phases: ['decay'] });
+  expect(maintenance.phases.join(',') === 'decay', 'expected only the requested phase');
+  expect(maintenance.expired_chunks >= 1 && getChunksByIds([maintainExpired], maintainDb)[0] === null, 'expected the expired chunk to be swept');
+  expect(maintenance.decayed >= 2 && maintenance.decayed <= edgesBeforeMaintenance, `expected decay to be counted, got ${maintenance.decayed}`);
+  const maintainLiveWeight = getEdgeWeight(`maintain_c_${testRunId}`, `maintain_d_${testRunId}`, { database: maintainDb });
+  expect(maintainLiveWeight !== null && maintainLiveWeight < 0.8, `expected the seeded edge to decay, got ${maintainLiveWeight}`);
+  expect(maintenance.dead_edges_swept >= 1, 'expected the dead edge to be swept');
+  expect(getEdgeWeight(`maintain_a_${testRunId}`, `maintain_b_${testRunId}`, { database: maintainDb }) === null, 'expected the dead edge to be gone');
+  expect(maintenance.reinforced === 0 && maintenance.concepts_synced === 0, 'expected skipped phases to report nothing');
+  expect(
+    maintenance.sources_classified === 0 && maintenance.associative_samples === 0 && maintenance.concepts_created === 0 && maintenance.concepts_refreshed === 0,
+    'expected the classify, train and abstract counts to be reported as zero when not run',
+  );
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(maintainDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(maintainDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(maintainDb);
+  console.log('   ✅ Combined maintenance run working');
+
+  console.log('66. Testing quantized weight export...');
+  const quantDb = `quant_db_${testRunId}`;
+  const quantImportDb = `quant_import_db_${testRunId}`;
+  const quantIds = ['a', 'b'].map(name => `test_quant_${name}_${testRunId}`);
+  const quantNow = new Date().toISOString();
+  const quantWeights = [0, 0.05, 0.123456789, 0.5, 0.987654321, 1];
+  // Only the edges are re-imported, so the endpoints are seeded on the receiving side
+  for (const chunkId of quantIds) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(chunkId, `Quantization chunk ${chunkId}`, 'quant_test', quantNow, quantImportDb);
+  }
+  quantWeights.forEach((weight, index) => {
+    db.prepare(`
+      INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `).run(`quant_edge_${index}_${testRunId}`, quantIds[0], quantIds[1], `rel_${index}`, weight, quantNow, quantDb);
+  });
+
+  const quantEdges = [...iterateGraphExport(quantDb, 100, 'u16')].filter(record => record.connection);
+  expect(quantEdges.every(record => record.connection?.weight === 0 && Number.isInteger(record.connection?.weight_q16)), 'expected u16 exports to carry only weight_q16');
+  const quantResult = await importGraphRecords(quantEdges.map(record => ({ ...record, database: quantImportDb })));
+  expect(quantResult.connections_imported === quantWeights.length, `expected ${quantWeights.length} imported edges, got ${quantResult.connections_imported}`);
+  const quantStored = db.prepare(`SELECT relationship, weight FROM connections WHERE database_id = ?`).all(quantImportDb) as Array<{ relationship: string; weight: number }>;
+  const quantTolerance = dequantizeWeight(1) / 2;
+  expect(quantStored.every(row => Math.abs(row.weight - quantWeights[Number(row.relationship.slice(4))]) <= quantTolerance), 'expected round-tripped weights within half a quantization step');
+  const exactEdges = [...iterateGraphExport(quantDb, 100, 'f64')].filter(record => record.connection);
+  expect(exactEdges.every(record => record.connection?.weight_q16 === undefined), 'expected f64 exports to be unchanged');
+
+  db.prepare(`DELETE FROM connections WHERE database_id IN (?, ?)`).run(quantDb, quantImportDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id IN (?, ?)`).run(quantDb, quantImportDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id IN (?, ?)`).run(quantDb, quantImportDb);
+  console.log('   ✅ Quantized weight export working');
+
+  console.log('67. Testing eviction listeners...');
+  const evictDb = `evict_db_${testRunId}`;
+  const evictNow = new Date().toISOString();
+  const evictPast = new Date(Date.now() - 60_000).toISOString();
+  const evictIds = ['old', 'older', 'live'].map(name => `test_evict_${name}_${testRunId}`);
+  evictIds.forEach((chunkId, index) => {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, expires_at, database_id)
+      VALUES (?, ?, ?, ?, ?, ?)
+    `).run(chunkId, `Eviction note ${index}`, 'evict_test', evictNow, index < 2 ? evictPast : null, evictDb);
+  });
+
+  const evicted: Array<{ chunk_id: string; text: string; reason: EvictionReason; stillStored: boolean }> = [];
+  const stopListening = onChunkEvicted((chunk, database, reason) => {
+    if (database !== evictDb) return;
+    // Runs after the delete committed, and may read the store freely
+    evicted.push({ chunk_id: chunk.chunk_id, text: chunk.text, reason, stillStored: getChunksByIds([chunk.chunk_id], database)[0] !== null });
+  });
+  await sweepExpiredChunks(evictDb);
+  stopListening();
+
+  expect(evicted.map(entry => entry.chunk_id).sort().join(',') === [evictIds[0], evictIds[1]].sort().join(','), `expected the two expired chunks, got ${evicted.map(entry => entry.chunk_id).join(',')}`);
+  expect(evicted.every(entry => entry.reason === 'expired' && entry.text.startsWith('Eviction note') && !entry.stillStored), 'expected full records after removal');
+  db.prepare(`UPDATE chunks SET expires_at = ? WHERE chunk_id = ?`).run(evictPast, evictIds[2]);
+  await sweepExpiredChunks(evictDb);
+  expect(evicted.length === 2, 'expected no calls after unsubscribing');
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(evictDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(evictDb);
+  console.log('   ✅ Eviction listeners working');
+
+  console.log('68. Testing live stats stream...');
+  const streamDb = `stream_db_${testRunId}`;
+  const streamNow = new Date().toISOString();
+  const streamIds = ['a', 'b', 'c'].map(name => `test_stream_${name}_${testRunId}`);
+  for (const chunkId of streamIds) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(chunkId, `Stream chunk ${chunkId}`, 'stream_test', streamNow, streamDb);
+  }
+  db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `).run(`stream_edge_${testRunId}`, streamIds[0], streamIds[1], 'related_to', 0.5, streamNow, streamDb);
+
+  const statsServer = http.createServer((req, res) => {
+    const requestUrl = new URL(req.url ?? '/', 'http://localhost');
+    void handleHealthRoutes(req, res, requestUrl, req.method ?? 'GET');
+  });
+  await new Promise<void>(resolve => statsServer.listen(0, resolve));
+  const statsPort = (statsServer.address() as { port: number }).port;
+  const streamEvents = await new Promise<Array<Record<string, unknown>>>((resolve, reject) => {
+    const events: Array<Record<string, unknown>> = [];
+    const request = http.get(`http://127.0.0.1:${statsPort}/api/stats/stream?database=${streamDb}&intervalMs=250`, response => {
+      let buffer = '';
+      response.setEncoding('utf8');
+      response.on('data', (data: string) => {
+        buffer += data;
+        let boundary = buffer.indexOf('\n\n');
+        while (boundary !== -1) {
+          events.push(JSON.parse(buffer.slice(0, boundary).replace(/^data: /, '')));
+          buffer = buffer.slice(boundary + 2);
+          boundary = buffer.indexOf('\n\n');
+        }
+        if (events.length >= 2) {
+          request.destroy();
+          resolve(events);
+        }
+      });
+    });
+    request.on('error', error => {
+      if (events.length < 2) reject(error);
+    });
+  });
+  await new Promise<void>(resolve => statsServer.close(() => resolve()));
+
+  expect(streamEvents.every(event => event.type === 'stats' && event.database === streamDb), 'expected stats events for the requested database');
+  expect(streamEvents[0].total_chunks === 3 && streamEvents[0].total_connections === 1 && streamEvents[0].total_concepts === 0, `unexpected totals ${JSON.stringify(streamEvents[0])}`);
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(streamDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(streamDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(streamDb);
+  console.log('   ✅ Live stats stream working');
+
+  console.log('69. Testing import progress reporting...');
+  const bulkDb = `bulk_db_${testRunId}`;
+  const bulkIds = ['a', 'b'].map(name => `test_bulk_${name}_${testRunId}`);
+  const bulkNow = new Date().toISOString();
+  for (const chunkId of bulkIds) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(chunkId, `Bulk endpoint ${chunkId}`, 'bulk_test', bulkNow, bulkDb);
+  }
+  const bulkTotal = 2_050;
+  function* bulkRecords(): Generator<GraphRecord> {
+    for (let index = 0; index < bulkTotal; index++) {
+      yield {
+        database: bulkDb,
+        connection: {
+          edge_id: `bulk_edge_${index}_${testRunId}`,
+          source_chunk: bulkIds[index % 2],
+          target_chunk: bulkIds[(index + 1) % 2],
+          relationship: 'related_to',
+          weight: 0.4,
+          confidence: 0.5,
+          created_at: bulkNow,
+        },
+      };
+    }
+  }
+
+  const bulkProgress: number[] = [];
+  const bulkResult = await importGraphRecords(bulkRecords(), bulkDb, { onProgress: processed => bulkProgress.push(processed), progressEvery: 500 });
+  expect(bulkProgress.join(',') === '500,1000,1500,2000,2050', `unexpected progress calls ${bulkProgress.join(',')}`);
+  expect(bulkResult.connections_imported === bulkTotal, `expected ${bulkTotal} imported edges, got ${bulkResult.connections_imported}`);
+  const bulkStored = db.prepare(`SELECT COUNT(*) AS total FROM connections WHERE database_id = ?`).get(bulkDb) as { total: number };
+  expect(bulkStored.total === bulkTotal, `expected ${bulkTotal} stored edges, got ${bulkStored.total}`);
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(bulkDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(bulkDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(bulkDb);
+  console.log('   ✅ Import progress reporting working');
+
+  console.log('70. Testing similarity edge builder...');
+  const simDb = `sim_edges_db_${testRunId}`;
+  const simNow = new Date().toISOString();
+  const simBase = 'Hippocampal replay during sleep consolidates spatial memories in rats';
+  const simTexts: Record<string, string> = {
+    one: `${simBase} overnight`,
+    two: `${simBase} repeatedly`,
+    three: `${simBase} reliably`,
+    four: `${simBase} quickly`,
+    basalt: 'Volcanic basalt columns form as lava cools slowly',
+  };
+  const simId = (name: string) => `test_sim_${name}_${testRunId}`;
+  for (const [name, text] of Object.entries(simTexts)) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+      VALUES (?, ?, ?, ?, ?)
+    `).run(simId(name), text, 'sim_test', simNow, simDb);
+  }
+
+  const simStats = buildSimilarityEdges(0.5, 2, simDb);
+  const simEdges = db.prepare(`SELECT source_chunk, target_chunk FROM connections WHERE database_id = ?`).all(simDb) as Array<{ source_chunk: string; target_chunk: string }>;
+  const simDegree = new Map<string, number>();
+  for (const edge of simEdges) {
+    simDegree.set(edge.source_chunk, (simDegree.get(edge.source_chunk) ?? 0) + 1);
+    simDegree.set(edge.target_chunk, (simDegree.get(edge.target_chunk) ?? 0) + 1);
+  }
+  expect(simStats.created === simEdges.length && simEdges.length >= 2, `expected similarity edges between the replay chunks, got ${simEdges.length}`);
+  expect(!simDegree.has(simId('basalt')), 'expected no edge to the dissimilar chunk');
+  expect([...simDegree.values()].every(count => count <= 2), 'expected at most 2 new edges per chunk');
+  expect(simStats.compared === 6, `expected only the pairs sharing words to be compared, got ${simStats.compared}`);
+  buildSimilarityEdges(0.5, 2, simDb);
+  const simRerun = db.prepare(`SELECT source_chunk, target_chunk FROM connections WHERE database_id = ?`).all(simDb) as Array<{ source_chunk: string; target_chunk: string }>;
+  const simPairs = new Set(simRerun.map(edge => [edge.source_chunk, edge.target_chunk].sort().join('|')));
+  expect(simPairs.size === simRerun.length, 'expected a rerun not to link connected pairs twice');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(simDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(simDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(simDb);
+  console.log('   ✅ Similarity edge builder working');
+
+  console.log('71. Testing gRPC idle connection limits...');
+  const idleServer = new grpc.Server({ ...grpcServerOptions(), 'grpc.max_connection_idle_ms': 300 });
+  const idlePort = await new Promise<number>((resolve, reject) => {
+    idleServer.bindAsync('127.0.0.1:0', grpc.ServerCredentials.createInsecure(), (error, boundPort) => {
+      if (error) reject(error);
+      else resolve(boundPort);
+    });
+  });
+  const idleClient = new grpc.Client(`127.0.0.1:${idlePort}`, grpc.credentials.createInsecure());
+  await new Promise<void>((resolve, reject) => {
+    idleClient.waitForReady(Date.now() + 3000, error => (error ? reject(error) : resolve()));
+  });
+  const idleChannel = idleClient.getChannel();
+  expect(idleChannel.getConnectivityState(false) === grpc.connectivityState.READY, 'expected the client to connect');
+  const idleDeadline = Date.now() + 3000;
+  while (idleChannel.getConnectivityState(false) === grpc.connectivityState.READY && Date.now() < idleDeadline) {
+    await new Promise(resolve => setTimeout(resolve, 50));
+  }
+  expect(idleChannel.getConnectivityState(false) !== grpc.connectivityState.READY, 'expected the server to close the idle connection');
+  idleClient.close();
+  idleServer.forceShutdown();
+  console.log('   ✅ gRPC idle connection limits working');
+
+  console.log('72. Testing chunk confidence...');
+  const trustDb = `trust_db_${testRunId}`;
+  createDatabase(trustDb, 'chunk confidence test db');
+  await ingestText('forum_post', `Octopuses have three hearts and blue, copper-based blood (${testRunId}).`, ['test'], { confidence: 0.2 }, trustDb);
+  await ingestText('field_guide', `An octopus pumps its blue copper-rich blood through three separate hearts (${testRunId}).`, ['test'], {}, trustDb);
+
+  const trustRows = db.prepare(`SELECT chunk_id, source FROM chunks WHERE database_id = ?`).all(trustDb) as Array<{ chunk_id: string; source: string }>;
+  const trustForum = trustRows.find(row => row.source === 'forum_post')?.chunk_id ?? '';
+  const trustGuide = trustRows.find(row => row.source === 'field_guide')?.chunk_id ?? '';
+  const [forumRecord, guideRecord] = getChunksByIds([trustForum, trustGuide], trustDb);
+  expect(forumRecord?.confidence === 0.2 && forumRecord.source === 'forum_post', 'expected stored provenance for the forum chunk');
+  expect(guideRecord?.confidence === 1, 'expected the default confidence of 1');
+
+  const trustQuery = 'how many hearts does an octopus have and what colour is its blood';
+  const trusted = await retrieve(trustQuery, { topK: 5, database: trustDb, recordAccess: false, useConfidence: true });
+  const trustRank = (results: Result[], chunkId: string) => results.findIndex(result => result.chunk_id === chunkId);
+  expect(trustRank(trusted, trustGuide) !== -1 && trustRank(trusted, trustGuide) < trustRank(trusted, trustForum), 'expected the trusted chunk to outrank the low-confidence one');
+
+  let rejectedConfidence = false;
+  try {
+    await ingestText('bad_confidence', 'Should never be stored.', [], { confidence: 1.5 }, trustDb);
+  } catch {
+    rejectedConfidence = true;
+  }
+  expect(rejectedConfidence, 'expected confidence outside [0, 1] to be rejected');
+
+  await clearDatabase(trustDb);
+  deleteDatabase(trustDb);
+  console.log('   ✅ Chunk confidence working');
+
+  console.log('73. Testing SQLite sync policy...');
+  const initialSync = getSynchronousMode();
+  expect(isSynchronousMode(initialSync), `expected a known synchronous mode, got ${initialSync}`);
+  setSynchronousMode('full');
+  expect(getSynchronousMode() === 'full', 'expected full sync after switching');
+  setSynchronousMode('off');
+  expect(getSynchronousMode() === 'off', 'expected sync off after switching');
+  let rejectedSync = false;
+  try {
+    setSynchronousMode('sometimes' as SynchronousMode);
+  } catch {
+    rejectedSync = true;
+  }
+  expect(rejectedSync && getSynchronousMode() === 'off', 'expected unknown modes to be rejected without changing the setting');
+  setSynchronousMode(initialSync);
+
+  const checkpoint = checkpointWal();
+  expect(checkpoint.busy || checkpoint.checkpointed_frames <= checkpoint.log_frames, `unexpected checkpoint result ${JSON.stringify(checkpoint)}`);
+  console.log('   ✅ SQLite sync policy working');
+
+  console.log('74. Testing reciprocal edge loop guard...');
+  const loopDb = `loop_db_${testRunId}`;
+  const [loopSeed, loopA, loopB] = ['seed', 'a', 'b'].map(name => `test_loop_${name}_${testRunId}`);
+  const loopNow = new Date().toISOString();
+  const insertLoopEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  db.prepare(`INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id) VALUES (?, ?, ?, ?, ?)`).run(loopSeed, 'Loop seed chunk.', 'loop_test', loopNow, loopDb);
+  insertLoopEdge.run(`loop_seed_a_${testRunId}`, loopSeed, loopA, 'related_to', 0.3, loopNow, loopDb);
+  insertLoopEdge.run(`loop_a_b_${testRunId}`, loopA, loopB, 'related_to', 1, loopNow, loopDb);
+  insertLoopEdge.run(`loop_b_a_${testRunId}`, loopB, loopA, 'related_to', 1, loopNow, loopDb);
+
+  // Under average scoring, seed → a → b → a would beat a's direct score
+  const loopSeeds = [{ chunkId: loopSeed, score: 0.9, hopDepth: 0, path: [loopSeed], vectorScore: 0.9 }];
+  const loopExpansion = await multiHopExpand(loopSeeds, new Set(), { database: loopDb, maxHops: 3, hopDecay: 0.9, pathAccumulation: 'average' });
+  const loopNodeA = loopExpansion.find(candidate => candidate.chunkId === loopA);
+  const loopNodeB = loopExpansion.find(candidate => candidate.chunkId === loopB);
+  expect(Boolean(loopNodeA && loopNodeB), 'expected both ends of the reciprocal pair to be reached');
+  expect(Math.abs((loopNodeA?.score ?? 0) - 0.9 * 0.9 * 0.3) < 1e-9 && loopNodeA?.path.length === 2, `expected a to keep its direct score, got ${loopNodeA?.score}`);
+  expect(loopNodeB?.path.length === 3, 'expected b to be credited once, through a');
+  expect(loopExpansion.every(candidate => new Set(candidate.path.map(step => step.split(' ')[0])).size === candidate.path.length), 'expected no path to revisit a node');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(loopDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(loopDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(loopDb);
+  console.log('   ✅ Reciprocal edge loop guard working');
+
+  console.log('75. Testing content dedup on import...');
+  const dedupDb = `dedup_db_${testRunId}`;
+  const [dedupA, dedupB, dedupC, dedupCopyA, dedupCopyB] = ['a', 'b', 'c', 'copy_a', 'copy_b'].map(name => `test_dedup_${name}_${testRunId}`);
+  await importGraph({
+    chunks: [
+      { chunk_id: dedupA, text: 'Dedup import chunk about glacier calving.' },
+      { chunk_id: dedupB, text: 'Dedup import chunk about fjord sediment.' },
+    ],
+    connections: [{ source_chunk: dedupA, target_chunk: dedupB, relationship: 'supports', weight: 0.4 }],
+  }, dedupDb);
+
+  // The overlapping graph repeats A under a new id, with different spacing
+  const merged = await importGraph({
+    chunks: [
+      { chunk_id: dedupCopyA, text: '  Dedup import chunk about   glacier calving. ', access_count: 3 },
+      { chunk_id: dedupC, text: 'Dedup import chunk about meltwater pulses.' },
+    ],
+    connections: [
+      { source_chunk: dedupCopyA, target_chunk: dedupB, relationship: 'supports', weight: 0.7 },
+      { source_chunk: dedupCopyA, target_chunk: dedupC, relationship: 'supports', weight: 0.5 },
+    ],
+  }, dedupDb, 'reject', 'content_merge');
+  expect(merged.chunks_merged === 1 && merged.chunks_imported === 1 && merged.connections_merged === 1, `unexpected merge result ${JSON.stringify(merged)}`);
+  expect(getChunksByIds([dedupCopyA], dedupDb)[0] === null, 'expected the duplicate chunk not to be stored');
+  const glacierCount = (db.prepare(`SELECT COUNT(*) AS count FROM chunks WHERE database_id = ? AND text LIKE '%glacier%'`).get(dedupDb) as { count: number }).count;
+  expect(glacierCount === 1, `expected one glacier chunk, found ${glacierCount}`);
+  expect(getEdgeWeight(dedupA, dedupB, { relationship: 'supports', database: dedupDb }) === 0.7, 'expected the existing edge to keep the stronger weight');
+  expect(getEdgeWeight(dedupA, dedupC, { relationship: 'supports', database: dedupDb }) === 0.5, 'expected the duplicate edge to be rewired onto the survivor');
+  const dedupAccess = (db.prepare(`SELECT access_count FROM chunks WHERE chunk_id = ?`).get(dedupA) as { access_count: number }).access_count;
+  expect(dedupAccess === 3, `expected access counts to be summed, got ${dedupAccess}`);
+
+  // A frozen survivor edge keeps its curated weight through a merge
+  freezeEdge(dedupA, dedupB, { relationship: 'supports', database: dedupDb });
+  const frozenMerge = await importGraph({
+    chunks: [{ chunk_id: dedupCopyA, text: 'Dedup import chunk about glacier calving.' }],
+    connections: [{ source_chunk: dedupCopyA, target_chunk: dedupB, relationship: 'supports', weight: 0.95 }],
+  }, dedupDb, 'reject', 'content_merge');
+  expect(frozenMerge.connections_merged === 1, `expected the edge to be folded, got ${JSON.stringify(frozenMerge)}`);
+  expect(getEdgeWeight(dedupA, dedupB, { relationship: 'supports', database: dedupDb }) === 0.7, 'expected a frozen edge not to be strengthened by a merge');
+
+  const skipped = await importGraph({
+    chunks: [{ chunk_id: dedupCopyB, text: 'Dedup import chunk about fjord sediment.' }],
+    connections: [{ source_chunk: dedupCopyB, target_chunk: dedupC, relationship: 'supports', weight: 0.9 }],
+  }, dedupDb, 'reject', 'content');
+  expect(skipped.chunks_imported === 0 && skipped.chunks_skipped === 1 && skipped.connections_skipped === 1, `unexpected skip result ${JSON.stringify(skipped)}`);
+  expect(getEdgeWeight(dedupB, dedupC, { database: dedupDb }) === null, 'expected edges of a skipped duplicate to be dropped');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(dedupDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(dedupDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(dedupDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [dedupA, dedupB, dedupC] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Content dedup on import working');
+
+  console.log('76. Testing frozen edges...');
+  const frozenDb = `frozen_db_${testRunId}`;
+  const [frozenA, frozenB] = ['a', 'b'].map(name => `test_frozen_${name}_${testRunId}`);
+  const frozenEdge = `frozen_edge_${testRunId}`;
+  const frozenControl = `frozen_control_${testRunId}`;
+  const frozenOld = new Date(Date.now() - 30 * 24 * 60 * 60 * 1000).toISOString();
+  for (const chunkId of [frozenA, frozenB]) {
+    db.prepare(`
+      INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, database_id)
+      VALUES (?, ?, ?, ?, ?, ?)
+    `).run(chunkId, `Frozen edge chunk ${chunkId}`, 'frozen_test', frozenOld, 10, frozenDb);
+  }
+  const insertFrozenEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, last_reinforced, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertFrozenEdge.run(frozenEdge, frozenA, frozenB, 'supports', 0.4, frozenOld, frozenOld, frozenDb);
+  insertFrozenEdge.run(frozenControl, frozenB, frozenA, 'supports', 0.4, frozenOld, frozenOld, frozenDb);
+  expect(freezeEdge(frozenA, frozenB, { database: frozenDb }) === 1, 'expected one edge to be frozen');
+  const frozenWeight = (edgeId: string) => (db.prepare(`SELECT weight FROM connections WHERE edge_id = ?`).get(edgeId) as { weight: number } | undefined)?.weight;
+
+  decayConnections(7);
+  expect(frozenWeight(frozenEdge) === 0.4, `expected decay to skip the frozen edge, got ${frozenWeight(frozenEdge)}`);
+  expect((frozenWeight(frozenControl) ?? 1) < 0.4, 'expected the unfrozen edge to decay');
+
+  const decayedControl = frozenWeight(frozenControl) ?? 0;
+  reinforceConnections();
+  expect(frozenWeight(frozenEdge) === 0.4, `expected reinforcement to skip the frozen edge, got ${frozenWeight(frozenEdge)}`);
+  expect((frozenWeight(frozenControl) ?? 0) > decayedControl, 'expected the unfrozen edge to be reinforced');
+
+  const frozenSince = Date.now() - 1000;
+  db.prepare(`
+    INSERT INTO co_access_events (event_id, chunk_ids, query_hash, query_embedding, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?, ?)
+  `).run(uuidv4(), JSON.stringify([frozenA, frozenB]), `frozen_query_${testRunId}`, null, Date.now(), frozenDb);
+  await hebbianStrengthen(frozenSince);
+  expect(frozenWeight(frozenEdge) === 0.4, `expected Hebbian strengthening to skip the frozen edge, got ${frozenWeight(frozenEdge)}`);
+
+  // Stale and weak: pruning would take it if it were not frozen
+  pruneStaleConnections(7, 0.5, frozenDb);
+  expect(frozenWeight(frozenEdge) === 0.4, 'expected pruning to keep the frozen edge');
+  expect(unfreezeEdge(frozenA, frozenB, { database: frozenDb }) === 1, 'expected the edge to be unfrozen');
+  pruneStaleConnections(7, 0.5, frozenDb);
+  expect(frozenWeight(frozenEdge) === undefined, 'expected the unfrozen stale edge to be pruned');
+
+  db.prepare(`DELETE FROM co_access_events WHERE database_id = ?`).run(frozenDb);
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(frozenDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(frozenDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(frozenDb);
+  console.log('   ✅ Frozen edges working');
+
+  console.log('77. Testing frozen edge counts in stats...');
+  const frozenStatsDb = `frozen_stats_db_${testRunId}`;
+  const frozenStatsIds = ['a', 'b', 'c'].map(name => `test_frozen_stats_${name}_${testRunId}`);
+  const frozenStatsNow = new Date().toISOString();
+  const insertFrozenStatsEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertFrozenStatsEdge.run(`frozen_stats_ab_${testRunId}`, frozenStatsIds[0], frozenStatsIds[1], 'supports', 0.5, frozenStatsNow, frozenStatsDb);
+  insertFrozenStatsEdge.run(`frozen_stats_ab_rel_${testRunId}`, frozenStatsIds[0], frozenStatsIds[1], 'related_to', 0.5, frozenStatsNow, frozenStatsDb);
+  insertFrozenStatsEdge.run(`frozen_stats_bc_${testRunId}`, frozenStatsIds[1], frozenStatsIds[2], 'supports', 0.5, frozenStatsNow, frozenStatsDb);
+  expect(getGraphSummary(frozenStatsDb).frozen_connections === 0, 'expected no frozen edges before freezing');
+
+  freezeEdge(frozenStatsIds[0], frozenStatsIds[1], { database: frozenStatsDb });
+  freezeEdge(frozenStatsIds[1], frozenStatsIds[2], { database: frozenStatsDb });
+  expect(getGraphSummary(frozenStatsDb).frozen_connections === 3, `expected 3 frozen edges, got ${getGraphSummary(frozenStatsDb).frozen_connections}`);
+  unfreezeEdge(frozenStatsIds[0], frozenStatsIds[1], { relationship: 'related_to', database: frozenStatsDb });
+  expect(getGraphSummary(frozenStatsDb).frozen_connections === 2, 'expected unfreezing one relationship to drop the count');
+  expect(getGraphSummary(`${frozenStatsDb}_other`).frozen_connections === 0, 'expected counts to be per database');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(frozenStatsDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(frozenStatsDb);
+  console.log('   ✅ Frozen edge counts working');
+
+  console.log('78. Testing recall on empty and sparse graphs...');
+  const emptyRecallDb = `empty_recall_db_${testRunId}`;
+  const coAccessCount = (database: string) => (db.prepare(`SELECT COUNT(*) AS total FROM co_access_events WHERE database_id = ?`).get(database) as { total: number }).total;
+  expect((await retrieve('anything at all', { database: emptyRecallDb })).length === 0, 'expected an empty database to recall nothing');
+  expect(coAccessCount(emptyRecallDb) === 0, 'expected no co-access event for an empty recall');
+
+  // One real chunk plus a vector whose chunk row is missing
+  const sparseDb = `sparse_recall_db_${testRunId}`;
+  const sparseChunk = `test_sparse_chunk_${testRunId}`;
+  const sparseOrphan = `test_sparse_orphan_${testRunId}`;
+  const [sparsePoint, sparseOrphanPoint] = [uuidv4(), uuidv4()];
+  const sparseText = `Sparse recall ${testRunId} about lone lighthouse keepers.`;
+  const sparseVector = await embed(sparseText);
+  await qdrant.upsert(COLLECTION, {
+    wait: true,
+    points: [
+      { id: sparsePoint, vector: sparseVector, payload: { text: sparseText, source: 'sparse_test', chunk_id: sparseChunk, database_id: sparseDb } },
+      { id: sparseOrphanPoint, vector: sparseVector, payload: { text: sparseText, source: 'sparse_test', chunk_id: sparseOrphan, database_id: sparseDb } },
+    ],
+  });
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(sparseChunk, sparseText, 'sparse_test', new Date().toISOString(), sparseDb);
+
+  const sparseResults = await retrieve(sparseText, { database: sparseDb });
+  expect(sparseResults.length === 1 && sparseResults[0].chunk_id === sparseChunk, `expected only the stored chunk, got ${JSON.stringify(sparseResults.map(r => r.chunk_id))}`);
+  flushChunkAccess();
+  const sparseAccess = db.prepare(`SELECT chunk_id, access_count FROM chunks WHERE database_id = ?`).all(sparseDb) as Array<{ chunk_id: string; access_count: number }>;
+  expect(sparseAccess.length === 1 && sparseAccess[0].access_count >= 1, 'expected access on the stored chunk and no row for the orphan');
+  const sparseEvents = db.prepare(`SELECT chunk_ids FROM co_access_events WHERE database_id = ?`).all(sparseDb) as Array<{ chunk_ids: string }>;
+  expect(sparseEvents.every(event => !event.chunk_ids.includes(sparseOrphan)), 'expected the orphan to stay out of co-access events');
+
+  db.prepare(`DELETE FROM co_access_events WHERE database_id IN (?, ?)`).run(emptyRecallDb, sparseDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(sparseDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id IN (?, ?)`).run(emptyRecallDb, sparseDb);
+  try {
+    await qdrant.delete(COLLECTION, { points: [sparsePoint, sparseOrphanPoint] });
+  } catch {
+    // Best effort cleanup
+  }
+  console.log('   ✅ Recall on empty and sparse graphs working');
+
+  console.log('79. Testing per-chunk edge limit...');
+  const limitDb = `edge_limit_db_${testRunId}`;
+  const limitHub = `test_limit_hub_${testRunId}`;
+  const limitNow = new Date().toISOString();
+  const insertLimitEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertLimitEdge.run(`limit_weak_${testRunId}`, limitHub, `test_limit_a_${testRunId}`, 'related_to', 0.2, limitNow, limitDb);
+  insertLimitEdge.run(`limit_mid_${testRunId}`, `test_limit_b_${testRunId}`, limitHub, 'related_to', 0.5, limitNow, limitDb);
+  insertLimitEdge.run(`limit_strong_${testRunId}`, limitHub, `test_limit_c_${testRunId}`, 'related_to', 0.8, limitNow, limitDb);
+  const limitNewcomer = `test_limit_new_${testRunId}`;
+  expect(countIncidentEdges(limitHub, limitDb) === 3, 'expected the hub to start with three edges');
+  const ignoreLimitEdge = db.prepare(`
+    INSERT OR IGNORE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  const addLimitEdge = (name: string, source: string, target: string, limit: number, policy: 'reject' | 'evict_weakest') =>
+    insertWithinEdgeLimit(source, target, () => ignoreLimitEdge.run(`${name}_${testRunId}`, source, target, 'related_to', 0.4, limitNow, limitDb).changes, limitDb, limit, policy);
+  const limitEdgeIds = () => (db.prepare(`SELECT edge_id FROM connections WHERE database_id = ? ORDER BY edge_id`).all(limitDb) as Array<{ edge_id: string }>).map(row => row.edge_id);
+
+  expect(planEdgeAdmission(limitHub, limitNewcomer, limitDb, 4, 'reject')?.length === 0, 'expected room below the limit');
+  expect(addLimitEdge('limit_rejected', limitHub, limitNewcomer, 3, 'reject') === 0, 'expected reject to refuse a fourth edge');
+  expect(countIncidentEdges(limitHub, limitDb) === 3, 'expected reject to leave existing edges alone');
+
+  // An ignored insert (the triplet is already stored) must not cost an edge
+  expect(addLimitEdge('limit_duplicate', limitHub, `test_limit_c_${testRunId}`, 3, 'evict_weakest') === 0, 'expected the duplicate triplet to be ignored');
+  expect(limitEdgeIds().includes(`limit_weak_${testRunId}`), 'expected an ignored insert to evict nothing');
+
+  expect(addLimitEdge('limit_newcomer', limitNewcomer, limitHub, 3, 'evict_weakest') === 1, 'expected eviction to make room');
+  const limitRemaining = limitEdgeIds();
+  expect(!limitRemaining.includes(`limit_weak_${testRunId}`) && limitRemaining.length === 3, `expected only the weakest edge to go, left ${limitRemaining.join(', ')}`);
+
+  // A full peer that cannot give anything up refuses the edge before the hub loses one
+  const limitPeer = `test_limit_peer_${testRunId}`;
+  ['x', 'y', 'z'].forEach(name => insertLimitEdge.run(`limit_peer_${name}_${testRunId}`, limitPeer, `test_limit_${name}_${testRunId}`, 'related_to', 0.9, limitNow, limitDb));
+  db.prepare(`UPDATE connections SET frozen = 1 WHERE database_id = ? AND source_chunk = ?`).run(limitDb, limitPeer);
+  expect(addLimitEdge('limit_to_peer', limitHub, limitPeer, 3, 'evict_weakest') === 0, 'expected a frozen peer to refuse');
+  expect(countIncidentEdges(limitHub, limitDb) === 3, 'expected the hub to keep its edges when the peer refuses');
+
+  // Frozen edges are never evicted, so a hub full of them refuses new edges
+  db.prepare(`UPDATE connections SET frozen = 1 WHERE database_id = ?`).run(limitDb);
+  expect(planEdgeAdmission(limitHub, `test_limit_other_${testRunId}`, limitDb, 3, 'evict_weakest') === null, 'expected a hub of frozen edges to refuse');
+  expect(addLimitEdge('limit_unlimited', limitHub, `test_limit_other_${testRunId}`, 0, 'reject') === 1, 'expected a zero limit to admit anything');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(limitDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(limitDb);
+  console.log('   ✅ Per-chunk edge limit working');
+
+  console.log('80. Testing maintenance dry run...');
+  const previewDb = `preview_db_${testRunId}`;
+  const previewNow = new Date().toISOString();
+  const previewExpired = `test_preview_expired_${testRunId}`;
+  const [previewA, previewB] = ['a', 'b'].map(name => `test_preview_${name}_${testRunId}`);
+  const insertPreviewChunk = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, expires_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertPreviewChunk.run(previewExpired, 'A preview note that has already expired.', 'preview_test', previewNow, 0, new Date(Date.now() - 60_000).toISOString(), previewDb);
+  insertPreviewChunk.run(previewA, 'Preview chunk A.', 'preview_test', previewNow, 10, null, previewDb);
+  insertPreviewChunk.run(previewB, 'Preview chunk B.', 'preview_test', previewNow, 0, null, previewDb);
+  const insertPreviewEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertPreviewEdge.run(`preview_live_${testRunId}`, previewA, previewB, 'supports', 0.5, previewNow, previewDb);
+  insertPreviewEdge.run(`preview_expiring_${testRunId}`, previewExpired, previewB, 'supports', 0.5, previewNow, previewDb);
+  insertPreviewEdge.run(`preview_dead_${testRunId}`, previewB, previewA, 'supports', 0, previewNow, previewDb);
+
+  const previewSnapshot = () => JSON.stringify({
+    edges: db.prepare(`SELECT edge_id, weight, last_reinforced FROM connections WHERE database_id = ? ORDER BY edge_id`).all(previewDb),
+    chunks: db.prepare(`SELECT chunk_id FROM chunks WHERE database_id = ? ORDER BY chunk_id`).all(previewDb),
+    version: getGraphVersion(previewDb),
+  });
+  const previewPhases = parseConsolidationPhases('reinforce,decay,prune,hebbian');
+  const beforePreview = previewSnapshot();
+  const preview = await previewMaintenance({ phases: previewPhases });
+  expect(previewSnapshot() === beforePreview, 'expected the dry run to change nothing');
+  expect(preview.dry_run === true && preview.skipped_phases?.join(',') === 'hebbian', `expected hebbian to be skipped, got ${preview.skipped_phases}`);
+  expect(preview.expired_chunks >= 1 && preview.reinforced >= 1 && preview.decayed >= 1 && preview.dead_edges_swept >= 1, `expected every step to find work, got ${JSON.stringify(preview)}`);
+
+  const realPass = await runMaintenance({ phases: parseConsolidationPhases('reinforce,decay,prune') });
+  const counts = (stats: typeof preview) => [stats.expired_chunks, stats.reinforced, stats.decayed, stats.pruned, stats.dead_edges_swept].join(',');
+  expect(counts(realPass) === counts(preview), `expected the real pass to match the dry run, ${counts(realPass)} vs ${counts(preview)}`);
+  expect(getChunksByIds([previewExpired], previewDb)[0] === null && getEdgeWeight(previewB, previewA, { database: previewDb }) === null, 'expected the real pass to apply the changes');
+
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(previewDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(previewDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(previewDb);
+  console.log('   ✅ Maintenance dry run working');
+
+  console.log('81. Testing unknown expansion seeds...');
+  const seedDb = `seed_db_${testRunId}`;
+  const knownSeed = `test_seed_known_${testRunId}`;
+  const typoSeed = `test_seed_typo_${testRunId}`;
+  db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id)
+    VALUES (?, ?, ?, ?, ?)
+  `).run(knownSeed, 'A stored seed chunk.', 'seed_test', new Date().toISOString(), seedDb);
+  const seedOf = (chunkId: string) => ({ chunkId, score: 1, hopDepth: 0, path: [chunkId], vectorScore: 1 });
+
+  const knownExpansion = await multiHopExpand([seedOf(knownSeed)], new Set(), { database: seedDb, maxHops: 2, validateSeeds: true });
+  expect(knownExpansion.length === 1 && knownExpansion[0].chunkId === knownSeed, 'expected a stored seed to expand normally');
+  let seedError: unknown = null;
+  try {
+    await multiHopExpand([seedOf(knownSeed), seedOf(typoSeed), seedOf(typoSeed)], new Set(), { database: seedDb, maxHops: 2, validateSeeds: true });
+  } catch (error) {
+    seedError = error;
+  }
+  expect(seedError instanceof UnknownSeedError && seedError.seeds.join(',') === typoSeed, `expected the unknown seed to be listed once, got ${seedError}`);
+  let otherDbError: unknown = null;
+  try {
+    await multiHopExpand([seedOf(knownSeed)], new Set(), { database: `${seedDb}_other`, maxHops: 0, validateSeeds: true });
+  } catch (error) {
+    otherDbError = error;
+  }
+  expect(otherDbError instanceof UnknownSeedError, 'expected a seed from another database to count as unknown');
+  const unvalidated = await multiHopExpand([seedOf(typoSeed)], new Set(), { database: seedDb, maxHops: 2 });
+  expect(unvalidated.length === 1 && unvalidated[0].chunkId === typoSeed, 'expected seeds to go unchecked without validateSeeds');
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(seedDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(seedDb);
+  console.log('   ✅ Unknown expansion seeds working');
+
+  console.log('82. Testing persisted database config...');
+  const configDb = createDatabase(`config_db_${testRunId}`);
+  expect(JSON.stringify(getDatabaseConfig(configDb.name)) === '{}', 'expected a new database to start without overrides');
+  updateDatabaseConfig(configDb.name, { hopDecay: 0.5, minContentLength: 12 });
+  const storedConfig = JSON.parse(getDatabaseByName(configDb.name)?.config_json ?? '{}');
+  expect(storedConfig.hopDecay === 0.5 && storedConfig.minContentLength === 12, `expected the change to be written to the catalog, got ${JSON.stringify(storedConfig)}`);
+  const cleared = updateDatabaseConfig(configDb.name, { minContentLength: null, degreeBoost: 0.2 });
+  expect(cleared.hopDecay === 0.5 && cleared.degreeBoost === 0.2 && cleared.minContentLength === undefined, `expected null to remove a key and the rest to merge, got ${JSON.stringify(cleared)}`);
+  let configError = '';
+  try {
+    updateDatabaseConfig(configDb.name, { hopDecay: 1.5 });
+  } catch (error) {
+    configError = error instanceof Error ? error.message : String(error);
+  }
+  expect(configError.includes('hopDecay') && getDatabaseConfig(configDb.name).hopDecay === 0.5, 'expected an out-of-range value to be rejected without writing');
+
+  // Recall picks the stored defaults up for options the caller leaves unset
+  const configText = `A short note on dentate gyrus neurogenesis (${testRunId}).`;
+  await ingestText('config_test', configText, ['test'], {}, configDb.name);
+  const configRecall = (options: { minContentLength?: number } = {}) =>
+    retrieve('dentate gyrus neurogenesis', { database: configDb.name, recordAccess: false, ...options });
+  const beforeDefault = await configRecall();
+  updateDatabaseConfig(configDb.name, { minContentLength: configText.length + 1 });
+  const withDefault = await configRecall();
+  const overridden = await configRecall({ minContentLength: 0 });
+  expect(beforeDefault.length > 0, 'expected the note to be recalled without a stored default');
+  expect(withDefault.length === 0, `expected the stored minContentLength to filter the note, got ${withDefault.length} results`);
+  expect(overridden.length === beforeDefault.length, 'expected an explicit option to win over the stored default');
+
+  await clearDatabase(configDb.name);
+  deleteDatabase(configDb.name);
+  console.log('   ✅ Persisted database config working');
+
+  console.log('83. Testing ingest version check at commit...');
+  const ingestCasDb = `ingest_cas_db_${testRunId}`;
+  db.prepare(`
+    INSERT INTO graph_versions (database_id, version) VALUES (?, 5)
+    ON CONFLICT(database_id) DO UPDATE SET version = 5
+  `).run(ingestCasDb);
+  const callIngest = (request: IngestRequest) => new Promise<{ error: grpc.ServiceError | null; response?: IngestResponse }>(resolve => {
+    ingestHandler({ request } as grpc.ServerUnaryCall<IngestRequest, IngestResponse>, (error, response) => {
+      resolve({ error: error as grpc.ServiceError | null, response: response ?? undefined });
+    });
+  });
+  const casText = `Grid cells in the entorhinal cortex tile space with hexagonal firing fields (${testRunId}).`;
+  const casChunkCount = () => (db.prepare(`SELECT COUNT(*) AS total FROM chunks WHERE database_id = ?`).get(ingestCasDb) as { total: number }).total;
+
+  // The early check passes against version 5; another writer lands while the handler is embedding
+  const racing = callIngest({ source: 'cas_test', text: casText, expected_version: 5, database: ingestCasDb });
+  db.prepare(`UPDATE graph_versions SET version = version + 1 WHERE database_id = ?`).run(ingestCasDb);
+  const raced = await racing;
+  expect(raced.error?.code === grpc.status.ABORTED, `expected ABORTED when the version moved before commit, got ${raced.error?.code}`);
+  expect(casChunkCount() === 0, 'expected the aborted ingest to write nothing');
+
+  const retried = await callIngest({ source: 'cas_test', text: casText, expected_version: getGraphVersion(ingestCasDb), database: ingestCasDb });
+  expect(retried.error === null && (retried.response?.chunks_stored ?? 0) > 0, `expected a retry on the current version to store, got ${retried.error?.message}`);
+  expect(casChunkCount() === retried.response?.chunks_stored, 'expected the chunks in the requested database');
+
+  const casIds = (db.prepare(`SELECT chunk_id FROM chunks WHERE database_id = ?`).all(ingestCasDb) as Array<{ chunk_id: string }>).map(row => row.chunk_id);
+  await qdrant.delete(COLLECTION, { points: casIds });
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(ingestCasDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(ingestCasDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(ingestCasDb);
+  console.log('   ✅ Ingest version check at commit working');
+
+  console.log('84. Testing the confidence range across write paths...');
+  const rangeDb = `confidence_range_db_${testRunId}`;
+  const storedConfidence = (source: string) => (db.prepare(`SELECT confidence FROM chunks WHERE database_id = ? AND source = ?`).all(rangeDb, source) as Array<{ confidence: number }>).map(row => row.confidence);
+
+  await ingestText('range_text', `Place cells fire when an animal occupies one spot in its arena (${testRunId}).`, [], { confidence: 0 }, rangeDb);
+  expect(storedConfidence('range_text').every(value => value === 0) && storedConfidence('range_text').length > 0, 'expected ingestText to store confidence 0');
+
+  const grpcZero = await callIngest({ source: 'range_grpc', text: `Head direction cells track which way the animal faces (${testRunId}).`, confidence: 0, database: rangeDb });
+  expect(grpcZero.error === null && storedConfidence('range_grpc').every(value => value === 0) && storedConfidence('range_grpc').length > 0, `expected gRPC Ingest to store confidence 0, got ${grpcZero.error?.message}`);
+  const grpcUnset = await callIngest({ source: 'range_grpc_unset', text: `Border cells fire near the walls of an enclosure (${testRunId}).`, database: rangeDb });
+  expect(grpcUnset.error === null && storedConfidence('range_grpc_unset').every(value => value === 1), 'expected an unset gRPC confidence to default to 1');
+  const grpcBad = await callIngest({ source: 'range_grpc_bad', text: `Out of range gRPC confidence ${testRunId}.`, confidence: 1.5, database: rangeDb });
+  expect(grpcBad.error?.code === grpc.status.INVALID_ARGUMENT && storedConfidence('range_grpc_bad').length === 0, 'expected gRPC Ingest to reject confidence above 1');
+
+  const rangeImportId = `test_range_import_${testRunId}`;
+  const rangeChunk = { chunk_id: rangeImportId, text: `Speed cells scale their firing with running speed (${testRunId}).`, source: 'range_import', page: 0, timestamp: new Date().toISOString(), access_count: 0, tags: '[]', metadata: '{}' };
+  await importGraphRecords([{ database: rangeDb, chunk: { ...rangeChunk, confidence: 0 } }], rangeDb);
+  expect(storedConfidence('range_import').join(',') === '0', `expected an imported confidence of 0 to be kept, got ${storedConfidence('range_import').join(',')}`);
+  let importRejected: unknown = null;
+  try {
+    await importGraphRecords([{ database: rangeDb, chunk: { ...rangeChunk, chunk_id: `${rangeImportId}_bad`, source: 'range_import_bad', confidence: 1.5 } }], rangeDb);
+  } catch (error) {
+    importRejected = error;
+  }
+  expect(importRejected instanceof InvalidGraphRecordError && storedConfidence('range_import_bad').length === 0, 'expected the import to reject confidence above 1');
+
+  const rangeIds = (db.prepare(`SELECT chunk_id FROM chunks WHERE database_id = ?`).all(rangeDb) as Array<{ chunk_id: string }>).map(row => row.chunk_id);
+  try {
+    await qdrant.delete(COLLECTION, { points: rangeIds });
+  } catch {
+    // Best effort cleanup
+  }
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(rangeDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(rangeDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(rangeDb);
+  console.log('   ✅ Confidence range across write paths working');
+
+  console.log('85. Testing novelty on ingest...');
+  const noveltyDb = `novelty_db_${testRunId}`;
+  const noveltyText = `Dentate gyrus granule cells separate similar experiences into distinct codes (${testRunId}).`;
+  const noveltyOf = (source: string) => (db.prepare(`SELECT novelty FROM chunks WHERE database_id = ? AND source = ?`).all(noveltyDb, source) as Array<{ novelty: number | null }>).map(row => row.novelty);
+
+  const firstNovel = await ingestText('novelty_first', noveltyText, [], {}, noveltyDb);
+  expect(firstNovel.novelty !== null && firstNovel.novelty > 0.9, `expected the first chunk in an empty database to be novel, got ${firstNovel.novelty}`);
+
+  // Raising the threshold lets a near-duplicate through so its novelty can be measured
+  const nearDuplicate = await ingestText('novelty_copy', noveltyText.replace('distinct', 'clearly distinct'), [], { duplicateThreshold: 1.01 }, noveltyDb);
+  const copyNovelty = noveltyOf('novelty_copy');
+  expect(nearDuplicate.chunks_stored === 1 && copyNovelty.length === 1 && copyNovelty[0] !== null && copyNovelty[0] < 0.1, `expected a near-duplicate to score low novelty, got ${copyNovelty.join(',')}`);
+  expect(nearDuplicate.novelty === copyNovelty[0], 'expected the result to report the stored novelty');
+
+  const unrelated = await ingestText('novelty_unrelated', `Sourdough starters need regular feeding with flour and water (${testRunId}).`, [], { duplicateThreshold: 1.01 }, noveltyDb);
+  const unrelatedNovelty = noveltyOf('novelty_unrelated');
+  expect(unrelatedNovelty.length === 1 && unrelatedNovelty[0] !== null && unrelatedNovelty[0] > 0.4, `expected unrelated text to score high novelty, got ${unrelatedNovelty.join(',')}`);
+  expect(unrelated.novelty !== null && unrelated.novelty > copyNovelty[0]!, 'expected unrelated text to be more novel than the near-duplicate');
+
+  const noveltyIds = (db.prepare(`SELECT chunk_id FROM chunks WHERE database_id = ?`).all(noveltyDb) as Array<{ chunk_id: string }>).map(row => row.chunk_id);
+  try {
+    await qdrant.delete(COLLECTION, { points: noveltyIds });
+  } catch {
+    // Best effort cleanup
+  }
+  db.prepare(`DELETE FROM connections WHERE database_id = ?`).run(noveltyDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(noveltyDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(noveltyDb);
+  console.log('   ✅ Novelty on ingest working');
+
+  console.log('86. Testing graph export and import over gRPC...');
+  const transferDb = `grpc_transfer_db_${testRunId}`;
+  const transferImportDb = `grpc_transfer_import_db_${testRunId}`;
+  const transferIds = ['a', 'b', 'c'].map(name => `test_grpc_transfer_${name}_${testRunId}`);
+  const transferNow = new Date().toISOString();
+  const insertTransferChunk = db.prepare(`
+    INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, access_count, confidence, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertTransferChunk.run(transferIds[0], `Mossy fibres link the dentate gyrus to CA3 (${testRunId}).`, 'grpc_transfer', transferNow, 3, 0.4, transferDb);
+  insertTransferChunk.run(transferIds[1], `CA3 recurrent collaterals support pattern completion (${testRunId}).`, 'grpc_transfer', transferNow, 0, 1, transferDb);
+  insertTransferChunk.run(transferIds[2], `Schaffer collaterals carry CA3 output to CA1 (${testRunId}).`, 'grpc_transfer', transferNow, 1, 1, transferDb);
+  const insertTransferEdge = db.prepare(`
+    INSERT OR REPLACE INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, confidence, created_at, database_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+  `);
+  insertTransferEdge.run(`grpc_transfer_ab_${testRunId}`, transferIds[0], transferIds[1], 'supports', 0.65, 0.7, transferNow, transferDb);
+  insertTransferEdge.run(`grpc_transfer_bc_${testRunId}`, transferIds[1], transferIds[2], 'related_to', 0.3, 0.5, transferNow, transferDb);
+
+  // A real server and client, so records go through the proto and the streaming handlers
+  const transferProto = grpc.loadPackageDefinition(protoLoader.loadSync(path.join(__dirname, '..', 'proto', 'hippocampus.proto'), {
+    keepCase: true,
+    longs: String,
+    enums: String,
+    defaults: true,
+    oneofs: true,
+  })) as any;
+  const transferServer = new grpc.Server(grpcServerOptions());
+  transferServer.addService(transferProto.hippocampus.Hippocampus.service, { ExportGraph: exportGraphHandler, ImportGraph: importGraphHandler });
+  const transferPort = await new Promise<number>((resolve, reject) => {
+    transferServer.bindAsync('127.0.0.1:0', grpc.ServerCredentials.createInsecure(), (error, boundPort) => {
+      if (error) reject(error);
+      else resolve(boundPort);
+    });
+  });
+  const transferClient = new transferProto.hippocampus.Hippocampus(`127.0.0.1:${transferPort}`, grpc.credentials.createInsecure());
+  const exportOverGrpc = (request: Record<string, unknown>) => new Promise<{ records: GraphRecord[]; error: grpc.ServiceError | null }>(resolve => {
+    const records: GraphRecord[] = [];
+    const stream = transferClient.ExportGraph(request);
+    stream.on('data', (record: GraphRecord) => records.push(record));
+    stream.on('end', () => resolve({ records, error: null }));
+    stream.on('error', (error: grpc.ServiceError) => resolve({ records, error }));
+  });
+  const importOverGrpc = (records: GraphRecord[]) => new Promise<{ result?: ImportGraphResult; error: grpc.ServiceError | null }>(resolve => {
+    const stream = transferClient.ImportGraph((error: grpc.ServiceError | null, result?: ImportGraphResult) => resolve({ result, error }));
+    for (const record of records) stream.write(record);
+    stream.end();
+  });
+
+  try {
+    // Page size 1 makes the handler stream across several pages
+    const exported = await exportOverGrpc({ database: transferDb, page_size: 1 });
+    expect(exported.error === null, `expected the export to succeed, got ${exported.error?.message}`);
+    expect(exported.records.length === 5, `expected 5 streamed records, got ${exported.records.length}`);
+    expect(exported.records.slice(0, 3).every(record => record.chunk) && exported.records.slice(3).every(record => record.connection), 'expected chunks streamed before connections');
+
+    const imported = await importOverGrpc(exported.records.map(record => ({ ...record, database: transferImportDb })));
+    expect(imported.error === null, `expected the import to succeed, got ${imported.error?.message}`);
+    expect(imported.result?.chunks_imported === 3 && imported.result?.connections_imported === 2, `expected 3 chunks and 2 connections imported, got ${JSON.stringify(imported.result)}`);
+    expect(getEdgeWeight(transferIds[0], transferIds[1], { database: transferImportDb }) === 0.65, 'expected the edge weight to survive the round trip');
+    const transferred = getChunksByIds([transferIds[0]], transferImportDb)[0];
+    expect(transferred?.access_count === 3 && transferred?.confidence === 0.4, 'expected access_count and confidence to survive the round trip');
+
+    const badPrecision = await exportOverGrpc({ database: transferDb, weight_precision: 'f32' });
+    expect(badPrecision.error?.code === grpc.status.INVALID_ARGUMENT && badPrecision.records.length === 0, `expected INVALID_ARGUMENT for an unknown precision, got ${badPrecision.error?.code}`);
+
+    const badChunk = { ...exported.records[0].chunk!, chunk_id: `test_grpc_transfer_bad_${testRunId}`, confidence: 1.5 };
+    const rejected = await importOverGrpc([{ database: transferImportDb, chunk: badChunk }]);
+    expect(rejected.error?.code === grpc.status.INVALID_ARGUMENT, `expected INVALID_ARGUMENT for an out-of-range record, got ${rejected.error?.code}`);
+    expect(getChunksByIds([badChunk.chunk_id], transferImportDb)[0] === null, 'expected the rejected record to write nothing');
+  } finally {
+    transferClient.close();
+    transferServer.forceShutdown();
+  }
+
+  try {
+    await qdrant.delete(COLLECTION, { points: transferIds });
+  } catch {
+    // Best effort cleanup
+  }
+  db.prepare(`DELETE FROM connections WHERE database_id IN (?, ?)`).run(transferDb, transferImportDb);
+  db.prepare(`DELETE FROM chunks WHERE database_id IN (?, ?)`).run(transferDb, transferImportDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id IN (?, ?)`).run(transferDb, transferImportDb);
+  console.log('   ✅ Graph export and import over gRPC working');
+
+  console.log('87. Testing store retries on deletes...');
+  const retryDeleteDb = `retry_delete_db_${testRunId}`;
+  const retryDeleteId = `test_retry_delete_${testRunId}`;
+  db.prepare(`INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id) VALUES (?, ?, ?, ?, ?)`).run(retryDeleteId, 'Retry delete chunk.', 'retry_delete_test', new Date().toISOString(), retryDeleteDb);
+  createDatabase(retryDeleteDb, 'delete retry test db');
+  const realQdrantDelete = qdrant.delete.bind(qdrant);
+  let qdrantDeleteCalls = 0;
+  // The first call of each delete drops its connection, as a restarting Qdrant would
+  qdrant.delete = (async (...args: Parameters<typeof qdrant.delete>) => {
+    qdrantDeleteCalls++;
+    if (qdrantDeleteCalls % 2 === 1) throw Object.assign(new Error('socket hang up'), { code: 'ECONNRESET' });
+    return realQdrantDelete(...args);
+  }) as typeof qdrant.delete;
+  try {
+    const retriedDelete = await deleteChunks([retryDeleteId], retryDeleteDb);
+    expect(retriedDelete.deleted_count === 1 && qdrantDeleteCalls === 2, `expected the chunk delete to be retried once, got ${qdrantDeleteCalls} calls`);
+    await clearDatabase(retryDeleteDb);
+    expect(qdrantDeleteCalls === 4, `expected the clear to be retried once, got ${qdrantDeleteCalls - 2} calls`);
+  } finally {
+    qdrant.delete = realQdrantDelete;
+  }
+  deleteDatabase(retryDeleteDb);
+  console.log('   ✅ Store retries on deletes working');
+
+  console.log('88. Testing the version check with capacity eviction...');
+  const evictCasDb = `evict_cas_db_${testRunId}`;
+  const evictCasIds = [0, 1, 2].map(index => `test_evict_cas_${index}_${testRunId}`);
+  const evictCasNew = `test_evict_cas_new_${testRunId}`;
+  const insertEvictCas = db.prepare(`INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id) VALUES (?, ?, ?, ?, ?)`);
+  // Oldest first, so the first chunk is the eviction victim
+  evictCasIds.forEach((chunkId, index) => insertEvictCas.run(chunkId, `Evict version chunk ${index}.`, 'evict_cas_test', new Date(Date.now() - (3 - index) * 60_000).toISOString(), evictCasDb));
+  const storeEvictCasChunk = () => insertEvictCas.run(evictCasNew, 'Evict version newcomer.', 'evict_cas_test', new Date().toISOString(), evictCasDb).changes;
+  const evictCasPresent = () => getChunksByIds([...evictCasIds, evictCasNew], evictCasDb).map(row => row !== null).join(',');
+  const evictCasOptions = { limit: 3, policy: 'evict' as const, maxEvictions: 0 };
+
+  const staleEvictVersion = getGraphVersion(evictCasDb);
+  db.prepare(`UPDATE graph_versions SET version = version + 1 WHERE database_id = ?`).run(evictCasDb);
+  let evictConflict: unknown = null;
+  try {
+    await writeWithinCapacity(1, evictCasDb, storeEvictCasChunk, { ...evictCasOptions, expectedVersion: staleEvictVersion });
+  } catch (error) {
+    evictConflict = error;
+  }
+  expect(evictConflict instanceof GraphVersionConflictError, 'expected a stale version to abort the write');
+  expect(evictCasPresent() === 'true,true,true,false', `expected nothing evicted or stored on a conflict, got ${evictCasPresent()}`);
+
+  // The eviction's own deletes move the version on; that must not fail the check it came after
+  const evictWritten = await writeWithinCapacity(1, evictCasDb, storeEvictCasChunk, { ...evictCasOptions, expectedVersion: getGraphVersion(evictCasDb) });
+  expect(evictWritten.evicted === 1 && evictWritten.result === 1, `expected one eviction and one insert, got ${JSON.stringify(evictWritten)}`);
+  expect(evictCasPresent() === 'false,true,true,true', `expected the oldest chunk to make room, got ${evictCasPresent()}`);
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(evictCasDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(evictCasDb);
+  console.log('   ✅ Version check with capacity eviction working');
+
+  console.log('89. Testing capacity checks at commit...');
+  const commitCapDb = `commit_cap_db_${testRunId}`;
+  const commitCapOld = `test_commit_cap_old_${testRunId}`;
+  const insertCommitCap = db.prepare(`INSERT OR REPLACE INTO chunks (chunk_id, text, source, timestamp, database_id) VALUES (?, ?, ?, ?, ?)`);
+  insertCommitCap.run(commitCapOld, 'Commit cap oldest chunk.', 'commit_cap_test', new Date(Date.now() - 60_000).toISOString(), commitCapDb);
+  const commitCapCount = () => (db.prepare(`SELECT COUNT(*) AS total FROM chunks WHERE database_id = ?`).get(commitCapDb) as { total: number }).total;
+
+  // The write sees what was evicted, so edges to the victim can be left out
+  let seenEvicted: string[] = [];
+  await writeWithinCapacity(1, commitCapDb, (evicted) => {
+    seenEvicted = [...evicted];
+    return insertCommitCap.run(`test_commit_cap_a_${testRunId}`, 'Commit cap newcomer A.', 'commit_cap_test', new Date().toISOString(), commitCapDb).changes;
+  }, { limit: 1, policy: 'evict', maxEvictions: 0 });
+  expect(seenEvicted.join(',') === commitCapOld, `expected the write to be told about the evicted chunk, got ${seenEvicted.join(',')}`);
+
+  // Two ingests racing for the last free slot: the count and the insert commit together, so only one fits
+  const racingWrites = await Promise.allSettled(['b', 'c'].map(name => writeWithinCapacity(1, commitCapDb, () => (
+    insertCommitCap.run(`test_commit_cap_${name}_${testRunId}`, `Commit cap newcomer ${name}.`, 'commit_cap_test', new Date().toISOString(), commitCapDb).changes
+  ), { limit: 2, policy: 'reject' })));
+  expect(racingWrites.filter(outcome => outcome.status === 'fulfilled').length === 1, 'expected exactly one racing write to fit');
+  expect(racingWrites.some(outcome => outcome.status === 'rejected' && outcome.reason instanceof CapacityExceededError), 'expected the other to be rejected for capacity');
+  expect(commitCapCount() === 2, `expected the cap to hold, got ${commitCapCount()} chunks`);
+
+  db.prepare(`DELETE FROM chunks WHERE database_id = ?`).run(commitCapDb);
+  db.prepare(`DELETE FROM graph_versions WHERE database_id = ?`).run(commitCapDb);
+  console.log('   ✅ Capacity checks at commit working');
+
   console.log('\n✅ All tests passed.\n');
 }
 
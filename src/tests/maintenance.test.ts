@@ -0,0 +1,127 @@
+// src/tests/maintenance.test.ts
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+import { withMaintenanceLock, isMaintenanceRunning } from '../db/maintenance';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+const sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
+async function runTests(): Promise<void> {
+  let passed = 0;
+  let failed = 0;
+
+  const test = async (name: string, fn: () => Promise<void>) => {
+    try {
+      await fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('maintenance lock tests:\n');
+
+  // Test 1: consolidation and clear never interleave
+  await test('concurrent maintenance tasks run one at a time', async () => {
+    const log: string[] = [];
+    const task = (name: string, ms: number) => withMaintenanceLock(async () => {
+      log.push(`${name}:start`);
+      await sleep(ms);
+      log.push(`${name}:end`);
+      return name;
+    });
+
+    const results = await Promise.all([task('consolidate', 20), task('clear', 5), task('sweep', 10)]);
+    assert(results.join(',') === 'consolidate,clear,sweep', 'each caller should get its own result');
+    assert(
+      log.join(',') === 'consolidate:start,consolidate:end,clear:start,clear:end,sweep:start,sweep:end',
+      `tasks interleaved: ${log.join(',')}`,
+    );
+  });
+
+  // Test 2: lock state is visible while held
+  await test('reports when maintenance is running', async () => {
+    assert(!isMaintenanceRunning(), 'lock should start free');
+    const pending = withMaintenanceLock(async () => {
+      await sleep(10);
+      return isMaintenanceRunning();
+    });
+    assert(await pending, 'lock should be held inside the task');
+    assert(!isMaintenanceRunning(), 'lock should be released afterwards');
+  });
+
+  // Test 3: failures release the lock
+  await test('a failing task does not block the queue', async () => {
+    const failing = withMaintenanceLock(async () => {
+      throw new Error('boom');
+    });
+    const next = withMaintenanceLock(async () => 'ran');
+
+    let rejected = false;
+    try {
+      await failing;
+    } catch {
+      rejected = true;
+    }
+    assert(rejected, 'the failing task should reject');
+    assert(await next === 'ran', 'the next task should still run');
+  });
+
+  // Test 4: a real consolidation pass and a database clear queue behind each other
+  await test('consolidateAll and clearDatabase are serialised', async () => {
+    // A throwaway SQLite file, so the pass never touches a real graph; Qdrant
+    // is not needed, clearDatabase only warns when its cleanup cannot reach it
+    const dbPath = path.join(os.tmpdir(), `hippocampus-maintenance-${process.pid}.db`);
+    process.env.DB_PATH = dbPath;
+    const { db, initSQLite } = await import('../db');
+    const { createDatabase, clearDatabase } = await import('../db/memoryDatabase');
+    const { consolidateAll } = await import('../consolidate');
+    initSQLite();
+
+    const name = 'maintenance_lock_test';
+    createDatabase(name, 'maintenance lock test db');
+    const now = new Date().toISOString();
+    db.prepare(`INSERT INTO chunks (chunk_id, text, source, timestamp, database_id) VALUES (?, ?, ?, ?, ?)`).run('lock_a', 'Lock test chunk.', 'lock_test', now, name);
+    db.prepare(`
+      INSERT INTO connections (edge_id, source_chunk, target_chunk, relationship, weight, created_at, database_id)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+    `).run('lock_edge', 'lock_a', 'lock_b', 'related_to', 0.8, now, name);
+    const countRows = () => (db.prepare(`SELECT COUNT(*) AS total FROM chunks WHERE database_id = ?`).get(name) as { total: number }).total;
+
+    try {
+      const log: string[] = [];
+      let release: () => void = () => {};
+      const gate = withMaintenanceLock(() => new Promise<void>(resolve => { release = resolve; }));
+      const clearing = clearDatabase(name).then(result => { log.push('clear'); return result; });
+      const consolidating = consolidateAll(['decay', 'prune']).then(() => { log.push('consolidate'); });
+
+      await sleep(20);
+      assert(isMaintenanceRunning() && log.length === 0, 'both should wait while the lock is held');
+      assert(countRows() === 1, 'the clear should not start while another task holds the lock');
+
+      release();
+      const [, cleared] = await Promise.all([gate, clearing, consolidating]);
+      // The clear awaits its Qdrant cleanup; an unserialised pass would finish inside that await
+      assert(log.join(',') === 'clear,consolidate', `expected the clear to finish before consolidation, got ${log.join(',')}`);
+      assert(cleared.chunks === 1 && cleared.connections === 1, 'the clear should remove the seeded rows');
+      assert(!isMaintenanceRunning(), 'lock should be released afterwards');
+    } finally {
+      db.close();
+      for (const suffix of ['', '-wal', '-shm']) fs.rmSync(`${dbPath}${suffix}`, { force: true });
+    }
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+void runTests();
@@ -0,0 +1,51 @@
+// src/tests/novelty.test.ts
+import { noveltyScore } from '../ingest/filters';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+function runTests(): void {
+  let passed = 0;
+  let failed = 0;
+
+  const test = (name: string, fn: () => void) => {
+    try {
+      fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('novelty tests:\n');
+
+  // Test 1: a near-duplicate of something stored is barely novel
+  test('a near-duplicate scores low', () => {
+    const novelty = noveltyScore(0.98);
+    assert(Math.abs(novelty - 0.02) < 1e-9, `expected 0.02, got ${novelty}`);
+  });
+
+  // Test 2: unrelated content is highly novel
+  test('unrelated content scores high', () => {
+    assert(noveltyScore(0.1) > 0.85, `expected high novelty, got ${noveltyScore(0.1)}`);
+    assert(noveltyScore(0) === 1, 'an empty store should give full novelty');
+  });
+
+  // Test 3: out-of-range similarities stay inside [0, 1]
+  test('scores are clamped to [0, 1]', () => {
+    assert(noveltyScore(1.0000001) === 0, 'similarity above 1 should clamp to 0 novelty');
+    assert(noveltyScore(-0.4) === 1, 'negative cosine similarity should clamp to full novelty');
+    assert(noveltyScore(Number.NaN) === 1, 'a missing score counts as no match');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+runTests();
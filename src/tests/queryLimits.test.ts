@@ -0,0 +1,51 @@
+// src/tests/queryLimits.test.ts
+import { checkQueryLimits } from '../server/helpers';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+function runTests(): void {
+  let passed = 0;
+  let failed = 0;
+
+  const test = (name: string, fn: () => void) => {
+    try {
+      fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  const limits = { maxTopK: 50, maxHops: 4 };
+
+  console.log('query limit tests:\n');
+
+  // Test 1: requests at the ceiling run
+  test('values at the limit are accepted', () => {
+    assert(checkQueryLimits({ topK: 50, maxHops: 4, exactDepth: 4 }, limits) === null, 'the limits themselves are allowed');
+    assert(checkQueryLimits({}, limits) === null, 'defaults are allowed');
+  });
+
+  // Test 2: an over-deep traversal is refused
+  test('over-large depth is rejected', () => {
+    assert(checkQueryLimits({ maxHops: 1000 }, limits) === 'max_hops must be at most 4', 'max_hops over the cap should fail');
+    assert(checkQueryLimits({ exactDepth: 5 }, limits) === 'exact_depth must be at most 4', 'exact_depth widens expansion, so it is capped too');
+  });
+
+  // Test 3: result counts have their own ceiling
+  test('over-large top_k is rejected', () => {
+    assert(checkQueryLimits({ topK: 51 }, limits) === 'top_k must be at most 50', 'top_k over the cap should fail');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+runTests();
@@ -0,0 +1,94 @@
+// src/tests/recallCache.test.ts
+import { nextCacheCapacity, getCachedRecall, setCachedRecall, clearRecallCache } from '../retrieve/cache';
+import type { Result } from '../retrieve';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+const bounds = { min: 50, max: 400 };
+const calm = { hitRate: 0.05, full: true, heapUsedRatio: 0.3 };
+const cachedResult: Result = {
+  text: 'note',
+  source: 'notes.md',
+  score: 1,
+  chunk_id: 'chunk-a',
+  graph_boosted: false,
+  retrieval_layer: 'vector',
+  path: ['chunk-a'],
+  conflicts: [],
+};
+
+function runTests(): void {
+  let passed = 0;
+  let failed = 0;
+
+  const test = (name: string, fn: () => void) => {
+    try {
+      fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('recall cache tests:\n');
+
+  // Test 1: a full cache with a poor hit rate grows until the max
+  test('low hit rate doubles capacity up to the max', () => {
+    let capacity = 100;
+    const seen: number[] = [];
+    for (let window = 0; window < 4; window++) {
+      capacity = nextCacheCapacity(capacity, calm, bounds);
+      seen.push(capacity);
+    }
+    assert(seen.join(',') === '200,400,400,400', `expected growth capped at 400, got ${seen.join(',')}`);
+  });
+
+  // Test 2: a cache that is not full, or already hits, keeps its size
+  test('healthy or under-filled caches stay put', () => {
+    assert(nextCacheCapacity(100, { ...calm, full: false }, bounds) === 100, 'a cache with room should not grow');
+    assert(nextCacheCapacity(100, { ...calm, hitRate: 0.6 }, bounds) === 100, 'a good hit rate should not grow');
+  });
+
+  // Test 3: heap pressure halves capacity, even with a poor hit rate
+  test('memory pressure shrinks capacity down to the min', () => {
+    const pressured = { ...calm, heapUsedRatio: 0.9 };
+    let capacity = 400;
+    const seen: number[] = [];
+    for (let window = 0; window < 4; window++) {
+      capacity = nextCacheCapacity(capacity, pressured, bounds);
+      seen.push(capacity);
+    }
+    assert(seen.join(',') === '200,100,50,50', `expected shrink floored at 50, got ${seen.join(',')}`);
+  });
+
+  // Test 4: out-of-range starting sizes are pulled into the bounds
+  test('capacity is clamped into the configured bounds', () => {
+    assert(nextCacheCapacity(10, { ...calm, full: false }, bounds) === 50, 'below min should clamp up');
+    assert(nextCacheCapacity(1000, { ...calm, full: false }, bounds) === 400, 'above max should clamp down');
+  });
+
+  // Test 5: an entry is served until the earliest expiry among its chunks, then dropped
+  test('entries end at the earliest chunk expiry', () => {
+    clearRecallCache();
+    setCachedRecall('lasting', [cachedResult]);
+    setCachedRecall('fresh', [cachedResult], new Date(Date.now() + 60_000).toISOString());
+    setCachedRecall('expired', [cachedResult], new Date(Date.now() - 1).toISOString());
+    assert(getCachedRecall('lasting')?.length === 1, 'an entry without expiring chunks should be served');
+    assert(getCachedRecall('fresh')?.length === 1, 'an entry before its expiry should be served');
+    assert(getCachedRecall('expired') === undefined, 'an entry past its expiry should miss');
+    assert(getCachedRecall('expired') === undefined, 'an expired entry should stay gone');
+    clearRecallCache();
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+runTests();
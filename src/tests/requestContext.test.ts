@@ -0,0 +1,75 @@
+// src/tests/requestContext.test.ts
+import { resolveRequestId, runWithRequestId, currentRequestId, installRequestIdLogging } from '../server/requestContext';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+const sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
+// Route console.log through a switchable capture before the prefixing wrapper is installed
+const realLog = console.log.bind(console);
+let captured: unknown[][] | null = null;
+console.log = (...args: unknown[]) => {
+  if (captured) captured.push(args);
+  else realLog(...args);
+};
+installRequestIdLogging();
+
+async function runTests(): Promise<void> {
+  let passed = 0;
+  let failed = 0;
+
+  const test = async (name: string, fn: () => Promise<void>) => {
+    try {
+      await fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('request context tests:\n');
+
+  // Test 1: caller ids are kept only when they are plain tokens
+  await test('caller ids are validated', async () => {
+    assert(resolveRequestId('abc-123') === 'abc-123', 'a plain id should be kept');
+    assert(resolveRequestId(['first', 'second']) === 'first', 'the first header value should win');
+    assert(resolveRequestId('bad id\nx') !== 'bad id\nx', 'ids with whitespace should be replaced');
+    assert(/^[0-9a-f-]{36}$/.test(resolveRequestId(undefined)), 'a missing id should become a uuid');
+  });
+
+  // Test 2: the id survives awaits and concurrent requests stay apart
+  await test('ids flow across awaits without leaking', async () => {
+    const seen = await Promise.all(['one', 'two'].map(id => runWithRequestId(id, async () => {
+      await sleep(id === 'one' ? 10 : 1);
+      return currentRequestId();
+    })));
+    assert(seen.join(',') === 'one,two', `expected one,two, got ${seen.join(',')}`);
+    assert(currentRequestId() === undefined, 'no id outside a request');
+  });
+
+  // Test 3: log lines inside a request carry its id
+  await test('console output is prefixed inside a request', async () => {
+    captured = [];
+    await runWithRequestId('trace-me', async () => {
+      await sleep(1);
+      console.log('inside');
+    });
+    console.log('outside');
+    const lines = captured;
+    captured = null;
+    assert(lines[0]?.[0] === '[req trace-me]' && lines[0]?.[1] === 'inside', 'expected the request id prefix');
+    assert(lines[1]?.length === 1 && lines[1][0] === 'outside', 'expected no prefix outside a request');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+void runTests();
@@ -0,0 +1,91 @@
+// src/tests/retry.test.ts
+import { withStoreRetry, isTransientStoreError } from '../db/retry';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+// Fails with `error` for the first `failures` calls, then resolves
+function flakyStore(failures: number, error: unknown): { write: () => Promise<string>; calls: () => number } {
+  let calls = 0;
+  return {
+    write: async () => {
+      calls++;
+      if (calls <= failures) throw error;
+      return 'stored';
+    },
+    calls: () => calls,
+  };
+}
+
+const unavailable = Object.assign(new Error('Service Unavailable'), { status: 503 });
+
+async function runTests(): Promise<void> {
+  let passed = 0;
+  let failed = 0;
+
+  const test = async (name: string, fn: () => Promise<void>) => {
+    try {
+      await fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('store retry tests:\n');
+
+  // Test 1: two transient failures fit in a budget of three
+  await test('a store that fails twice succeeds on the third try', async () => {
+    const store = flakyStore(2, unavailable);
+    const delays: number[] = [];
+    const result = await withStoreRetry(store.write, { attempts: 3, baseDelayMs: 10, sleep: async ms => { delays.push(ms); } });
+    assert(result === 'stored', 'expected the write to succeed');
+    assert(store.calls() === 3, `expected 3 calls, got ${store.calls()}`);
+    assert(delays.join(',') === '10,20', `expected exponential backoff, got ${delays.join(',')}`);
+  });
+
+  // Test 2: the budget is a hard limit
+  await test('the last error surfaces once attempts run out', async () => {
+    const store = flakyStore(5, unavailable);
+    let thrown: unknown = null;
+    try {
+      await withStoreRetry(store.write, { attempts: 3, baseDelayMs: 0, sleep: async () => {} });
+    } catch (error) {
+      thrown = error;
+    }
+    assert(thrown === unavailable, 'expected the store error to be rethrown');
+    assert(store.calls() === 3, `expected 3 calls, got ${store.calls()}`);
+  });
+
+  // Test 3: permanent errors are not retried
+  await test('non-transient errors fail immediately', async () => {
+    const store = flakyStore(1, Object.assign(new Error('Bad Request'), { status: 400 }));
+    let thrown = false;
+    try {
+      await withStoreRetry(store.write, { attempts: 3, baseDelayMs: 0, sleep: async () => {} });
+    } catch {
+      thrown = true;
+    }
+    assert(thrown && store.calls() === 1, 'a 400 should not be retried');
+  });
+
+  // Test 4: classification
+  await test('transient errors are recognised', async () => {
+    assert(isTransientStoreError(unavailable), '503 is transient');
+    assert(isTransientStoreError(Object.assign(new Error('x'), { status: 429 })), '429 is transient');
+    assert(isTransientStoreError(Object.assign(new Error('reset'), { code: 'ECONNRESET' })), 'ECONNRESET is transient');
+    assert(isTransientStoreError(new TypeError('fetch failed', { cause: Object.assign(new Error('refused'), { code: 'ECONNREFUSED' }) })), 'wrapped network errors are transient');
+    assert(!isTransientStoreError(new Error('Wrong input: Vector dimension error')), 'validation errors are not');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+void runTests();
@@ -0,0 +1,106 @@
+// src/tests/shutdown.test.ts
+import http from 'http';
+import { AddressInfo } from 'net';
+import { stopHttpServer } from '../server/shutdown';
+import { ingestSseConnections, statsSseConnections } from '../server/sse';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+// A server with an SSE endpoint (never ends by itself) and a request that hangs
+function startTestServer(): Promise<http.Server> {
+  const server = http.createServer((req, res) => {
+    if (req.url === '/stream') {
+      res.writeHead(200, { 'Content-Type': 'text/event-stream' });
+      res.write('data: {}\n\n');
+      statsSseConnections.add(res);
+      req.on('close', () => statsSseConnections.delete(res));
+      return;
+    }
+    if (req.url === '/hang') {
+      return;
+    }
+    res.end('ok');
+  });
+  return new Promise(resolve => server.listen(0, '127.0.0.1', () => resolve(server)));
+}
+
+// Open a stream and resolve once its first event has arrived; `ended` settles when the server closes it
+function openStream(server: http.Server): Promise<{ ended: Promise<void> }> {
+  const { port } = server.address() as AddressInfo;
+  return new Promise((resolve, reject) => {
+    const req = http.get({ host: '127.0.0.1', port, path: '/stream' }, (res) => {
+      const ended = new Promise<void>(done => {
+        res.on('end', () => done());
+        res.on('close', () => done());
+      });
+      res.once('data', () => resolve({ ended }));
+      res.resume();
+    });
+    req.on('error', reject);
+  });
+}
+
+async function runTests(): Promise<void> {
+  let passed = 0;
+  let failed = 0;
+
+  const test = async (name: string, fn: () => Promise<void>) => {
+    try {
+      await fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('shutdown tests:\n');
+
+  // Test 1: an open SSE client is closed and the server stops listening
+  await test('open streams are closed and the server stops', async () => {
+    const server = await startTestServer();
+    const client = await openStream(server);
+    assert(statsSseConnections.size === 1, 'stream should be registered');
+
+    await stopHttpServer(server, 1000);
+    await client.ended;
+    assert(!server.listening, 'server should no longer listen');
+    assert(statsSseConnections.size === 0 && ingestSseConnections.size === 0, 'stream registries should be empty');
+  });
+
+  // Test 2: a request that never answers is cut off after the grace period
+  await test('hanging requests are dropped after the grace period', async () => {
+    const server = await startTestServer();
+    const { port } = server.address() as AddressInfo;
+    const aborted = new Promise<void>(resolve => {
+      const req = http.get({ host: '127.0.0.1', port, path: '/hang' });
+      req.on('error', () => resolve());
+    });
+    await new Promise(resolve => setTimeout(resolve, 50));
+
+    const started = Date.now();
+    await stopHttpServer(server, 100);
+    await aborted;
+    const elapsed = Date.now() - started;
+    assert(elapsed >= 90 && elapsed < 1000, `expected shutdown near the 100ms grace, took ${elapsed}ms`);
+  });
+
+  // Test 3: stopping a server that is not listening resolves right away
+  await test('stopping a closed server is a no-op', async () => {
+    const server = await startTestServer();
+    await stopHttpServer(server, 100);
+    await stopHttpServer(server, 100);
+    assert(!server.listening, 'server should stay stopped');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+void runTests();
@@ -0,0 +1,77 @@
+// src/tests/textSearch.test.ts
+import { levenshtein, matchQuality, candidateTokenQuery } from '../retrieve/textSearch';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+function runTests(): void {
+  let passed = 0;
+  let failed = 0;
+
+  const test = (name: string, fn: () => void) => {
+    try {
+      fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('textSearch tests:\n');
+
+  // Test 1: edit distance basics
+  test('computes Levenshtein distance', () => {
+    assert(levenshtein('kitten', 'sitting') === 3, 'kitten -> sitting is 3 edits');
+    assert(levenshtein('', 'abc') === 3, 'empty -> abc is 3 inserts');
+    assert(levenshtein('same', 'same') === 0, 'identical strings have distance 0');
+  });
+
+  // Test 2: substring mode
+  test('substring matches anywhere, case-insensitively', () => {
+    assert(matchQuality('The Hippocampus indexes memories', 'hippocampus', { kind: 'substring' }) !== null, 'should match mid-text');
+    assert(matchQuality('Cortex only', 'hippocampus', { kind: 'substring' }) === null, 'should not match absent text');
+    const dense = matchQuality('hippocampus', 'hippocampus', { kind: 'substring' }) ?? 0;
+    const sparse = matchQuality('a long passage that mentions the hippocampus once', 'hippocampus', { kind: 'substring' }) ?? 0;
+    assert(dense > sparse, 'denser matches should rank higher');
+  });
+
+  // Test 3: prefix mode
+  test('prefix matches only at the start', () => {
+    assert(matchQuality('Memory consolidation during sleep', 'memory cons', { kind: 'prefix' }) !== null, 'should match leading text');
+    assert(matchQuality('Sleep aids memory consolidation', 'memory', { kind: 'prefix' }) === null, 'should not match mid-text');
+  });
+
+  // Test 4: fuzzy mode within budget
+  test('fuzzy matches a misspelling within the distance budget', () => {
+    const quality = matchQuality('Notes on the hippocampus and recall', 'hipocampus', { kind: 'fuzzy', maxDistance: 1 });
+    assert(quality !== null, 'one-edit typo should match with maxDistance=1');
+    assert(matchQuality('Notes on the hippocampus and recall', 'hipocmpus', { kind: 'fuzzy', maxDistance: 1 }) === null, 'two-edit typo should exceed maxDistance=1');
+    const exact = matchQuality('hippocampus', 'hippocampus', { kind: 'fuzzy', maxDistance: 2 }) ?? 0;
+    assert(exact > (quality ?? 0), 'exact matches should outrank fuzzy ones');
+  });
+
+  // Test 5: fuzzy multi-word windows
+  test('fuzzy compares multi-word queries against word windows', () => {
+    assert(matchQuality('long term potentiation strengthens synapses', 'long-term potentiation', { kind: 'fuzzy', maxDistance: 1 }) !== null, 'hyphen vs space is one edit');
+  });
+
+  // Test 6: token pre-filter for multi-word queries
+  test('builds a token pre-filter only from words that must appear whole', () => {
+    assert(candidateTokenQuery('emory consolidation dur', { kind: 'substring' }) === '"consolidation" AND "dur"*', 'edge words are partial, inner words whole');
+    assert(candidateTokenQuery('memory cons', { kind: 'prefix' }) === '"memory" AND "cons"*', 'prefix mode keeps the first word whole');
+    assert(candidateTokenQuery('a "quoted" b', { kind: 'substring' }) === '"""quoted""" AND "b"*', 'quotes are escaped');
+    assert(candidateTokenQuery('hippocampus', { kind: 'substring' }) === null, 'single words use LIKE instead');
+    assert(candidateTokenQuery('long term', { kind: 'fuzzy', maxDistance: 1 }) === null, 'fuzzy cannot be narrowed by tokens');
+  });
+
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+runTests();
@@ -0,0 +1,78 @@
+// src/tests/timeout.test.ts
+import * as grpc from '@grpc/grpc-js';
+import { withUnaryTimeout } from '../server/timeout';
+
+function assert(condition: boolean, message: string): void {
+  if (!condition) {
+    throw new Error(`FAIL: ${message}`);
+  }
+}
+
+const sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
+type Outcome = { error: grpc.ServiceError | null; value: unknown };
+
+// Invoke a wrapped unary handler and collect every callback it makes
+function invoke(handler: (call: unknown, callback: grpc.sendUnaryData<unknown>) => void): Outcome[] {
+  const outcomes: Outcome[] = [];
+  handler({}, (error, value) => outcomes.push({ error: error as grpc.ServiceError | null, value }));
+  return outcomes;
+}
+
+const answerAfter = (ms: number) => (_call: unknown, callback: grpc.sendUnaryData<unknown>) => {
+  setTimeout(() => callback(null, 'done'), ms);
+};
+
+// Silence the timeout log while the tests run
+const realError = console.error;
+console.error = () => {};
+
+async function runTests(): Promise<void> {
+  let passed = 0;
+  let failed = 0;
+
+  const test = async (name: string, fn: () => Promise<void>) => {
+    try {
+      await fn();
+      console.log(`  ✅ ${name}`);
+      passed++;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      console.log(`  ❌ ${name}: ${message}`);
+      failed++;
+    }
+  };
+
+  console.log('unary timeout tests:\n');
+
+  // Test 1: a fast handler answers normally
+  await test('fast handlers pass their response through', async () => {
+    const { Fast } = withUnaryTimeout({ Fast: answerAfter(5) }, 100);
+    const outcomes = invoke(Fast);
+    await sleep(150);
+    assert(outcomes.length === 1 && outcomes[0].error === null && outcomes[0].value === 'done', 'expected the handler response');
+  });
+
+  // Test 2: a slow handler gets DEADLINE_EXCEEDED, and its late answer is dropped
+  await test('slow handlers fail with DEADLINE_EXCEEDED', async () => {
+    const { Slow } = withUnaryTimeout({ Slow: answerAfter(150) }, 30);
+    const outcomes = invoke(Slow);
+    await sleep(60);
+    assert(outcomes.length === 1 && outcomes[0].error?.code === grpc.status.DEADLINE_EXCEEDED, 'expected DEADLINE_EXCEEDED');
+    assert(outcomes[0].error?.message.includes('Slow') === true, 'expected the method name in the message');
+    await sleep(150);
+    assert(outcomes.length === 1, 'expected the late response to be dropped');
+  });
+
+  // Test 3: a timeout of 0 leaves handlers untouched
+  await test('a zero timeout disables the wrapper', async () => {
+    const handlers = { Slow: answerAfter(10) };
+    assert(withUnaryTimeout(handlers, 0) === handlers, 'expected the same handlers back');
+  });
+
+  console.error = realError;
+  console.log(`\n${passed} passed, ${failed} failed`);
+  if (failed > 0) process.exit(1);
+}
+
+void runTests();